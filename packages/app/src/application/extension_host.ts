@@ -16,15 +16,18 @@ import type {
     InvView_PouchList,
     InvView_Gdt,
     InvView_Overworld,
+    StepRunEntry,
 } from "@pistonite/skybook-api";
 import {
     searchItemLocalized,
     translateParserError,
     translateRuntimeError,
+    translateUI,
 } from "skybook-localization";
 import { getActorParam } from "skybook-item-system";
 
 import { useSessionStore } from "./session_store.ts";
+import { usePersistStore } from "./persist_store.ts";
 
 /**
  * This is the host that handles function calls from Extensions using the ExtensionApp API
@@ -56,6 +59,16 @@ class ExtensionAppHost implements ExtensionApp {
         useSessionStore.getState().setActiveScript(script, charPos);
         return {};
     }
+    public async getIncludedScripts() {
+        return { val: usePersistStore.getState().includedScripts };
+    }
+    public async setIncludedScript(
+        path: string,
+        script: string | undefined,
+    ) {
+        usePersistStore.getState().setIncludedScript(path, script);
+        return {};
+    }
     public async resolveItem(
         query: string,
         localized: boolean,
@@ -202,6 +215,20 @@ class ExtensionAppHost implements ExtensionApp {
         });
     }
 
+    public async provideStepRunDiagnostics(
+        script: string,
+        taskId: string,
+    ): WxPromise<MaybeAborted<Diagnostic[]>> {
+        const bytePositions = await this.runtime.getStepBytePositions(script);
+        if (bytePositions.err) {
+            return bytePositions;
+        }
+        const result = await this.runtime.getStepRuns(script, taskId, -1);
+        return mapMaybeAbortedResult(result, (value) => {
+            return stepRunsToDiagnostics(script, bytePositions.val, value);
+        });
+    }
+
     public async getPouchList(
         taskId: string,
         inputScript: string | undefined,
@@ -287,17 +314,70 @@ const errorReportsToDiagnostics = <T>(
     translator: (error: T) => string,
 ): Diagnostic[] => {
     const bytePosToCharPos = createBytePosToCharPosArray(script);
-    return reports.map(({ span, error, isWarning }) => {
+    // spans of errors in included scripts are not in this script,
+    // parser and runtime errors in those are also reported at the `include`
+    reports = reports.filter(({ file }) => file === 0);
+    return reports.map(({ span, error, isWarning, suggestions }) => {
         const [start, end] = span;
         return {
             message: translator(error),
             isWarning,
+            isInfo: false,
+            suggestions,
             start: bytePosToCharPos[start],
             end: bytePosToCharPos[end],
         };
     });
 };
 
+/**
+ * Show the branch taken by each `if` as information diagnostics
+ *
+ * Steps expanded from the same statement have the same position,
+ * so the branches of all `if`s at the same position are shown together
+ */
+const stepRunsToDiagnostics = (
+    script: string,
+    bytePositions: Uint32Array,
+    runs: StepRunEntry[],
+): Diagnostic[] => {
+    const bytePosToCharPos = createBytePosToCharPosArray(script);
+    const diagnostics: Diagnostic[] = [];
+    const len = Math.min(bytePositions.length, runs.length);
+    let i = 0;
+    while (i < len) {
+        const bytePos = bytePositions[i];
+        const messages: string[] = [];
+        for (; i < len && bytePositions[i] === bytePos; i++) {
+            const run = runs[i];
+            if (run.type !== "branch") {
+                continue;
+            }
+            if (run.branch === null) {
+                messages.push(translateUI("step_run.branch_unknown"));
+            } else {
+                messages.push(
+                    translateUI("step_run.branch", { branch: run.branch + 1 }),
+                );
+            }
+        }
+        if (!messages.length) {
+            continue;
+        }
+        const start = bytePosToCharPos[bytePos];
+        const lineEnd = script.indexOf("\n", start);
+        diagnostics.push({
+            message: messages.join("\n"),
+            isWarning: false,
+            isInfo: true,
+            suggestions: [],
+            start,
+            end: lineEnd === -1 ? script.length : lineEnd,
+        });
+    }
+    return diagnostics;
+};
+
 const mapMaybeAbortedResult = <TIn, TOut>(
     result: Awaited<WxPromise<MaybeAborted<TIn>>>,
     fn: (t: TIn) => TOut,
@@ -21,6 +21,14 @@ export type PersistStore = {
     savedScript: string;
     setSavedScript: (script: string) => void;
 
+    /**
+     * Scripts that can be included with `include "PATH"`,
+     * from the path to the content of the script
+     */
+    includedScripts: Record<string, string>;
+    /** Add or replace an included script, or remove it if `script` is undefined */
+    setIncludedScript: (path: string, script: string | undefined) => void;
+
     /**
      * The previously stored custom image version ("1.5" or "1.6"). Empty if no custom image
      */
@@ -57,6 +65,18 @@ export const usePersistStore = create<PersistStore>()(
                         env.image ? "1" : "",
                     );
                 },
+                includedScripts: {},
+                setIncludedScript: (path, script) => {
+                    set((state) => {
+                        const includedScripts = { ...state.includedScripts };
+                        if (script === undefined) {
+                            delete includedScripts[path];
+                        } else {
+                            includedScripts[path] = script;
+                        }
+                        return { includedScripts };
+                    });
+                },
                 customImageVersion: "",
                 setCustomImageVersion: (version) => {
                     if (version) {
@@ -137,6 +137,19 @@ export const bootstrapAppWithRuntime = (runtime: Runtime): void => {
             void triggerSimulationAndUpdateState(scriptChanged);
         }
     });
+
+    // provide the included scripts before the first run, and
+    // run the script again when they change
+    void runtime.setIncludedScripts(usePersistStore.getState().includedScripts);
+    usePersistStore.subscribe((curr, prev) => {
+        if (curr.includedScripts === prev.includedScripts) {
+            return;
+        }
+        void (async () => {
+            await runtime.setIncludedScripts(curr.includedScripts);
+            void triggerSimulationAndUpdateState(true);
+        })();
+    });
 };
 
 /** (runtime-side) initialization with the given arguments, return localized error message on error */
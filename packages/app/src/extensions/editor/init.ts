@@ -8,6 +8,7 @@ import {
     provideParserDiagnostics,
     type Provider,
     provideRuntimeDiagnostics,
+    provideStepRunDiagnostics,
 } from "./marker.ts";
 import { legend, provideSemanticTokens } from "./semantic.ts";
 
@@ -40,6 +41,7 @@ const CustomLanguageOptions: LanguageClient = {
     getDiagnosticProviders: () => [
         ParserDiagnosticProvider,
         RuntimeDiagnosticProvider,
+        StepRunDiagnosticProvider,
     ],
     getSemanticTokensProvider: () => {
         return {
@@ -76,6 +78,18 @@ export const RuntimeDiagnosticProvider: Provider = {
     mergeData: mergeDataByReplace,
 };
 
+/** Show which branch of each `if` is taken */
+export const StepRunDiagnosticProvider: Provider = {
+    ownerId: "step-run",
+    newRequest: async (_filename, _model, script) => {
+        if (!theApp) {
+            return [];
+        }
+        return await provideStepRunDiagnostics(theApp, script);
+    },
+    mergeData: mergeDataByReplace,
+};
+
 /** Token colors for special tokens in skybook script */
 const CustomTokenColors = [
     {
@@ -65,6 +65,7 @@ export const language: LanguageTokenizer = {
             [/<@word>/, "string.item.literal"],
             [/"[^"]*"/, "string.item.quoted"],
             [/!@word/, "function.command.super"],
+            [/\$@word/, "variable"],
             [/(true|false)/, "constant.language.boolean"],
             [
                 /@word/,
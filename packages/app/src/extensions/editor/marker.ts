@@ -19,6 +19,9 @@ import { logger } from "@pistonite/pure/log";
 import type { Diagnostic, ExtensionApp } from "@pistonite/skybook-api";
 
 const EDITOR_EXTENSION_UUID = "b1b45de4-1df7-4832-ae0b-99b516f81df6";
+// separate from the runtime diagnostics, since requesting
+// new task ids cancels the previous ones with the same key
+const STEP_RUN_TASK_ID_KEY = `${EDITOR_EXTENSION_UUID}-step-run`;
 
 const log = logger("diagnostics", "#02648B").default();
 
@@ -128,6 +131,37 @@ export const provideParserDiagnostics = async (
     ];
 };
 
+export const provideStepRunDiagnostics = async (
+    app: ExtensionApp,
+    script: string,
+): Promise<Task[]> => {
+    const taskIdResult = await app.requestNewTaskIds(STEP_RUN_TASK_ID_KEY, 1);
+    if (taskIdResult.err) {
+        log.error("failed to get taskIds for step run diagnostics");
+        return [];
+    }
+    const taskId = taskIdResult.val[0];
+    return [
+        {
+            data: (async () => {
+                const result = await app.provideStepRunDiagnostics(
+                    script,
+                    taskId,
+                );
+                if (result.val?.type === "Aborted") {
+                    return undefined;
+                }
+                if (result.err) {
+                    log.error("failed to get step run diagnostics");
+                    log.error(result.err);
+                    return undefined;
+                }
+                return result.val.value;
+            })(),
+        },
+    ];
+};
+
 export const provideRuntimeDiagnostics = async (
     app: ExtensionApp,
     script: string,
@@ -213,12 +247,16 @@ const convertDiagnosticToMarker = (
     model: TextModel,
     diagnostic: Diagnostic,
 ): CustomMarkerData => {
-    const { message, isWarning, start, end } = diagnostic;
+    const { message, isWarning, isInfo, start, end } = diagnostic;
     const range = spanToRange(model, start, end);
+    let severity = isWarning ? MarkerSeverity.Warning : MarkerSeverity.Error;
+    if (isInfo) {
+        severity = MarkerSeverity.Info;
+    }
     return {
         charPos: [start, end],
         message,
-        severity: isWarning ? MarkerSeverity.Warning : MarkerSeverity.Error,
+        severity,
         ...range,
     };
 };
@@ -1,5 +1,6 @@
 import { Text, Button, Tooltip } from "@fluentui/react-components";
 import {
+    Chat20Regular,
     DesktopOff20Regular,
     PersonRunning20Regular,
     ShoppingBagPause20Regular,
@@ -36,6 +37,10 @@ export const ScreenIndicator: React.FC<ScreenIndicatorProps> = ({
             icon = <ShoppingBag20Regular />;
             break;
         }
+        case "dialog": {
+            icon = <Chat20Regular />;
+            break;
+        }
         default: {
             icon = <DesktopOff20Regular />;
         }
@@ -9,6 +9,9 @@ const DEFAULT_USE = PouchItemUse.Item;
 const typeMap = {};
 const useMap = {};
 const lifeMap = {};
+const sellingPriceMap = {};
+const buyingPriceMap = {};
+const upgradeMaterialsMap = {};
 const canStackActors = new Set<string>();
 const cannotSellActors = new Set<string>();
 const canUseActors = new Set<string>();
@@ -26,6 +29,18 @@ for (const actor in ActorDataMap) {
         const life = getActorParam(actor, "generalLife");
         lifeMap[actor] = life;
     }
+    const sellingPrice = getActorParam(actor, "itemSellingPrice");
+    if (sellingPrice >= 0) {
+        sellingPriceMap[actor] = sellingPrice;
+    }
+    const buyingPrice = getActorParam(actor, "itemBuyingPrice");
+    if (buyingPrice >= 0) {
+        buyingPriceMap[actor] = buyingPrice;
+    }
+    const upgradeMaterials = getActorParam(actor, "armorUpgradeMaterials");
+    if (upgradeMaterials.length) {
+        upgradeMaterialsMap[actor] = upgradeMaterials;
+    }
     const canStack = getActorParam(actor, "canStack");
     if (canStack) {
         canStackActors.add(actor);
@@ -43,6 +58,9 @@ for (const actor in ActorDataMap) {
 console.log(Object.keys(typeMap).length, "actors found with non-default type");
 console.log(Object.keys(useMap).length, "actors found with non-default use");
 console.log(Object.keys(lifeMap).length, "weapon actor life values found");
+console.log(Object.keys(sellingPriceMap).length, "actor selling prices found");
+console.log(Object.keys(buyingPriceMap).length, "actor buying prices found");
+console.log(Object.keys(upgradeMaterialsMap).length, "upgradable armors found");
 console.log(canStackActors.size, "stackable actors found");
 console.log(cannotSellActors.size, "non-sellable actors found");
 console.log(canUseActors.size, "eatable actors found");
@@ -71,6 +89,28 @@ for (const actor in lifeMap) {
 }
 lines.push("};");
 
+lines.push("#[rustfmt::skip]");
+lines.push("pub static SELLING_PRICE_MAP: phf::Map<&'static str, i32> = phf::phf_map! {");
+for (const actor in sellingPriceMap) {
+    lines.push(`    "${actor}" => ${sellingPriceMap[actor]},`);
+}
+lines.push("};");
+
+lines.push("#[rustfmt::skip]");
+lines.push("pub static BUYING_PRICE_MAP: phf::Map<&'static str, i32> = phf::phf_map! {");
+for (const actor in buyingPriceMap) {
+    lines.push(`    "${actor}" => ${buyingPriceMap[actor]},`);
+}
+lines.push("};");
+
+lines.push("#[rustfmt::skip]");
+lines.push("pub static ARMOR_UPGRADE_MATERIALS_MAP: phf::Map<&'static str, &'static [(&'static str, i32)]> = phf::phf_map! {");
+for (const actor in upgradeMaterialsMap) {
+    const materials = upgradeMaterialsMap[actor].map(([name, num]) => `("${name}", ${num})`);
+    lines.push(`    "${actor}" => &[${materials.join(", ")}],`);
+}
+lines.push("};");
+
 lines.push("#[rustfmt::skip]");
 lines.push("pub static STACKABLE_ACTORS_SORTED: &[&str] = &[");
 const canStackActorsSorted = [...canStackActors];
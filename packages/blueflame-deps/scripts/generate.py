@@ -46,6 +46,8 @@ def main():
     generate_flag_function("Vector3f.yaml", lines, max_lengths)
     generate_flag_function("Vector4f.yaml", lines, max_lengths)
 
+    generate_flag_names(lines)
+
     lines += [
         "#[rustfmt::skip]",
         "static FALSE_BOOL: &[bool] = &[" + ", ".join(["false"] * max_lengths["FALSE_BOOL"]) + "];",
@@ -103,6 +105,42 @@ def generate_flag_function(file: str, lines: list[str], max_lengths):
     lines += static_values
     progress.done()
 
+FLAG_FILES = [
+    "ArrayBool.yaml",
+    "ArrayS32.yaml",
+    "ArrayF32.yaml",
+    "ArrayString64.yaml",
+    "ArrayString256.yaml",
+    "ArrayVector2f.yaml",
+    "ArrayVector3f.yaml",
+    "Bool.yaml",
+    "F32.yaml",
+    "S32.yaml",
+    "String32.yaml",
+    "String64.yaml",
+    "String256.yaml",
+    "Vector2f.yaml",
+    "Vector3f.yaml",
+    "Vector4f.yaml",
+]
+
+def generate_flag_names(lines: list[str]):
+    """Generate the sorted list of all flag names, used for suggestions"""
+    names = set()
+    for file in FLAG_FILES:
+        with open(os.path.join(RESEARCH_SCRIPTS_DIR, "output", "GameData", file), "r", encoding="utf-8") as f:
+            entries = yaml.safe_load(f)
+        for entry in entries:
+            names.add(entry["name"])
+    lines += [
+        "#[rustfmt::skip]",
+        "/// Names of all flags in the game data, sorted",
+        "pub static FLAG_NAMES: &[&str] = &[",
+    ]
+    for name in sorted(names):
+        lines.append(f"    \"{name}\",")
+    lines.append("];")
+
 def generate_flag_entry(entry, typ, is_array, max_lengths, static_values):
     name = entry["name"]
     hash = entry["hash"]
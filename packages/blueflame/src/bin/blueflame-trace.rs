@@ -0,0 +1,128 @@
+//! Read and filter a trace recorded by the BlueFlame tracer
+//!
+//! Usage: blueflame-trace TRACE_FILE [OPTIONS]
+//!
+//! Options:
+//!   --type TYPE        Only show events of the type (call, return, mem-write, gdt).
+//!                      Can be specified multiple times
+//!   --addr START[:END] Only show memory writes to the address range (hex).
+//!                      If END is not specified, the range is 8 bytes
+//!   --flag NAME        Only show GDT accesses to the flag (name or 0x-prefixed hash)
+//!   --within OFFSET    Only show events inside calls to the function at the
+//!                      main offset (hex)
+//!   --stack            Print the call stack with each event
+//!   --symbols FILE     Show function names from the symbol map
+//!                      (CSV, IDA .map or Ghidra export)
+//!   --jsonl            Output as JSONL instead of text
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::process::ExitCode;
+
+use blueflame::env::SymbolMap;
+use blueflame::trace::{TraceFilter, TraceReader, format_main_offset_with_symbols};
+
+fn main() -> ExitCode {
+    match main_internal() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main_internal() -> Result<(), String> {
+    let mut args = std::env::args().skip(1);
+    let mut path = None;
+    let mut filter = TraceFilter::default();
+    let mut print_stack = false;
+    let mut jsonl = false;
+    let mut symbols = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--type" => filter.types.push(next_arg(&mut args, &arg)?),
+            "--addr" => {
+                let value = next_arg(&mut args, &arg)?;
+                let (start, end) = match value.split_once(':') {
+                    Some((start, end)) => (parse_hex(start)?, parse_hex(end)?),
+                    None => {
+                        let start = parse_hex(&value)?;
+                        (start, start + 8)
+                    }
+                };
+                filter.addr = Some(start..end);
+            }
+            "--flag" => filter.flag = Some(next_arg(&mut args, &arg)?),
+            "--within" => {
+                let value = next_arg(&mut args, &arg)?;
+                filter.within = Some(parse_hex(&value)? as i64);
+            }
+            "--stack" => print_stack = true,
+            "--symbols" => {
+                let symbol_path = next_arg(&mut args, &arg)?;
+                let text = std::fs::read_to_string(&symbol_path)
+                    .map_err(|e| format!("failed to read {symbol_path}: {e}"))?;
+                let map = SymbolMap::parse(&text)
+                    .map_err(|e| format!("failed to parse {symbol_path}: {e}"))?;
+                symbols = Some(map);
+            }
+            "--jsonl" => jsonl = true,
+            _ if arg.starts_with("--") => return Err(format!("unknown option: {arg}")),
+            _ => path = Some(arg),
+        }
+    }
+    let Some(path) = path else {
+        return Err("usage: blueflame-trace TRACE_FILE [OPTIONS]".to_string());
+    };
+
+    let file = File::open(&path).map_err(|e| format!("failed to open {path}: {e}"))?;
+    let reader = TraceReader::new(BufReader::new(file)).map_err(|e| e.to_string())?;
+
+    let stdout = std::io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    if !jsonl {
+        let _ = writeln!(out, "main_start=0x{:016x}", reader.main_start());
+    }
+    for event in reader {
+        let event = event.map_err(|e| e.to_string())?;
+        if !filter.accept(&event) {
+            continue;
+        }
+        let result = if jsonl {
+            event.write_jsonl(&mut out).map_err(|e| e.to_string())
+        } else {
+            let name = symbols
+                .as_ref()
+                .zip(event.code_offset().and_then(|x| u32::try_from(x).ok()))
+                .and_then(|(symbols, main_offset)| symbols.symbolize(main_offset));
+            match name {
+                Some(name) => writeln!(out, "{event} <{name}>"),
+                None => writeln!(out, "{event}"),
+            }
+            .map_err(|e| e.to_string())
+        };
+        result?;
+        if print_stack && !jsonl {
+            for frame in filter.stack().iter().rev() {
+                let hook = frame
+                    .hook
+                    .as_deref()
+                    .map(|x| format!(" [{x}]"))
+                    .unwrap_or_default();
+                let target = format_main_offset_with_symbols(frame.target, symbols.as_ref());
+                let _ = writeln!(out, "    at {target}{hook}");
+            }
+        }
+    }
+    out.flush().map_err(|e| e.to_string())
+}
+
+fn next_arg(args: &mut impl Iterator<Item = String>, name: &str) -> Result<String, String> {
+    args.next()
+        .ok_or_else(|| format!("missing value for {name}"))
+}
+
+fn parse_hex(s: &str) -> Result<u64, String> {
+    let s = s.trim_start_matches("0x");
+    u64::from_str_radix(s, 16).map_err(|e| format!("invalid hex number {s}: {e}"))
+}
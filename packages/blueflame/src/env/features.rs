@@ -1,3 +1,5 @@
+use std::cell::Cell;
+
 use enumset::EnumSetType;
 
 pub use blueflame_proc_macros::enabled;
@@ -6,10 +8,8 @@ pub use blueflame_proc_macros::enabled;
 #[doc(hidden)]
 pub use enumset;
 
-/// BlueFlame features that can be enabled or disabled at init time.
-/// They should not be changed after init
-///
-/// TODO manual
+/// BlueFlame features that can be enabled or disabled at init time,
+/// or by the `:features` annotation in scripts
 #[rustfmt::skip] // to be more readable
 #[allow(non_camel_case_types)] // to be more readable
 #[derive(Debug, Hash, EnumSetType)]
@@ -58,20 +58,26 @@ pub enum Feature {
 
 pub type FeatureSet = enumset::EnumSet<Feature>;
 
-static mut FEATURES: FeatureSet = Feature::default_const();
+thread_local! {
+    static FEATURES: Cell<FeatureSet> = const { Cell::new(Feature::default_const()) };
+}
 
-/// Set the features for the BlueFlame core
+/// Set the features for the BlueFlame core on the current thread
 ///
-/// # Safety
-/// The features is simply a static bitset in memory to allow
-/// for efficient reads. There is no locking. Initializing
-/// features flag should be done before using anything in the crate.
+/// The features are stored per-thread to allow for efficient reads
+/// without locking, while still allowing different threads to run
+/// with different features. The features should be set on the thread
+/// that is executing the core, before executing anything.
 ///
 /// Before this is called, the "default" set of features is used.
-pub unsafe fn init_features(features: FeatureSet) {
-    unsafe {
-        FEATURES = features;
-    }
+pub fn init_features(features: FeatureSet) {
+    FEATURES.set(features);
+}
+
+/// Get the features currently enabled on this thread
+#[inline(always)]
+pub fn current_features() -> FeatureSet {
+    FEATURES.get()
 }
 
 /// Check if a feature is enabled
@@ -82,10 +88,33 @@ pub unsafe fn init_features(features: FeatureSet) {
 /// to be consistent with the style used in scripts
 #[inline(always)]
 pub fn is_feature_enabled(feature: Feature) -> bool {
-    unsafe {
-        // SAFETY: we are just reading a number
-        // if people read the thing above, it will be safe
-        #[allow(static_mut_refs)]
-        FEATURES.contains(feature)
+    FEATURES.get().contains(feature)
+}
+
+/// Apply a list of feature toggles on top of `base`.
+///
+/// Each entry is a kebab-case feature name, optionally prefixed with `-`
+/// to turn the feature off, or `+` to turn it on. Returns the unknown name
+/// as the error if any feature cannot be recognized
+pub fn apply_feature_toggles<S: AsRef<str>>(
+    base: FeatureSet,
+    toggles: &[S],
+) -> Result<FeatureSet, String> {
+    let mut features = base;
+    for toggle in toggles {
+        let toggle = toggle.as_ref().trim();
+        let (name, on) = match toggle.strip_prefix('-') {
+            Some(name) => (name, false),
+            None => (toggle.strip_prefix('+').unwrap_or(toggle), true),
+        };
+        let Some(feature) = Feature::parse(name) else {
+            return Err(name.to_string());
+        };
+        if on {
+            features.insert(feature);
+        } else {
+            features.remove(feature);
+        }
     }
+    Ok(features)
 }
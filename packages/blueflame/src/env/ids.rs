@@ -21,12 +21,16 @@ use serde::{Deserialize, Serialize};
 pub enum DataId {
     /// Actor/ActorInfo.product.byml (decompressed version of the sbyml)
     ActorInfoByml,
+    /// Symbol map of the main module, in one of the formats supported by
+    /// [`SymbolMap::parse`](crate::env::SymbolMap::parse)
+    SymbolMap,
 }
 
 impl From<ArchivedDataId> for DataId {
     fn from(archived: ArchivedDataId) -> Self {
         match archived {
             ArchivedDataId::ActorInfoByml => DataId::ActorInfoByml,
+            ArchivedDataId::SymbolMap => DataId::SymbolMap,
         }
     }
 }
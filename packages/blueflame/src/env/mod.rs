@@ -4,5 +4,7 @@ mod features;
 pub use features::*;
 mod ids;
 pub use ids::*;
+mod symbols;
+pub use symbols::*;
 
 pub use blueflame_proc_macros::no_panic;
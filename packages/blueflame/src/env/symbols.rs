@@ -0,0 +1,340 @@
+use std::sync::{Arc, LazyLock, RwLock};
+
+use enum_map::EnumMap;
+
+use crate::env::GameVer;
+
+/// Default image base of the main module in IDA and Ghidra
+/// when loading the game's executable
+const DISASSEMBLER_MAIN_BASE: u64 = 0x71_0000_0000;
+
+/// A function symbol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    /// Offset of the function relative to the start of the main module
+    pub main_offset: u32,
+    /// Size of the function in bytes, 0 if unknown
+    pub size: u32,
+    /// Name of the function
+    pub name: String,
+}
+
+/// Map from main offsets to function names, used to symbolize
+/// stack traces, crash reports and execution traces
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolMap {
+    /// Symbols sorted by main offset
+    symbols: Vec<Symbol>,
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum SymbolMapError {
+    #[error("line {0}: invalid address `{1}`")]
+    InvalidAddress(usize, String),
+    #[error("line {0}: missing column `{1}`")]
+    MissingColumn(usize, &'static str),
+    #[error("the symbol map does not have any symbols")]
+    Empty,
+}
+
+impl SymbolMap {
+    /// Create the symbol map from a list of symbols
+    pub fn new(mut symbols: Vec<Symbol>) -> Self {
+        symbols.sort_by_key(|s| s.main_offset);
+        symbols.dedup_by_key(|s| s.main_offset);
+        Self { symbols }
+    }
+
+    /// Parse a symbol map, detecting the format automatically
+    ///
+    /// Supported formats are:
+    /// - IDA `.map` files (only symbols in the first segment,
+    ///   which is `.text` of the main module, are loaded)
+    /// - CSV with a header row, where the columns for address and name
+    ///   are `Address` (or `Location`) and `Name`, with an optional `Size` column.
+    ///   This includes the symbol table exported from Ghidra.
+    /// - CSV without a header row, where the columns are address, name
+    ///   and optionally the size
+    ///
+    /// Addresses are in hex, and can either be relative to the main module,
+    /// or absolute with the main module loaded at `0x7100000000`
+    /// (the default for IDA and Ghidra)
+    pub fn parse(text: &str) -> Result<Self, SymbolMapError> {
+        let map = if text.contains("Publics by Value") {
+            Self::parse_ida_map(text)?
+        } else {
+            Self::parse_csv(text)?
+        };
+        if map.is_empty() {
+            return Err(SymbolMapError::Empty);
+        }
+        Ok(map)
+    }
+
+    fn parse_ida_map(text: &str) -> Result<Self, SymbolMapError> {
+        let mut symbols = Vec::new();
+        let mut in_publics = false;
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.contains("Publics by Value") {
+                in_publics = true;
+                continue;
+            }
+            if !in_publics || line.is_empty() {
+                continue;
+            }
+            // 0001:00000000       nnMain
+            let Some((addr, name)) = line.split_once(char::is_whitespace) else {
+                continue;
+            };
+            let Some((segment, offset)) = addr.split_once(':') else {
+                continue;
+            };
+            if segment != "0001" {
+                continue;
+            }
+            let main_offset = parse_address(offset)
+                .ok_or_else(|| SymbolMapError::InvalidAddress(i + 1, offset.to_string()))?;
+            symbols.push(Symbol {
+                main_offset,
+                size: 0,
+                name: name.trim().to_string(),
+            });
+        }
+        Ok(Self::new(symbols))
+    }
+
+    fn parse_csv(text: &str) -> Result<Self, SymbolMapError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| (i + 1, split_csv_line(line)))
+            .peekable();
+        let Some((_, first)) = lines.peek() else {
+            return Ok(Self::default());
+        };
+        let has_header = first.first().and_then(|x| parse_address(x)).is_none();
+        let (addr_col, name_col, size_col) = if has_header {
+            let find = |names: &[&str]| {
+                first
+                    .iter()
+                    .position(|c| names.iter().any(|n| c.eq_ignore_ascii_case(n)))
+            };
+            let addr_col = find(&["address", "location"])
+                .ok_or(SymbolMapError::MissingColumn(1, "address"))?;
+            let name_col = find(&["name"]).ok_or(SymbolMapError::MissingColumn(1, "name"))?;
+            let size_col = find(&["size", "length"]);
+            lines.next();
+            (addr_col, name_col, size_col)
+        } else {
+            (0, 1, Some(2))
+        };
+
+        let mut symbols = Vec::new();
+        for (line_no, columns) in lines {
+            let addr = columns
+                .get(addr_col)
+                .ok_or(SymbolMapError::MissingColumn(line_no, "address"))?;
+            let name = columns
+                .get(name_col)
+                .ok_or(SymbolMapError::MissingColumn(line_no, "name"))?;
+            let main_offset = parse_address(addr)
+                .ok_or_else(|| SymbolMapError::InvalidAddress(line_no, addr.to_string()))?;
+            let size = size_col
+                .and_then(|c| columns.get(c))
+                .and_then(|x| parse_size(x))
+                .unwrap_or_default();
+            if name.is_empty() {
+                continue;
+            }
+            symbols.push(Symbol {
+                main_offset,
+                size,
+                name: name.to_string(),
+            });
+        }
+        Ok(Self::new(symbols))
+    }
+
+    /// Get the number of symbols
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Check if the map has no symbols
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Find the function containing the main offset, and the offset
+    /// into the function
+    pub fn lookup(&self, main_offset: u32) -> Option<(&Symbol, u32)> {
+        let i = match self
+            .symbols
+            .binary_search_by_key(&main_offset, |s| s.main_offset)
+        {
+            Ok(i) => i,
+            Err(0) => return None,
+            Err(i) => i - 1,
+        };
+        let symbol = &self.symbols[i];
+        let off = main_offset - symbol.main_offset;
+        if symbol.size != 0 && off >= symbol.size {
+            return None;
+        }
+        Some((symbol, off))
+    }
+
+    /// Format the main offset as `name+0xoff`, or `None` if not
+    /// in a known function
+    pub fn symbolize(&self, main_offset: u32) -> Option<String> {
+        let (symbol, off) = self.lookup(main_offset)?;
+        if off == 0 {
+            Some(symbol.name.clone())
+        } else {
+            Some(format!("{}+0x{off:x}", symbol.name))
+        }
+    }
+}
+
+/// Parse an address in the symbol map to main offset
+fn parse_address(s: &str) -> Option<u32> {
+    let s = s.trim();
+    let s = s.strip_prefix("ram:").unwrap_or(s);
+    let s = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s);
+    let addr = u64::from_str_radix(s.trim_end_matches(['h', 'H']), 16).ok()?;
+    let addr = if addr >= DISASSEMBLER_MAIN_BASE {
+        addr - DISASSEMBLER_MAIN_BASE
+    } else {
+        addr
+    };
+    u32::try_from(addr).ok()
+}
+
+/// Parse a size in the symbol map, which can be decimal or hex
+fn parse_size(s: &str) -> Option<u32> {
+    let s = s.trim();
+    match s.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+/// Split a CSV line, handling double-quoted columns
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut columns = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                chars.next();
+                current.push('"');
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => columns.push(std::mem::take(&mut current).trim().to_string()),
+            c => current.push(c),
+        }
+    }
+    columns.push(current.trim().to_string());
+    columns
+}
+
+static SYMBOL_MAPS: LazyLock<RwLock<EnumMap<GameVer, Option<Arc<SymbolMap>>>>> =
+    LazyLock::new(Default::default);
+
+/// Set the symbol map to use for the game version, replacing
+/// the existing one. `None` removes the symbol map.
+pub fn set_symbol_map(ver: GameVer, map: Option<SymbolMap>) {
+    let mut maps = SYMBOL_MAPS.write().expect("symbol maps lock is poisoned");
+    maps[ver] = map.map(Arc::new);
+}
+
+/// Get the symbol map for the game version, if loaded
+pub fn symbol_map(ver: GameVer) -> Option<Arc<SymbolMap>> {
+    let maps = SYMBOL_MAPS.read().expect("symbol maps lock is poisoned");
+    maps[ver].clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_no_header() {
+        let map = SymbolMap::parse(
+            "0x00971438,uking::ui::PauseMenuDataMgr::createPlayerEquipment,0x34c\n\
+             0x0073c5b4,spawnDroppedInventoryItem\n",
+        )
+        .unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(
+            map.symbolize(0x971540).as_deref(),
+            Some("uking::ui::PauseMenuDataMgr::createPlayerEquipment+0x108")
+        );
+        assert_eq!(
+            map.symbolize(0x73c5b4).as_deref(),
+            Some("spawnDroppedInventoryItem")
+        );
+        // after the end of the function with known size
+        assert_eq!(map.symbolize(0x971438 + 0x34c), None);
+        // before the first function
+        assert_eq!(map.symbolize(0x100), None);
+    }
+
+    #[test]
+    fn test_parse_csv_ghidra() {
+        let map = SymbolMap::parse(
+            "\"Name\",\"Location\",\"Type\",\"Namespace\"\n\
+             \"getPlayer\",\"710085456c\",\"Function\",\"Global\"\n\
+             \"memcpy\",\"ram:71018001d0\",\"Function\",\"Global\"\n",
+        )
+        .unwrap();
+        assert_eq!(map.symbolize(0x85456c).as_deref(), Some("getPlayer"));
+        assert_eq!(map.symbolize(0x18001d4).as_deref(), Some("memcpy+0x4"));
+    }
+
+    #[test]
+    fn test_parse_csv_header_with_size() {
+        let map = SymbolMap::parse(
+            "Address,Quality,Size,Name\n\
+             0x00000071006669f8,O,000408,uking::act::CreatePlayerEquipActorMgr::doRequestCreateWeapon\n",
+        )
+        .unwrap();
+        assert_eq!(
+            map.lookup(0x6669f8 + 407).map(|(s, off)| (s.size, off)),
+            Some((408, 407))
+        );
+        assert_eq!(map.lookup(0x6669f8 + 408), None);
+    }
+
+    #[test]
+    fn test_parse_ida_map() {
+        let map = SymbolMap::parse(
+            " Start         Length     Name                   Class\n\
+             \x20 0001:00000000 01234567H .text                  CODE\n\
+             \n\
+             \x20 Address         Publics by Value\n\
+             \n\
+             \x20 0001:0085456C       getPlayer\n\
+             \x20 0002:00000010       someData\n",
+        )
+        .unwrap();
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.symbolize(0x854570).as_deref(), Some("getPlayer+0x4"));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(
+            SymbolMap::parse("Address,Name\nxyz,foo\n"),
+            Err(SymbolMapError::InvalidAddress(2, "xyz".to_string()))
+        );
+        assert_eq!(SymbolMap::parse(""), Err(SymbolMapError::Empty));
+    }
+}
@@ -7,6 +7,11 @@ pub fn get_hash(name: &str) -> i32 {
     crc32fast::hash(name.as_bytes()) as i32
 }
 
+/// Get the names of all flags in the game data, sorted
+pub fn flag_names() -> &'static [&'static str] {
+    blueflame_deps::generated::gdt::FLAG_NAMES
+}
+
 /// Get the trigger param raw pointer from GDTM instance
 pub fn trigger_param_ptr(memory: &Memory) -> Result<u64, memory::Error> {
     let gdt_manager = singleton_instance!(gdtm(memory))?;
@@ -25,6 +25,9 @@ pub mod vm;
 /// Shared environment utilities
 pub mod env;
 
+/// Structured execution trace recording and reading
+pub mod trace;
+
 #[cfg(test)]
 pub mod test_utils;
 
@@ -1,6 +1,7 @@
 use crate::game::{SafeString, gdt};
 use crate::memory::{Memory, Ptr, mem, proxy};
 use crate::processor::{self, Cpu0, Process, reg};
+use crate::trace::{self, GdtOp};
 
 // this macro is needed because template generic types are not stable
 macro_rules! get_flag_impl {
@@ -161,7 +162,9 @@ fn get_flag_impl<
     };
 
     proxy! { let params = *this_ptr as trigger_param in proc };
-    let Some(flag) = params.get::<Fd, _>(idx) else {
+    let flag = params.get::<Fd, _>(idx);
+    trace_access(GdtOp::Get, Some(idx), None, None, flag);
+    let Some(flag) = flag else {
         reg! { cpu: x[0] = false, return };
     };
     if check_perms && !flag.readable() {
@@ -201,7 +204,9 @@ fn get_flag_by_name_impl<
     }
 
     proxy! { let params = *this_ptr as trigger_param in proc };
-    let Some(flag) = params.by_name::<Fd>(name) else {
+    let flag = params.by_name::<Fd>(&name);
+    trace_access(GdtOp::Get, None, Some(&name), None, flag);
+    let Some(flag) = flag else {
         reg! { cpu: x[0] = false, return };
     };
     if check_perms && !flag.readable() {
@@ -236,7 +241,9 @@ fn get_flag_array_by_index_impl<
     };
 
     proxy! {let params = *this_ptr as trigger_param in proc};
-    let Some(flag) = params.get::<Fd, _>(idx) else {
+    let flag = params.get::<Fd, _>(idx);
+    trace_access(GdtOp::Get, Some(idx), None, Some(array_idx), flag);
+    let Some(flag) = flag else {
         reg! { cpu: x[0] = false, return };
     };
 
@@ -278,7 +285,9 @@ fn get_flag_array_by_name_impl<
     let name = name_ptr.cstr(m)?.load_utf8_lossy(m)?;
 
     proxy! {let params = *this_ptr as trigger_param in proc};
-    let Some(flag) = params.by_name::<Fd>(name) else {
+    let flag = params.by_name::<Fd>(&name);
+    trace_access(GdtOp::Get, None, Some(&name), Some(array_idx), flag);
+    let Some(flag) = flag else {
         reg! { cpu: x[0] = false, return };
     };
 
@@ -358,7 +367,9 @@ fn set_flag_by_index_impl<
     let value = reader(cpu, proc)?;
 
     proxy! { let mut params = *this_ptr as trigger_param in proc };
-    let Some(flag) = params.get_mut::<Fd, _>(idx) else {
+    let flag = params.get_mut::<Fd, _>(idx);
+    trace_access(GdtOp::Set, Some(idx), None, None, flag.as_deref());
+    let Some(flag) = flag else {
         reg! { cpu: x[0] = false, return };
     };
     if check_perms && !flag.writable() {
@@ -399,7 +410,9 @@ fn set_flag_by_name_impl<
     }
 
     proxy! { let mut params = *this_ptr as trigger_param in proc };
-    let Some(flag) = params.by_name_mut::<Fd>(name) else {
+    let flag = params.by_name_mut::<Fd>(&name);
+    trace_access(GdtOp::Set, None, Some(&name), None, flag.as_deref());
+    let Some(flag) = flag else {
         reg! { cpu: x[0] = false, return };
     };
     if check_perms && !flag.writable() {
@@ -435,7 +448,15 @@ fn set_flag_array_by_index_impl<
     let value = reader(cpu, proc)?;
 
     proxy! { let mut params = *this_ptr as trigger_param in proc };
-    let Some(flag) = params.get_mut::<Fd, _>(idx) else {
+    let flag = params.get_mut::<Fd, _>(idx);
+    trace_access(
+        GdtOp::Set,
+        Some(idx),
+        None,
+        Some(array_idx),
+        flag.as_deref(),
+    );
+    let Some(flag) = flag else {
         reg! { cpu: x[0] = false, return };
     };
     if check_perms && !flag.writable() {
@@ -477,7 +498,15 @@ fn set_flag_array_by_name_impl<
     }
 
     proxy! { let mut params = *this_ptr as trigger_param in proc };
-    let Some(flag) = params.by_name_mut::<Fd>(name) else {
+    let flag = params.by_name_mut::<Fd>(&name);
+    trace_access(
+        GdtOp::Set,
+        None,
+        Some(&name),
+        Some(array_idx),
+        flag.as_deref(),
+    );
+    let Some(flag) = flag else {
         reg! { cpu: x[0] = false, return };
     };
     if check_perms && !flag.writable() {
@@ -500,7 +529,9 @@ pub fn reset<Fd: gdt::FlagDescriptor>(
     }
 
     proxy! { let mut params = *this_ptr as trigger_param in proc };
-    let Some(flag) = params.get_mut::<Fd, _>(idx) else {
+    let flag = params.get_mut::<Fd, _>(idx);
+    trace_access(GdtOp::Reset, Some(idx), None, None, flag.as_deref());
+    let Some(flag) = flag else {
         reg! { cpu: x[0] = false, return };
     };
     if check_perms && !flag.writable() {
@@ -528,7 +559,9 @@ pub fn reset_by_name<Fd: gdt::FlagDescriptor>(
     }
 
     proxy! { let mut params = *this_ptr as trigger_param in proc };
-    let Some(flag) = params.by_name_mut::<Fd>(name) else {
+    let flag = params.by_name_mut::<Fd>(&name);
+    trace_access(GdtOp::Reset, None, Some(&name), None, flag.as_deref());
+    let Some(flag) = flag else {
         reg! { cpu: x[0] = false, return };
     };
     if check_perms && !flag.writable() {
@@ -551,7 +584,15 @@ pub fn reset_array<Fd: gdt::ArrayFlagDescriptor + 'static>(
     }
 
     proxy! { let mut params = *this_ptr as trigger_param in proc };
-    let Some(flag) = params.get_mut::<Fd, _>(idx) else {
+    let flag = params.get_mut::<Fd, _>(idx);
+    trace_access(
+        GdtOp::Reset,
+        Some(idx),
+        None,
+        Some(array_idx),
+        flag.as_deref(),
+    );
+    let Some(flag) = flag else {
         reg! { cpu: x[0] = false, return };
     };
     if check_perms && !flag.writable() {
@@ -612,3 +653,14 @@ pub fn get_s32_max(cpu: &mut Cpu0, proc: &mut Process) -> Result<(), processor::
     mem! { (proc.memory_mut()): *out_ptr = value; };
     reg! { cpu: x[0] = true, return }
 }
+
+/// Record the flag access in the execution trace
+fn trace_access<T: gdt::FlagType>(
+    op: GdtOp,
+    index: Option<i32>,
+    name: Option<&str>,
+    array_index: Option<i32>,
+    flag: Option<&gdt::Flag<T>>,
+) {
+    trace::on_gdt(op, index, name, array_index, flag.map(|f| f.hash()));
+}
@@ -6,15 +6,25 @@ use crate::processor::insn::paste_insn;
 use crate::processor::{self, Cpu0, Hook, HookProvider, Process, reg};
 
 macro_rules! replace_hooks {
-    ($main_offset:ident size fn $( $offset:literal $size:literal $function:expr ),* $(,)?) => {
-        match $main_offset {
-        $(
-            #[allow(clippy::zero_prefixed_literal)]
-            $offset => {
-                return Ok(Some(Hook::Replace(processor::box_execute($function), $size)));
+    (main_offset size name fn $( $offset:literal $size:literal $name:literal $function:expr ),* $(,)?) => {
+        /// Get the replace hook at the main offset
+        fn fetch_replace_hook(main_offset: u32) -> Option<Hook> {
+            match main_offset {
+            $(
+                #[allow(clippy::zero_prefixed_literal)]
+                $offset => Some(Hook::Replace(processor::box_execute($function), $size)),
+            )*
+            _ => None,
+            }
+        }
+        /// Get the name of the game function replaced by the hook at the main offset
+        fn replace_hook_name(main_offset: u32) -> Option<&'static str> {
+            match main_offset {
+            $(
+                $offset => Some($name),
+            )*
+            _ => None,
             }
-        )*
-        _ => {}
         }
     };
 }
@@ -50,151 +60,156 @@ impl HookProvider for GameHooks {
             return Ok(None);
         }
 
-        replace_hooks! {
-
-        main_offset size   fn
-        0x006669f8  000408 return_void, // uking::act::CreatePlayerEquipActorMgr::doRequestCreateWeapon
-        0x00666cf8  000688 return_void, // uking::act::CreatePlayerEquipActorMgr::doRequestCreateArmor
-        0x0073c5b4  000732 return_void, // spawnDroppedInventoryItem. This is at 0xD23B20 in 1.6
-                                        // but parameters are optimized out
-        0x00849580  003456 return_void, // Player::equipmentStuff
-        0x0084a300  000624 return_void, // Player::dropOverworldEquip
-        0x0085456c  000068 get_player,  // ksys::act::PlayerInfo::getPlayer
-        0x00d2e950  000348 return_void, // ksys::act::InfoData::logFailure
-        //
+        Ok(fetch_replace_hook(main_offset))
+    }
 
-        // ksys::gdt::TriggerParam
-        0x00ddf0f8  000124 gdt_hooks::get_bool, // getBool by idx
-        0x00ddf174  000120 gdt_hooks::get_s32,  // getS32 by idx
-        0x00ddf1ec  000120 gdt_hooks::get_f32,  // getF32 by idx
-        0x00ddf264  000140 gdt_hooks::get_str::<gdt::fd!(str32)>, // getStr by idx
-        0x00ddf2f0  000140 gdt_hooks::get_str::<gdt::fd!(str64)>, // getStr by idx
-        0x00ddf37c  000140 gdt_hooks::get_str::<gdt::fd!(str256)>, // getStr by idx
-        0x00ddf408  000124 gdt_hooks::get_vec3f, // getVec3f by idx
-        0x00ddf484  000368 gdt_hooks::get_bool_by_name,
-        0x00ddf5f4  000368 gdt_hooks::get_bool_by_name,
-        0x00ddf764  000364 gdt_hooks::get_s32_by_name,
-        0x00ddf8d0  000364 gdt_hooks::get_f32_by_name,
-        0x00ddfa3c  000384 gdt_hooks::get_str_by_name::<gdt::fd!(str32)>,
-        0x00ddfbbc  000384 gdt_hooks::get_str_by_name::<gdt::fd!(str64)>,
-        0x00ddfd3c  000384 gdt_hooks::get_str_by_name::<gdt::fd!(str256)>,
-        0x00ddfebc  000368 gdt_hooks::get_vec3f_by_name,
-        0x00de002c  000164 gdt_hooks::get_bool_array, //_ZNK4ksys3gdt12TriggerParam7getBoolEPbiib
-        0x00de00d0  000160 gdt_hooks::get_s32_array, //_ZNK4ksys3gdt12TriggerParam6getS32EPiiib
-        0x00de0170  000160 gdt_hooks::get_f32_array, //_ZNK4ksys3gdt12TriggerParam6getF32EPfiib
-        0x00de0210  000180 gdt_hooks::get_str_array::<gdt::fd!(str64[])>, //_ZNK4ksys3gdt12TriggerParam8getStr64EPPKciib
-        0x00de02c4  000180 gdt_hooks::get_str_array::<gdt::fd!(str256[])>, //_ZNK4ksys3gdt12TriggerParam9getStr256EPPKciib
-        0x00de0378  000160 gdt_hooks::get_vec2f_array, //_ZNK4ksys3gdt12TriggerParam8getVec2fEPN4sead7Vector2IfEEiib
-        0x00de0418  000164 gdt_hooks::get_vec3f_array, //_ZNK4ksys3gdt12TriggerParam8getVec3fEPN4sead7Vector3IfEEiib
-        0x00de04bc  000356 gdt_hooks::get_bool_array_by_name, //_ZNK4ksys3gdt12TriggerParam7getBoolEPbRKN4sead14SafeStringBaseIcEEibb
-        0x00de0620  000352 gdt_hooks::get_s32_array_by_name, //_ZNK4ksys3gdt12TriggerParam6getS32EPiRKN4sead14SafeStringBaseIcEEibb
-        0x00de0780  000352 gdt_hooks::get_f32_array_by_name, //_ZNK4ksys3gdt12TriggerParam6getF32EPfRKN4sead14SafeStringBaseIcEEibb
-        0x00de08e0  000372 return_false,//_ZNK4ksys3gdt12TriggerParam6getStrEPPKcRKN4sead14SafeStringBaseIcEEibb
-        0x00de0a54  000372 gdt_hooks::get_str_array_by_name::<gdt::fd!(str64[])>,//_ZNK4ksys3gdt12TriggerParam8getStr64EPPKcRKN4sead14SafeStringBaseIcEEibb
-        0x00de0bc8  000372 gdt_hooks::get_str_array_by_name::<gdt::fd!(str256[])>,//_ZNK4ksys3gdt12TriggerParam9getStr256EPPKcRKN4sead14SafeStringBaseIcEEibb
-        0x00de0d3c  000056 gdt_hooks::get_array_size::<gdt::fd!(bool[])>,//_ZNK4ksys3gdt12TriggerParam16getBoolArraySizeEPii
-        0x00de0d74  000056 gdt_hooks::get_array_size::<gdt::fd!(s32[])>,//_ZNK4ksys3gdt12TriggerParam15getS32ArraySizeEPii
-        0x00de0dac  000056 gdt_hooks::get_array_size::<gdt::fd!(f32[])>,//_ZNK4ksys3gdt12TriggerParam15getF32ArraySizeEPii
-        0x00de0de4  000056 return_0,//_ZNK4ksys3gdt12TriggerParam15getStrArraySizeEPii
-        0x00de0e1c  000056 gdt_hooks::get_array_size::<gdt::fd!(str64[])>,//_ZNK4ksys3gdt12TriggerParam17getStr64ArraySizeEPii
-        0x00de0e54  000056 gdt_hooks::get_array_size::<gdt::fd!(str256[])>,//_ZNK4ksys3gdt12TriggerParam18getStr256ArraySizeEPii
-        0x00de0e8c  000056 gdt_hooks::get_array_size::<gdt::fd!(vec2f[])>,//_ZNK4ksys3gdt12TriggerParam17getVec2fArraySizeEPii
-        0x00de0ec4  000056 gdt_hooks::get_array_size::<gdt::fd!(vec3f[])>,//_ZNK4ksys3gdt12TriggerParam17getVec3fArraySizeEPii
-        0x00de0efc  000056 return_0,//_ZNK4ksys3gdt12TriggerParam17getVec4fArraySizeEPii
-        0x00de0f34  000192 gdt_hooks::get_array_size_by_hash::<gdt::fd!(bool[])>,//_ZNK4ksys3gdt12TriggerParam22getBoolArraySizeByHashEPij
-        0x00de0ff4  000192 gdt_hooks::get_array_size_by_hash::<gdt::fd!(s32[])>,//_ZNK4ksys3gdt12TriggerParam21getS32ArraySizeByHashEPij
-        0x00de10b4  000192 gdt_hooks::get_array_size_by_hash::<gdt::fd!(f32[])>,//_ZNK4ksys3gdt12TriggerParam21getF32ArraySizeByHashEPij
-        0x00de1174  000192 return_0,//_ZNK4ksys3gdt12TriggerParam21getStrArraySizeByHashEPij
-        0x00de1234  000192 gdt_hooks::get_array_size_by_hash::<gdt::fd!(str64[])>,//_ZNK4ksys3gdt12TriggerParam23getStr64ArraySizeByHashEPij
-        0x00de12f4  000192 gdt_hooks::get_array_size_by_hash::<gdt::fd!(str256[])>,//_ZNK4ksys3gdt12TriggerParam24getStr256ArraySizeByHashEPij
-        0x00de13b4  000192 gdt_hooks::get_array_size_by_hash::<gdt::fd!(vec2f[])>,//_ZNK4ksys3gdt12TriggerParam23getVec2fArraySizeByHashEPij
-        0x00de1474  000192 gdt_hooks::get_array_size_by_hash::<gdt::fd!(vec3f[])>,//_ZNK4ksys3gdt12TriggerParam23getVec3fArraySizeByHashEPij
-        0x00de1534  000192 return_0, //_ZNK4ksys3gdt12TriggerParam23getVec4fArraySizeByHashEPij
-        // 0x00de15f4  000248,_ZNK4ksys3gdt12TriggerParam15getS32ArraySizeEPiRKN4sead14SafeStringBaseIcEE
-        // 0x00de16ec  000248,_ZNK4ksys3gdt12TriggerParam17getStr64ArraySizeEPiRKN4sead14SafeStringBaseIcEE
-        // 0x00de17e4  000248,_ZNK4ksys3gdt12TriggerParam17getVec3fArraySizeEPiRKN4sead14SafeStringBaseIcEE
-        // 0x00de18dc  000324,_ZNK4ksys3gdt12TriggerParam17getMinValueForS32EPiRKN4sead14SafeStringBaseIcEE
-        0x00de1a20  000324 gdt_hooks::get_s32_max,
+    fn hook_name(&self, main_offset: u32, env: Environment) -> Option<&'static str> {
+        if env.is160() {
+            return None;
+        }
+        replace_hook_name(main_offset)
+    }
+}
 
-        0x00de1b64  000236 gdt_hooks::set_bool, // setBool by idx
-        0x00de22f8  000332 gdt_hooks::set_s32,  // setS32 by idx
-        0x00de2908  000340 gdt_hooks::set_f32,//_ZN4ksys3gdt12TriggerParam6setF32Efibb
-        0x00de2f20  000432 gdt_hooks::set_str::<gdt::fd!(str32)>,//_ZN4ksys3gdt12TriggerParam6setStrEPKcibb
-        0x00de37b0  000432 gdt_hooks::set_str::<gdt::fd!(str64)>,//_ZN4ksys3gdt12TriggerParam8setStr64EPKcibb
-        0x00de4040  000440 gdt_hooks::set_str::<gdt::fd!(str256)>,//_ZN4ksys3gdt12TriggerParam9setStr256EPKcibb
-        0x00de4ea0  000180 gdt_hooks::set_vec3f,//_ZN4ksys3gdt12TriggerParam8setVec3fERKN4sead7Vector3IfEEibb
-        0x00de59e4  000296 gdt_hooks::set_bool_by_name, // setBool by name
-        0x00de5b0c  000296 gdt_hooks::set_s32_by_name,  // setS32 by name
-        0x00de5c34  000304 gdt_hooks::set_f32_by_name,//_ZN4ksys3gdt12TriggerParam6setF32EfRKN4sead14SafeStringBaseIcEEbbb
-        0x00de5d64  000296 gdt_hooks::set_str_by_name::<gdt::fd!(str32)>,//_ZN4ksys3gdt12TriggerParam6setStrEPKcRKN4sead14SafeStringBaseIcEEbbb
-        0x00de5e8c  000296 gdt_hooks::set_str_by_name::<gdt::fd!(str64)>,//_ZN4ksys3gdt12TriggerParam8setStr64EPKcRKN4sead14SafeStringBaseIcEEbbb
-        0x00de5fb4  000444 gdt_hooks::set_vec3f_by_name,//_ZN4ksys3gdt12TriggerParam8setVec3fERKN4sead7Vector3IfEERKNS2_14SafeStringBaseIcEEbbb
-        0x00de6170  000236 gdt_hooks::set_bool_array,//_ZN4ksys3gdt12TriggerParam7setBoolEbiibb
-        0x00de625c  000388 gdt_hooks::set_s32_array,//_ZN4ksys3gdt12TriggerParam6setS32Eiiibb
-        0x00de63e0  000396 gdt_hooks::set_f32_array,//_ZN4ksys3gdt12TriggerParam6setF32Efiibb
-        0x00de656c  000492 gdt_hooks::set_str_array::<gdt::fd!(str64[])>,//_ZN4ksys3gdt12TriggerParam8setStr64EPKciibb
-        0x00de6758  000492 gdt_hooks::set_str_array::<gdt::fd!(str256[])>,//_ZN4ksys3gdt12TriggerParam9setStr256EPKciibb
-        0x00de6944  000224 gdt_hooks::set_vec2f_array,//_ZN4ksys3gdt12TriggerParam8setVec2fERKN4sead7Vector2IfEEiibb
-        0x00de6a24  000224 gdt_hooks::set_vec3f_array,//_ZN4ksys3gdt12TriggerParam8setVec3fERKN4sead7Vector3IfEEiibb
-        0x00de6b04  000260 gdt_hooks::set_bool_array_by_name,//_ZN4ksys3gdt12TriggerParam7setBoolEbRKN4sead14SafeStringBaseIcEEibbb
-        0x00de6c08  000260 gdt_hooks::set_s32_array_by_name,//_ZN4ksys3gdt12TriggerParam6setS32EiRKN4sead14SafeStringBaseIcEEibbb
-        0x00de6d0c  000268 gdt_hooks::set_f32_array_by_name,//_ZN4ksys3gdt12TriggerParam6setF32EfRKN4sead14SafeStringBaseIcEEibbb
-        0x00de6e18  000260 gdt_hooks::set_str_array_by_name::<gdt::fd!(str64[])>,//_ZN4ksys3gdt12TriggerParam8setStr64EPKcRKN4sead14SafeStringBaseIcEEibbb
-        0x00de6f1c  000232 gdt_hooks::reset::<gdt::fd!(bool)>, // resetBool
-        0x00de7004  000232 gdt_hooks::reset::<gdt::fd!(s32)>,  // resetS32
-        0x00de70ec  000232 gdt_hooks::reset::<gdt::fd!(f32)>,//_ZN4ksys3gdt12TriggerParam8resetF32Eib
-        0x00de71d4  000232 gdt_hooks::reset::<gdt::fd!(str64)>,//_ZN4ksys3gdt12TriggerParam10resetStr64Eib
-        0x00de72bc  000232 gdt_hooks::reset::<gdt::fd!(vec3f)>,//_ZN4ksys3gdt12TriggerParam10resetVec3fEib
-        0x00de73a4  000272 gdt_hooks::reset_by_name::<gdt::fd!(bool)>,//_ZN4ksys3gdt12TriggerParam9resetBoolERKN4sead14SafeStringBaseIcEEbb
-        0x00de74b4  000272 gdt_hooks::reset_by_name::<gdt::fd!(s32)>,//_ZN4ksys3gdt12TriggerParam8resetS32ERKN4sead14SafeStringBaseIcEEbb
-        0x00de75c4  000272 gdt_hooks::reset_by_name::<gdt::fd!(f32)>,//_ZN4ksys3gdt12TriggerParam10resetStr64ERKN4sead14SafeStringBaseIcEEbb
-        0x00de76d4  000272 gdt_hooks::reset_by_name::<gdt::fd!(vec3f)>,//_ZN4ksys3gdt12TriggerParam10resetVec3fERKN4sead14SafeStringBaseIcEEbb
-        0x00de77e4  000284 gdt_hooks::reset_array::<gdt::fd!(bool[])>,//_ZN4ksys3gdt12TriggerParam9resetBoolEiib
-        0x00de7900  000284 gdt_hooks::reset_array::<gdt::fd!(s32[])>,//_ZN4ksys3gdt12TriggerParam8resetS32Eiib
-        0x00de7a1c  000284 gdt_hooks::reset_array::<gdt::fd!(f32[])>,//_ZN4ksys3gdt12TriggerParam8resetF32Eiib
-        0x00de7b38  000284 return_false,//_ZN4ksys3gdt12TriggerParam8resetStrEiib
-        0x00de7c54  000284 gdt_hooks::reset_array::<gdt::fd!(str64[])>,//_ZN4ksys3gdt12TriggerParam10resetStr64Eiib
-        0x00de7d70  000284 gdt_hooks::reset_array::<gdt::fd!(str256[])>,//_ZN4ksys3gdt12TriggerParam11resetStr256Eiib
-        0x00de7e8c  000284 gdt_hooks::reset_array::<gdt::fd!(vec2f[])>,//_ZN4ksys3gdt12TriggerParam10resetVec2fEiib
-        0x00de7fa8  000284 gdt_hooks::reset_array::<gdt::fd!(vec3f[])>,//_ZN4ksys3gdt12TriggerParam10resetVec3fEiib
-        0x00de80c4  000284 return_false,//_ZN4ksys3gdt12TriggerParam10resetVec4fEiib
-            // not doing copyFlags stuff
-        0x00deeb8c  002628 gdt_hooks::reset_all,//_ZN4ksys3gdt12TriggerParam28resetAllFlagsToInitialValuesEv
+replace_hooks! {
+    main_offset size   name fn
+    0x006669f8  000408 "uking::act::CreatePlayerEquipActorMgr::doRequestCreateWeapon" return_void,
+    0x00666cf8  000688 "uking::act::CreatePlayerEquipActorMgr::doRequestCreateArmor" return_void,
+    0x0073c5b4  000732 "spawnDroppedInventoryItem" return_void, // This is at 0xD23B20 in 1.6
+                                    // but parameters are optimized out
+    0x00849580  003456 "Player::equipmentStuff" return_void,
+    0x0084a300  000624 "Player::dropOverworldEquip" return_void,
+    0x0085456c  000068 "ksys::act::PlayerInfo::getPlayer" get_player,
+    0x00d2e950  000348 "ksys::act::InfoData::logFailure" return_void,
+    //
 
-        0x00df08b8  000184 gdt_hooks::idx_from_hash::<gdt::fd!(bool)>, // getBoolIdx by hash
-        0x00df0970  000184 gdt_hooks::idx_from_hash::<gdt::fd!(s32)>,  // getS32Idx by hash
-        0x00df0a28  000184 gdt_hooks::idx_from_hash::<gdt::fd!(f32)>,//_ZNK4ksys3gdt12TriggerParam9getF32IdxEj
-        0x00df0ae0  000184 gdt_hooks::idx_from_hash::<gdt::fd!(str32)>,//_ZNK4ksys3gdt12TriggerParam9getStrIdxEj
-        0x00df0b98  000184 gdt_hooks::idx_from_hash::<gdt::fd!(str64)>,//_ZNK4ksys3gdt12TriggerParam11getStr64IdxEj
-        0x00df0c50  000184 gdt_hooks::idx_from_hash::<gdt::fd!(str256)>,//_ZNK4ksys3gdt12TriggerParam12getStr256IdxEj
-        0x00df0d08  000184 gdt_hooks::idx_from_hash::<gdt::fd!(vec2f)>,//_ZNK4ksys3gdt12TriggerParam11getVec2fIdxEj
-        0x00df0dc0  000184 gdt_hooks::idx_from_hash::<gdt::fd!(vec3f)>,//_ZNK4ksys3gdt12TriggerParam11getVec3fIdxEj
-        0x00df0e78  000144 gdt_hooks::idx_from_hash::<gdt::fd!(bool[])>,//_ZNK4ksys3gdt12TriggerParam15getBoolArrayIdxEj
-        0x00df0f08  000144 gdt_hooks::idx_from_hash::<gdt::fd!(s32[])>,//_ZNK4ksys3gdt12TriggerParam14getS32ArrayIdxEj
-        0x00df0f98  000144 gdt_hooks::idx_from_hash::<gdt::fd!(f32[])>,//_ZNK4ksys3gdt12TriggerParam14getF32ArrayIdxEj
-        0x00df1028  000144 gdt_hooks::idx_from_hash::<gdt::fd!(str64[])>,//_ZNK4ksys3gdt12TriggerParam16getStr64ArrayIdxEj
-        0x00df10b8  000144 gdt_hooks::idx_from_hash::<gdt::fd!(str256[])>,//_ZNK4ksys3gdt12TriggerParam17getStr256ArrayIdxEj
-        0x00df1148  000144 gdt_hooks::idx_from_hash::<gdt::fd!(vec2f[])>,//_ZNK4ksys3gdt12TriggerParam16getVec2fArrayIdxEj
-        0x00df11d8  000144 gdt_hooks::idx_from_hash::<gdt::fd!(vec3f[])>,//_ZNK4ksys3gdt12TriggerParam16getVec3fArrayIdxEj
-        //
-        0x00e491d4  000332 return_void, // EventMgr::auto1 (called from doGetItem)
+    // ksys::gdt::TriggerParam
+    0x00ddf0f8  000124 "ksys::gdt::TriggerParam::getBool(bool*, int, bool) const" gdt_hooks::get_bool, // getBool by idx
+    0x00ddf174  000120 "ksys::gdt::TriggerParam::getS32(int*, int, bool) const" gdt_hooks::get_s32,  // getS32 by idx
+    0x00ddf1ec  000120 "ksys::gdt::TriggerParam::getF32(float*, int, bool) const" gdt_hooks::get_f32,  // getF32 by idx
+    0x00ddf264  000140 "ksys::gdt::TriggerParam::getStr(char const**, int, bool) const" gdt_hooks::get_str::<gdt::fd!(str32)>, // getStr by idx
+    0x00ddf2f0  000140 "ksys::gdt::TriggerParam::getStr64(char const**, int, bool) const" gdt_hooks::get_str::<gdt::fd!(str64)>, // getStr by idx
+    0x00ddf37c  000140 "ksys::gdt::TriggerParam::getStr256(char const**, int, bool) const" gdt_hooks::get_str::<gdt::fd!(str256)>, // getStr by idx
+    0x00ddf408  000124 "ksys::gdt::TriggerParam::getVec3f(sead::Vector3<float>*, int, bool) const" gdt_hooks::get_vec3f, // getVec3f by idx
+    0x00ddf484  000368 "ksys::gdt::TriggerParam::getBool(bool*, sead::SafeStringBase<char> const&, bool, bool) const" gdt_hooks::get_bool_by_name,
+    0x00ddf5f4  000368 "ksys::gdt::TriggerParam::getBoolIfCopied(bool*, sead::SafeStringBase<char> const&, bool, bool) const" gdt_hooks::get_bool_by_name,
+    0x00ddf764  000364 "ksys::gdt::TriggerParam::getS32(int*, sead::SafeStringBase<char> const&, bool, bool) const" gdt_hooks::get_s32_by_name,
+    0x00ddf8d0  000364 "ksys::gdt::TriggerParam::getF32(float*, sead::SafeStringBase<char> const&, bool, bool) const" gdt_hooks::get_f32_by_name,
+    0x00ddfa3c  000384 "ksys::gdt::TriggerParam::getStr(char const**, sead::SafeStringBase<char> const&, bool, bool) const" gdt_hooks::get_str_by_name::<gdt::fd!(str32)>,
+    0x00ddfbbc  000384 "ksys::gdt::TriggerParam::getStr64(char const**, sead::SafeStringBase<char> const&, bool, bool) const" gdt_hooks::get_str_by_name::<gdt::fd!(str64)>,
+    0x00ddfd3c  000384 "ksys::gdt::TriggerParam::getStr256(char const**, sead::SafeStringBase<char> const&, bool, bool) const" gdt_hooks::get_str_by_name::<gdt::fd!(str256)>,
+    0x00ddfebc  000368 "ksys::gdt::TriggerParam::getVec3f(sead::Vector3<float>*, sead::SafeStringBase<char> const&, bool, bool) const" gdt_hooks::get_vec3f_by_name,
+    0x00de002c  000164 "ksys::gdt::TriggerParam::getBool(bool*, int, int, bool) const" gdt_hooks::get_bool_array, //_ZNK4ksys3gdt12TriggerParam7getBoolEPbiib
+    0x00de00d0  000160 "ksys::gdt::TriggerParam::getS32(int*, int, int, bool) const" gdt_hooks::get_s32_array, //_ZNK4ksys3gdt12TriggerParam6getS32EPiiib
+    0x00de0170  000160 "ksys::gdt::TriggerParam::getF32(float*, int, int, bool) const" gdt_hooks::get_f32_array, //_ZNK4ksys3gdt12TriggerParam6getF32EPfiib
+    0x00de0210  000180 "ksys::gdt::TriggerParam::getStr64(char const**, int, int, bool) const" gdt_hooks::get_str_array::<gdt::fd!(str64[])>, //_ZNK4ksys3gdt12TriggerParam8getStr64EPPKciib
+    0x00de02c4  000180 "ksys::gdt::TriggerParam::getStr256(char const**, int, int, bool) const" gdt_hooks::get_str_array::<gdt::fd!(str256[])>, //_ZNK4ksys3gdt12TriggerParam9getStr256EPPKciib
+    0x00de0378  000160 "ksys::gdt::TriggerParam::getVec2f(sead::Vector2<float>*, int, int, bool) const" gdt_hooks::get_vec2f_array, //_ZNK4ksys3gdt12TriggerParam8getVec2fEPN4sead7Vector2IfEEiib
+    0x00de0418  000164 "ksys::gdt::TriggerParam::getVec3f(sead::Vector3<float>*, int, int, bool) const" gdt_hooks::get_vec3f_array, //_ZNK4ksys3gdt12TriggerParam8getVec3fEPN4sead7Vector3IfEEiib
+    0x00de04bc  000356 "ksys::gdt::TriggerParam::getBool(bool*, sead::SafeStringBase<char> const&, int, bool, bool) const" gdt_hooks::get_bool_array_by_name, //_ZNK4ksys3gdt12TriggerParam7getBoolEPbRKN4sead14SafeStringBaseIcEEibb
+    0x00de0620  000352 "ksys::gdt::TriggerParam::getS32(int*, sead::SafeStringBase<char> const&, int, bool, bool) const" gdt_hooks::get_s32_array_by_name, //_ZNK4ksys3gdt12TriggerParam6getS32EPiRKN4sead14SafeStringBaseIcEEibb
+    0x00de0780  000352 "ksys::gdt::TriggerParam::getF32(float*, sead::SafeStringBase<char> const&, int, bool, bool) const" gdt_hooks::get_f32_array_by_name, //_ZNK4ksys3gdt12TriggerParam6getF32EPfRKN4sead14SafeStringBaseIcEEibb
+    0x00de08e0  000372 "ksys::gdt::TriggerParam::getStr(char const**, sead::SafeStringBase<char> const&, int, bool, bool) const" return_false,//_ZNK4ksys3gdt12TriggerParam6getStrEPPKcRKN4sead14SafeStringBaseIcEEibb
+    0x00de0a54  000372 "ksys::gdt::TriggerParam::getStr64(char const**, sead::SafeStringBase<char> const&, int, bool, bool) const" gdt_hooks::get_str_array_by_name::<gdt::fd!(str64[])>,//_ZNK4ksys3gdt12TriggerParam8getStr64EPPKcRKN4sead14SafeStringBaseIcEEibb
+    0x00de0bc8  000372 "ksys::gdt::TriggerParam::getStr256(char const**, sead::SafeStringBase<char> const&, int, bool, bool) const" gdt_hooks::get_str_array_by_name::<gdt::fd!(str256[])>,//_ZNK4ksys3gdt12TriggerParam9getStr256EPPKcRKN4sead14SafeStringBaseIcEEibb
+    0x00de0d3c  000056 "ksys::gdt::TriggerParam::getBoolArraySize(int*, int) const" gdt_hooks::get_array_size::<gdt::fd!(bool[])>,//_ZNK4ksys3gdt12TriggerParam16getBoolArraySizeEPii
+    0x00de0d74  000056 "ksys::gdt::TriggerParam::getS32ArraySize(int*, int) const" gdt_hooks::get_array_size::<gdt::fd!(s32[])>,//_ZNK4ksys3gdt12TriggerParam15getS32ArraySizeEPii
+    0x00de0dac  000056 "ksys::gdt::TriggerParam::getF32ArraySize(int*, int) const" gdt_hooks::get_array_size::<gdt::fd!(f32[])>,//_ZNK4ksys3gdt12TriggerParam15getF32ArraySizeEPii
+    0x00de0de4  000056 "ksys::gdt::TriggerParam::getStrArraySize(int*, int) const" return_0,//_ZNK4ksys3gdt12TriggerParam15getStrArraySizeEPii
+    0x00de0e1c  000056 "ksys::gdt::TriggerParam::getStr64ArraySize(int*, int) const" gdt_hooks::get_array_size::<gdt::fd!(str64[])>,//_ZNK4ksys3gdt12TriggerParam17getStr64ArraySizeEPii
+    0x00de0e54  000056 "ksys::gdt::TriggerParam::getStr256ArraySize(int*, int) const" gdt_hooks::get_array_size::<gdt::fd!(str256[])>,//_ZNK4ksys3gdt12TriggerParam18getStr256ArraySizeEPii
+    0x00de0e8c  000056 "ksys::gdt::TriggerParam::getVec2fArraySize(int*, int) const" gdt_hooks::get_array_size::<gdt::fd!(vec2f[])>,//_ZNK4ksys3gdt12TriggerParam17getVec2fArraySizeEPii
+    0x00de0ec4  000056 "ksys::gdt::TriggerParam::getVec3fArraySize(int*, int) const" gdt_hooks::get_array_size::<gdt::fd!(vec3f[])>,//_ZNK4ksys3gdt12TriggerParam17getVec3fArraySizeEPii
+    0x00de0efc  000056 "ksys::gdt::TriggerParam::getVec4fArraySize(int*, int) const" return_0,//_ZNK4ksys3gdt12TriggerParam17getVec4fArraySizeEPii
+    0x00de0f34  000192 "ksys::gdt::TriggerParam::getBoolArraySizeByHash(int*, unsigned int) const" gdt_hooks::get_array_size_by_hash::<gdt::fd!(bool[])>,//_ZNK4ksys3gdt12TriggerParam22getBoolArraySizeByHashEPij
+    0x00de0ff4  000192 "ksys::gdt::TriggerParam::getS32ArraySizeByHash(int*, unsigned int) const" gdt_hooks::get_array_size_by_hash::<gdt::fd!(s32[])>,//_ZNK4ksys3gdt12TriggerParam21getS32ArraySizeByHashEPij
+    0x00de10b4  000192 "ksys::gdt::TriggerParam::getF32ArraySizeByHash(int*, unsigned int) const" gdt_hooks::get_array_size_by_hash::<gdt::fd!(f32[])>,//_ZNK4ksys3gdt12TriggerParam21getF32ArraySizeByHashEPij
+    0x00de1174  000192 "ksys::gdt::TriggerParam::getStrArraySizeByHash(int*, unsigned int) const" return_0,//_ZNK4ksys3gdt12TriggerParam21getStrArraySizeByHashEPij
+    0x00de1234  000192 "ksys::gdt::TriggerParam::getStr64ArraySizeByHash(int*, unsigned int) const" gdt_hooks::get_array_size_by_hash::<gdt::fd!(str64[])>,//_ZNK4ksys3gdt12TriggerParam23getStr64ArraySizeByHashEPij
+    0x00de12f4  000192 "ksys::gdt::TriggerParam::getStr256ArraySizeByHash(int*, unsigned int) const" gdt_hooks::get_array_size_by_hash::<gdt::fd!(str256[])>,//_ZNK4ksys3gdt12TriggerParam24getStr256ArraySizeByHashEPij
+    0x00de13b4  000192 "ksys::gdt::TriggerParam::getVec2fArraySizeByHash(int*, unsigned int) const" gdt_hooks::get_array_size_by_hash::<gdt::fd!(vec2f[])>,//_ZNK4ksys3gdt12TriggerParam23getVec2fArraySizeByHashEPij
+    0x00de1474  000192 "ksys::gdt::TriggerParam::getVec3fArraySizeByHash(int*, unsigned int) const" gdt_hooks::get_array_size_by_hash::<gdt::fd!(vec3f[])>,//_ZNK4ksys3gdt12TriggerParam23getVec3fArraySizeByHashEPij
+    0x00de1534  000192 "ksys::gdt::TriggerParam::getVec4fArraySizeByHash(int*, unsigned int) const" return_0, //_ZNK4ksys3gdt12TriggerParam23getVec4fArraySizeByHashEPij
+    // 0x00de15f4  000248,_ZNK4ksys3gdt12TriggerParam15getS32ArraySizeEPiRKN4sead14SafeStringBaseIcEE
+    // 0x00de16ec  000248,_ZNK4ksys3gdt12TriggerParam17getStr64ArraySizeEPiRKN4sead14SafeStringBaseIcEE
+    // 0x00de17e4  000248,_ZNK4ksys3gdt12TriggerParam17getVec3fArraySizeEPiRKN4sead14SafeStringBaseIcEE
+    // 0x00de18dc  000324,_ZNK4ksys3gdt12TriggerParam17getMinValueForS32EPiRKN4sead14SafeStringBaseIcEE
+    0x00de1a20  000324 "ksys::gdt::TriggerParam::getMaxValueForS32(int*, sead::SafeStringBase<char> const&) const" gdt_hooks::get_s32_max,
 
-        0x011f3364  000032 return_0, // ksys::util::getDebugHeap
+    0x00de1b64  000236 "ksys::gdt::TriggerParam::setBool(bool, int, bool, bool)" gdt_hooks::set_bool, // setBool by idx
+    0x00de22f8  000332 "ksys::gdt::TriggerParam::setS32(int, int, bool, bool)" gdt_hooks::set_s32,  // setS32 by idx
+    0x00de2908  000340 "ksys::gdt::TriggerParam::setF32(float, int, bool, bool)" gdt_hooks::set_f32,//_ZN4ksys3gdt12TriggerParam6setF32Efibb
+    0x00de2f20  000432 "ksys::gdt::TriggerParam::setStr(char const*, int, bool, bool)" gdt_hooks::set_str::<gdt::fd!(str32)>,//_ZN4ksys3gdt12TriggerParam6setStrEPKcibb
+    0x00de37b0  000432 "ksys::gdt::TriggerParam::setStr64(char const*, int, bool, bool)" gdt_hooks::set_str::<gdt::fd!(str64)>,//_ZN4ksys3gdt12TriggerParam8setStr64EPKcibb
+    0x00de4040  000440 "ksys::gdt::TriggerParam::setStr256(char const*, int, bool, bool)" gdt_hooks::set_str::<gdt::fd!(str256)>,//_ZN4ksys3gdt12TriggerParam9setStr256EPKcibb
+    0x00de4ea0  000180 "ksys::gdt::TriggerParam::setVec3f(sead::Vector3<float> const&, int, bool, bool)" gdt_hooks::set_vec3f,//_ZN4ksys3gdt12TriggerParam8setVec3fERKN4sead7Vector3IfEEibb
+    0x00de59e4  000296 "ksys::gdt::TriggerParam::setBool(bool, sead::SafeStringBase<char> const&, bool, bool, bool)" gdt_hooks::set_bool_by_name, // setBool by name
+    0x00de5b0c  000296 "ksys::gdt::TriggerParam::setS32(int, sead::SafeStringBase<char> const&, bool, bool, bool)" gdt_hooks::set_s32_by_name,  // setS32 by name
+    0x00de5c34  000304 "ksys::gdt::TriggerParam::setF32(float, sead::SafeStringBase<char> const&, bool, bool, bool)" gdt_hooks::set_f32_by_name,//_ZN4ksys3gdt12TriggerParam6setF32EfRKN4sead14SafeStringBaseIcEEbbb
+    0x00de5d64  000296 "ksys::gdt::TriggerParam::setStr(char const*, sead::SafeStringBase<char> const&, bool, bool, bool)" gdt_hooks::set_str_by_name::<gdt::fd!(str32)>,//_ZN4ksys3gdt12TriggerParam6setStrEPKcRKN4sead14SafeStringBaseIcEEbbb
+    0x00de5e8c  000296 "ksys::gdt::TriggerParam::setStr64(char const*, sead::SafeStringBase<char> const&, bool, bool, bool)" gdt_hooks::set_str_by_name::<gdt::fd!(str64)>,//_ZN4ksys3gdt12TriggerParam8setStr64EPKcRKN4sead14SafeStringBaseIcEEbbb
+    0x00de5fb4  000444 "ksys::gdt::TriggerParam::setVec3f(sead::Vector3<float> const&, sead::SafeStringBase<char> const&, bool, bool, bool)" gdt_hooks::set_vec3f_by_name,//_ZN4ksys3gdt12TriggerParam8setVec3fERKN4sead7Vector3IfEERKNS2_14SafeStringBaseIcEEbbb
+    0x00de6170  000236 "ksys::gdt::TriggerParam::setBool(bool, int, int, bool, bool)" gdt_hooks::set_bool_array,//_ZN4ksys3gdt12TriggerParam7setBoolEbiibb
+    0x00de625c  000388 "ksys::gdt::TriggerParam::setS32(int, int, int, bool, bool)" gdt_hooks::set_s32_array,//_ZN4ksys3gdt12TriggerParam6setS32Eiiibb
+    0x00de63e0  000396 "ksys::gdt::TriggerParam::setF32(float, int, int, bool, bool)" gdt_hooks::set_f32_array,//_ZN4ksys3gdt12TriggerParam6setF32Efiibb
+    0x00de656c  000492 "ksys::gdt::TriggerParam::setStr64(char const*, int, int, bool, bool)" gdt_hooks::set_str_array::<gdt::fd!(str64[])>,//_ZN4ksys3gdt12TriggerParam8setStr64EPKciibb
+    0x00de6758  000492 "ksys::gdt::TriggerParam::setStr256(char const*, int, int, bool, bool)" gdt_hooks::set_str_array::<gdt::fd!(str256[])>,//_ZN4ksys3gdt12TriggerParam9setStr256EPKciibb
+    0x00de6944  000224 "ksys::gdt::TriggerParam::setVec2f(sead::Vector2<float> const&, int, int, bool, bool)" gdt_hooks::set_vec2f_array,//_ZN4ksys3gdt12TriggerParam8setVec2fERKN4sead7Vector2IfEEiibb
+    0x00de6a24  000224 "ksys::gdt::TriggerParam::setVec3f(sead::Vector3<float> const&, int, int, bool, bool)" gdt_hooks::set_vec3f_array,//_ZN4ksys3gdt12TriggerParam8setVec3fERKN4sead7Vector3IfEEiibb
+    0x00de6b04  000260 "ksys::gdt::TriggerParam::setBool(bool, sead::SafeStringBase<char> const&, int, bool, bool, bool)" gdt_hooks::set_bool_array_by_name,//_ZN4ksys3gdt12TriggerParam7setBoolEbRKN4sead14SafeStringBaseIcEEibbb
+    0x00de6c08  000260 "ksys::gdt::TriggerParam::setS32(int, sead::SafeStringBase<char> const&, int, bool, bool, bool)" gdt_hooks::set_s32_array_by_name,//_ZN4ksys3gdt12TriggerParam6setS32EiRKN4sead14SafeStringBaseIcEEibbb
+    0x00de6d0c  000268 "ksys::gdt::TriggerParam::setF32(float, sead::SafeStringBase<char> const&, int, bool, bool, bool)" gdt_hooks::set_f32_array_by_name,//_ZN4ksys3gdt12TriggerParam6setF32EfRKN4sead14SafeStringBaseIcEEibbb
+    0x00de6e18  000260 "ksys::gdt::TriggerParam::setStr64(char const*, sead::SafeStringBase<char> const&, int, bool, bool, bool)" gdt_hooks::set_str_array_by_name::<gdt::fd!(str64[])>,//_ZN4ksys3gdt12TriggerParam8setStr64EPKcRKN4sead14SafeStringBaseIcEEibbb
+    0x00de6f1c  000232 "ksys::gdt::TriggerParam::resetBool(int, bool)" gdt_hooks::reset::<gdt::fd!(bool)>, // resetBool
+    0x00de7004  000232 "ksys::gdt::TriggerParam::resetS32(int, bool)" gdt_hooks::reset::<gdt::fd!(s32)>,  // resetS32
+    0x00de70ec  000232 "ksys::gdt::TriggerParam::resetF32(int, bool)" gdt_hooks::reset::<gdt::fd!(f32)>,//_ZN4ksys3gdt12TriggerParam8resetF32Eib
+    0x00de71d4  000232 "ksys::gdt::TriggerParam::resetStr64(int, bool)" gdt_hooks::reset::<gdt::fd!(str64)>,//_ZN4ksys3gdt12TriggerParam10resetStr64Eib
+    0x00de72bc  000232 "ksys::gdt::TriggerParam::resetVec3f(int, bool)" gdt_hooks::reset::<gdt::fd!(vec3f)>,//_ZN4ksys3gdt12TriggerParam10resetVec3fEib
+    0x00de73a4  000272 "ksys::gdt::TriggerParam::resetBool(sead::SafeStringBase<char> const&, bool, bool)" gdt_hooks::reset_by_name::<gdt::fd!(bool)>,//_ZN4ksys3gdt12TriggerParam9resetBoolERKN4sead14SafeStringBaseIcEEbb
+    0x00de74b4  000272 "ksys::gdt::TriggerParam::resetS32(sead::SafeStringBase<char> const&, bool, bool)" gdt_hooks::reset_by_name::<gdt::fd!(s32)>,//_ZN4ksys3gdt12TriggerParam8resetS32ERKN4sead14SafeStringBaseIcEEbb
+    0x00de75c4  000272 "ksys::gdt::TriggerParam::resetF32(sead::SafeStringBase<char> const&, bool, bool)" gdt_hooks::reset_by_name::<gdt::fd!(f32)>,//_ZN4ksys3gdt12TriggerParam8resetF32ERKN4sead14SafeStringBaseIcEEbb
+    0x00de76d4  000272 "ksys::gdt::TriggerParam::resetVec3f(sead::SafeStringBase<char> const&, bool, bool)" gdt_hooks::reset_by_name::<gdt::fd!(vec3f)>,//_ZN4ksys3gdt12TriggerParam10resetVec3fERKN4sead14SafeStringBaseIcEEbb
+    0x00de77e4  000284 "ksys::gdt::TriggerParam::resetBool(int, int, bool)" gdt_hooks::reset_array::<gdt::fd!(bool[])>,//_ZN4ksys3gdt12TriggerParam9resetBoolEiib
+    0x00de7900  000284 "ksys::gdt::TriggerParam::resetS32(int, int, bool)" gdt_hooks::reset_array::<gdt::fd!(s32[])>,//_ZN4ksys3gdt12TriggerParam8resetS32Eiib
+    0x00de7a1c  000284 "ksys::gdt::TriggerParam::resetF32(int, int, bool)" gdt_hooks::reset_array::<gdt::fd!(f32[])>,//_ZN4ksys3gdt12TriggerParam8resetF32Eiib
+    0x00de7b38  000284 "ksys::gdt::TriggerParam::resetStr(int, int, bool)" return_false,//_ZN4ksys3gdt12TriggerParam8resetStrEiib
+    0x00de7c54  000284 "ksys::gdt::TriggerParam::resetStr64(int, int, bool)" gdt_hooks::reset_array::<gdt::fd!(str64[])>,//_ZN4ksys3gdt12TriggerParam10resetStr64Eiib
+    0x00de7d70  000284 "ksys::gdt::TriggerParam::resetStr256(int, int, bool)" gdt_hooks::reset_array::<gdt::fd!(str256[])>,//_ZN4ksys3gdt12TriggerParam11resetStr256Eiib
+    0x00de7e8c  000284 "ksys::gdt::TriggerParam::resetVec2f(int, int, bool)" gdt_hooks::reset_array::<gdt::fd!(vec2f[])>,//_ZN4ksys3gdt12TriggerParam10resetVec2fEiib
+    0x00de7fa8  000284 "ksys::gdt::TriggerParam::resetVec3f(int, int, bool)" gdt_hooks::reset_array::<gdt::fd!(vec3f[])>,//_ZN4ksys3gdt12TriggerParam10resetVec3fEiib
+    0x00de80c4  000284 "ksys::gdt::TriggerParam::resetVec4f(int, int, bool)" return_false,//_ZN4ksys3gdt12TriggerParam10resetVec4fEiib
+        // not doing copyFlags stuff
+    0x00deeb8c  002628 "ksys::gdt::TriggerParam::resetAllFlagsToInitialValues()" gdt_hooks::reset_all,//_ZN4ksys3gdt12TriggerParam28resetAllFlagsToInitialValuesEv
 
-        // --- .plt
-        0x018001d0  000016 memcpy,
-        0x018001e0  000016 return_true, // __cxa_guard_acquire
-        0x01800260  000016 memset,
-        0x01800270  000016 return_true, // nn::os::GetSystemTick
-        0x01800760  000016 strcmp,
-        0x01800a10  000016 return_void, // nn::os::LockMutex
-        0x01800a20  000016 return_void, // nn::os::UnlockMutex
-        0x01800bf0  000016 vsnprintf,   // nn::util::VSNPrintf
+    0x00df08b8  000184 "ksys::gdt::TriggerParam::getBoolIdx(unsigned int) const" gdt_hooks::idx_from_hash::<gdt::fd!(bool)>, // getBoolIdx by hash
+    0x00df0970  000184 "ksys::gdt::TriggerParam::getS32Idx(unsigned int) const" gdt_hooks::idx_from_hash::<gdt::fd!(s32)>,  // getS32Idx by hash
+    0x00df0a28  000184 "ksys::gdt::TriggerParam::getF32Idx(unsigned int) const" gdt_hooks::idx_from_hash::<gdt::fd!(f32)>,//_ZNK4ksys3gdt12TriggerParam9getF32IdxEj
+    0x00df0ae0  000184 "ksys::gdt::TriggerParam::getStrIdx(unsigned int) const" gdt_hooks::idx_from_hash::<gdt::fd!(str32)>,//_ZNK4ksys3gdt12TriggerParam9getStrIdxEj
+    0x00df0b98  000184 "ksys::gdt::TriggerParam::getStr64Idx(unsigned int) const" gdt_hooks::idx_from_hash::<gdt::fd!(str64)>,//_ZNK4ksys3gdt12TriggerParam11getStr64IdxEj
+    0x00df0c50  000184 "ksys::gdt::TriggerParam::getStr256Idx(unsigned int) const" gdt_hooks::idx_from_hash::<gdt::fd!(str256)>,//_ZNK4ksys3gdt12TriggerParam12getStr256IdxEj
+    0x00df0d08  000184 "ksys::gdt::TriggerParam::getVec2fIdx(unsigned int) const" gdt_hooks::idx_from_hash::<gdt::fd!(vec2f)>,//_ZNK4ksys3gdt12TriggerParam11getVec2fIdxEj
+    0x00df0dc0  000184 "ksys::gdt::TriggerParam::getVec3fIdx(unsigned int) const" gdt_hooks::idx_from_hash::<gdt::fd!(vec3f)>,//_ZNK4ksys3gdt12TriggerParam11getVec3fIdxEj
+    0x00df0e78  000144 "ksys::gdt::TriggerParam::getBoolArrayIdx(unsigned int) const" gdt_hooks::idx_from_hash::<gdt::fd!(bool[])>,//_ZNK4ksys3gdt12TriggerParam15getBoolArrayIdxEj
+    0x00df0f08  000144 "ksys::gdt::TriggerParam::getS32ArrayIdx(unsigned int) const" gdt_hooks::idx_from_hash::<gdt::fd!(s32[])>,//_ZNK4ksys3gdt12TriggerParam14getS32ArrayIdxEj
+    0x00df0f98  000144 "ksys::gdt::TriggerParam::getF32ArrayIdx(unsigned int) const" gdt_hooks::idx_from_hash::<gdt::fd!(f32[])>,//_ZNK4ksys3gdt12TriggerParam14getF32ArrayIdxEj
+    0x00df1028  000144 "ksys::gdt::TriggerParam::getStr64ArrayIdx(unsigned int) const" gdt_hooks::idx_from_hash::<gdt::fd!(str64[])>,//_ZNK4ksys3gdt12TriggerParam16getStr64ArrayIdxEj
+    0x00df10b8  000144 "ksys::gdt::TriggerParam::getStr256ArrayIdx(unsigned int) const" gdt_hooks::idx_from_hash::<gdt::fd!(str256[])>,//_ZNK4ksys3gdt12TriggerParam17getStr256ArrayIdxEj
+    0x00df1148  000144 "ksys::gdt::TriggerParam::getVec2fArrayIdx(unsigned int) const" gdt_hooks::idx_from_hash::<gdt::fd!(vec2f[])>,//_ZNK4ksys3gdt12TriggerParam16getVec2fArrayIdxEj
+    0x00df11d8  000144 "ksys::gdt::TriggerParam::getVec3fArrayIdx(unsigned int) const" gdt_hooks::idx_from_hash::<gdt::fd!(vec3f[])>,//_ZNK4ksys3gdt12TriggerParam16getVec3fArrayIdxEj
+    //
+    0x00e491d4  000332 "EventMgr::auto1" return_void, // EventMgr::auto1 (called from doGetItem)
 
-        };
+    0x011f3364  000032 "ksys::util::getDebugHeap" return_0,
 
-        Ok(None)
-    }
+    // --- .plt
+    0x018001d0  000016 "memcpy" memcpy,
+    0x018001e0  000016 "__cxa_guard_acquire" return_true,
+    0x01800260  000016 "memset" memset,
+    0x01800270  000016 "nn::os::GetSystemTick" return_true,
+    0x01800760  000016 "strcmp" strcmp,
+    0x01800a10  000016 "nn::os::LockMutex" return_void,
+    0x01800a20  000016 "nn::os::UnlockMutex" return_void,
+    0x01800bf0  000016 "nn::util::VSNPrintf" vsnprintf,
 }
 
 fn get_player(cpu: &mut Cpu0, _: &mut Process) -> Result<(), processor::Error> {
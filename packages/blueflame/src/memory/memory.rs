@@ -3,7 +3,7 @@ use std::sync::Arc;
 use crate::env::{Environment, enabled};
 use crate::memory::{
     AccessFlag, AccessFlags, Error, PAGE_SIZE, Page, Ptr, REGION_ALIGN, Reader, Section,
-    SimpleHeap, Writer, align_up, perm, region,
+    SimpleHeap, WatchAccess, Watchpoint, Writer, align_up, perm, region,
 };
 use crate::program::ArchivedModule;
 
@@ -15,6 +15,9 @@ pub struct Memory {
     heap: SimpleHeap,
     program_start: u64,
     stack_end: u64,
+    /// Watchpoints checked on every read and write, `None` if there are none
+    /// so the check is cheap
+    watchpoints: Option<Arc<[Watchpoint]>>,
 }
 
 impl Memory {
@@ -35,6 +38,7 @@ impl Memory {
             heap,
             program_start: 0,
             stack_end,
+            watchpoints: None,
         }
     }
 
@@ -84,6 +88,7 @@ impl Memory {
             heap,
             program_start,
             stack_end,
+            watchpoints: None,
         })
     }
 
@@ -106,6 +111,48 @@ impl Memory {
         self.stack_end
     }
 
+    /// Get the sections in the memory, sorted by start address
+    pub fn sections(&self) -> impl Iterator<Item = &Section> {
+        self.sections.iter().map(Arc::as_ref)
+    }
+
+    /// Get the watchpoints set on the memory
+    pub fn watchpoints(&self) -> &[Watchpoint] {
+        self.watchpoints.as_deref().unwrap_or_default()
+    }
+
+    /// Check if any watchpoint is set on the memory
+    #[inline(always)]
+    pub fn has_watchpoints(&self) -> bool {
+        self.watchpoints.is_some()
+    }
+
+    /// Replace the watchpoints on the memory. Accesses that hit
+    /// them are recorded and can be taken with [`take_watch_records`](crate::memory::take_watch_records)
+    pub fn set_watchpoints(&mut self, watchpoints: Vec<Watchpoint>) {
+        self.watchpoints = if watchpoints.is_empty() {
+            None
+        } else {
+            Some(watchpoints.into())
+        };
+    }
+
+    /// Find the first watchpoint hit by the access, returns the index
+    /// of the watchpoint and if it should break
+    #[inline(always)]
+    pub(crate) fn find_watchpoint(
+        &self,
+        addr: u64,
+        size: u32,
+        access: WatchAccess,
+    ) -> Option<(usize, bool)> {
+        let watchpoints = self.watchpoints.as_deref()?;
+        watchpoints
+            .iter()
+            .position(|w| w.matches(addr, size, access))
+            .map(|i| (i, watchpoints[i].break_on_hit))
+    }
+
     /// Format the address as section+offset
     pub fn format_addr(&self, addr: u64) -> String {
         let i = match self.sections.binary_search_by_key(&addr, |s| s.start()) {
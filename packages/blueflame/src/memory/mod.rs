@@ -22,6 +22,8 @@ pub use write::*;
 
 mod section;
 pub use section::*;
+mod watch;
+pub use watch::*;
 
 #[doc(hidden)]
 pub mod traits;
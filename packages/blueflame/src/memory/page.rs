@@ -23,6 +23,12 @@ impl Page {
         page
     }
 
+    /// Get the data of the page
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
     /// Read a u8 at offset without checking permissions
     #[inline(always)]
     pub fn read_u8(&self, off: u32) -> u8 {
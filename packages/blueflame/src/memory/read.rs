@@ -2,7 +2,10 @@ use derive_more::derive::Constructor;
 
 #[cfg(feature = "trace-memory")]
 use crate::memory::PAGE_SIZE;
-use crate::memory::{AccessFlags, Error, Memory, Page};
+use crate::memory::{
+    AccessFlag, AccessFlags, Error, Memory, Page, WatchAccess, WatchAccessRecord,
+    record_watch_access,
+};
 
 #[cfg(feature = "trace-memory")]
 static READS: std::sync::LazyLock<
@@ -108,6 +111,7 @@ impl<'m> Reader<'m> {
             Err(Error::Bypassed) => false,
             Err(e) => return Err(e),
         };
+        self.check_watch(1, val as u64);
         trace!(bool, self.addr, self.memory.format_addr(self.addr), val);
         self.skip(1);
 
@@ -121,6 +125,7 @@ impl<'m> Reader<'m> {
             Err(Error::Bypassed) => 0,
             Err(e) => return Err(e),
         };
+        self.check_watch(1, val as u64);
         trace!(1, self.addr, self.memory.format_addr(self.addr), val, 2);
         self.skip(1);
 
@@ -140,6 +145,7 @@ impl<'m> Reader<'m> {
             Err(Error::Bypassed) => 0,
             Err(e) => return Err(e),
         };
+        self.check_watch(2, val as u64);
         trace!(2, self.addr, self.memory.format_addr(self.addr), val, 4);
         self.skip(2);
 
@@ -159,6 +165,7 @@ impl<'m> Reader<'m> {
             Err(Error::Bypassed) => 0,
             Err(e) => return Err(e),
         };
+        self.check_watch(4, val as u64);
         trace!(4, self.addr, self.memory.format_addr(self.addr), val, 8);
         self.skip(4);
 
@@ -178,6 +185,7 @@ impl<'m> Reader<'m> {
             Err(Error::Bypassed) => 0,
             Err(e) => return Err(e),
         };
+        self.check_watch(8, val);
         trace!(8, self.addr, self.memory.format_addr(self.addr), val, 16);
         self.skip(8);
 
@@ -202,6 +210,29 @@ impl<'m> Reader<'m> {
         Ok(f64::from_bits(val).into())
     }
 
+    /// Record the read if it hits a watchpoint
+    #[inline(always)]
+    fn check_watch(&self, len: u8, val: u64) {
+        if !self.memory.has_watchpoints() || self.flags.any(AccessFlag::Execute) {
+            // instruction fetches don't count as reads
+            return;
+        }
+        if let Some((watchpoint, break_on_hit)) =
+            self.memory
+                .find_watchpoint(self.addr, len as u32, WatchAccess::Read)
+        {
+            record_watch_access(WatchAccessRecord {
+                watchpoint,
+                access: WatchAccess::Read,
+                addr: self.addr,
+                size: len,
+                old_value: val,
+                new_value: val,
+                break_on_hit,
+            });
+        }
+    }
+
     /// Prepare a read
     ///
     /// First it will make sure the region and page reference are valid,
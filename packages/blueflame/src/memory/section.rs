@@ -131,6 +131,11 @@ impl Section {
         self.pages.len() as u32 * PAGE_SIZE
     }
 
+    /// Get the pages in this section
+    pub fn pages(&self) -> impl Iterator<Item = &Page> {
+        self.pages.iter().map(Arc::as_ref)
+    }
+
     /// Get a page by page index without checking bounds.
     ///
     /// Unsafe: will panic if the index is out of bounds, so you
@@ -0,0 +1,152 @@
+use std::cell::RefCell;
+use std::ops::Range;
+
+/// Which accesses trigger a watchpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WatchKind {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+impl WatchKind {
+    /// Check if the watch kind includes the access
+    #[inline(always)]
+    pub fn matches(self, access: WatchAccess) -> bool {
+        match self {
+            Self::Read => access == WatchAccess::Read,
+            Self::Write => access == WatchAccess::Write,
+            Self::ReadWrite => true,
+        }
+    }
+}
+
+/// The access that triggered a watchpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WatchAccess {
+    Read,
+    Write,
+}
+
+impl std::fmt::Display for WatchAccess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read => write!(f, "read"),
+            Self::Write => write!(f, "write"),
+        }
+    }
+}
+
+/// A range of physical addresses to watch for reads and/or writes
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Watchpoint {
+    /// Physical address range being watched
+    pub range: Range<u64>,
+    pub kind: WatchKind,
+    /// Stop execution with an error when the watchpoint is hit
+    pub break_on_hit: bool,
+}
+
+impl Watchpoint {
+    /// Check if an access of `size` bytes at `addr` triggers the watchpoint
+    #[inline(always)]
+    pub fn matches(&self, addr: u64, size: u32, access: WatchAccess) -> bool {
+        self.kind.matches(access) && self.range.start < addr + size as u64 && addr < self.range.end
+    }
+}
+
+/// A memory access that triggered a watchpoint, before the processor
+/// adds the PC and stack trace to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchAccessRecord {
+    /// Index of the watchpoint that was hit
+    pub watchpoint: usize,
+    pub access: WatchAccess,
+    /// Physical address that was accessed
+    pub addr: u64,
+    /// Size of the access in bytes
+    pub size: u8,
+    /// Value in memory before the access
+    pub old_value: u64,
+    /// Value in memory after the access (same as the old value for reads)
+    pub new_value: u64,
+    pub break_on_hit: bool,
+}
+
+thread_local! {
+    static PENDING: RefCell<Vec<WatchAccessRecord>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Record an access that triggered a watchpoint on the current thread.
+///
+/// Readers only have a shared reference to the memory, so hits are
+/// buffered here until the processor collects them with [`take_watch_records`]
+pub(crate) fn record_watch_access(record: WatchAccessRecord) {
+    PENDING.with_borrow_mut(|pending| pending.push(record))
+}
+
+/// Take the accesses that triggered watchpoints on the current thread
+/// since the last call
+pub fn take_watch_records() -> Vec<WatchAccessRecord> {
+    PENDING.with_borrow_mut(std::mem::take)
+}
+
+/// Discard the accesses that triggered watchpoints on the current thread
+/// that are not collected yet
+pub fn clear_watch_records() {
+    PENDING.with_borrow_mut(|pending| pending.clear())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{Memory, Ptr};
+
+    #[test]
+    pub fn test_watchpoints() -> anyhow::Result<()> {
+        let mut mem = Memory::new_for_test();
+        mem.set_watchpoints(vec![
+            Watchpoint {
+                range: 0x500..0x508,
+                kind: WatchKind::Write,
+                break_on_hit: false,
+            },
+            Watchpoint {
+                range: 0x600..0x604,
+                kind: WatchKind::ReadWrite,
+                break_on_hit: true,
+            },
+        ]);
+        take_watch_records();
+
+        Ptr!(<u32>(0x504)).store(&5, &mut mem)?;
+        Ptr!(<u32>(0x504)).store(&6, &mut mem)?;
+        // not watched for read
+        Ptr!(<u32>(0x504)).load(&mem)?;
+        // not in range
+        Ptr!(<u32>(0x508)).store(&7, &mut mem)?;
+        let records = take_watch_records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].watchpoint, 0);
+        assert_eq!(records[0].access, WatchAccess::Write);
+        assert_eq!(records[0].addr, 0x504);
+        assert_eq!(records[0].size, 4);
+        assert_eq!(records[1].old_value, 5);
+        assert_eq!(records[1].new_value, 6);
+        assert!(!records[1].break_on_hit);
+
+        // overlapping the range
+        Ptr!(<u64>(0x5fc)).load(&mem)?;
+        let records = take_watch_records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].watchpoint, 1);
+        assert_eq!(records[0].access, WatchAccess::Read);
+        assert!(records[0].break_on_hit);
+
+        mem.set_watchpoints(vec![]);
+        assert!(!mem.has_watchpoints());
+        Ptr!(<u32>(0x600)).store(&1, &mut mem)?;
+        assert!(take_watch_records().is_empty());
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 use derive_more::derive::Constructor;
 
-use crate::memory::{AccessFlags, Error, Memory};
+use crate::memory::{
+    AccessFlags, Error, Memory, WatchAccess, WatchAccessRecord, record_watch_access,
+};
+use crate::trace;
 
 /// Stream writer to memory
 #[derive(Constructor)]
@@ -62,10 +65,18 @@ impl<'m> Writer<'m> {
         };
         let val: bool = val.into();
         trace!(bool, self.memory.format_addr(self.addr), val);
+        let watch = self
+            .memory
+            .find_watchpoint(self.addr, 1, WatchAccess::Write);
         let page = self
             .memory
             .page_by_indices_mut_unchecked(self.section_idx, self.page_idx);
+        let old_value = watch.map(|_| page.read_u8(self.page_off) as u64);
         page.write_u8(self.page_off, if val { 1 } else { 0 });
+        if let Some((watch, old_value)) = watch.zip(old_value) {
+            self.record_watch(watch, 1, old_value, val as u64);
+        }
+        trace::on_mem_write(self.addr, 1, val as u64);
         self.skip(1);
         Ok(())
     }
@@ -79,10 +90,18 @@ impl<'m> Writer<'m> {
         };
         let val: u8 = val.into();
         trace!(1, self.memory.format_addr(self.addr), val, 2);
+        let watch = self
+            .memory
+            .find_watchpoint(self.addr, 1, WatchAccess::Write);
         let page = self
             .memory
             .page_by_indices_mut_unchecked(self.section_idx, self.page_idx);
+        let old_value = watch.map(|_| page.read_u8(self.page_off) as u64);
         page.write_u8(self.page_off, val);
+        if let Some((watch, old_value)) = watch.zip(old_value) {
+            self.record_watch(watch, 1, old_value, val as u64);
+        }
+        trace::on_mem_write(self.addr, 1, val as u64);
         self.skip(1);
         Ok(())
     }
@@ -101,10 +120,18 @@ impl<'m> Writer<'m> {
         };
         let val: u16 = val.into();
         trace!(2, self.memory.format_addr(self.addr), val, 4);
+        let watch = self
+            .memory
+            .find_watchpoint(self.addr, 2, WatchAccess::Write);
         let page = self
             .memory
             .page_by_indices_mut_unchecked(self.section_idx, self.page_idx);
+        let old_value = watch.map(|_| page.read_u16(self.page_off) as u64);
         page.write_u16(self.page_off, val);
+        if let Some((watch, old_value)) = watch.zip(old_value) {
+            self.record_watch(watch, 2, old_value, val as u64);
+        }
+        trace::on_mem_write(self.addr, 2, val as u64);
         self.skip(2);
         Ok(())
     }
@@ -123,10 +150,18 @@ impl<'m> Writer<'m> {
         };
         let val: u32 = val.into();
         trace!(4, self.memory.format_addr(self.addr), val, 8);
+        let watch = self
+            .memory
+            .find_watchpoint(self.addr, 4, WatchAccess::Write);
         let page = self
             .memory
             .page_by_indices_mut_unchecked(self.section_idx, self.page_idx);
+        let old_value = watch.map(|_| page.read_u32(self.page_off) as u64);
         page.write_u32(self.page_off, val);
+        if let Some((watch, old_value)) = watch.zip(old_value) {
+            self.record_watch(watch, 4, old_value, val as u64);
+        }
+        trace::on_mem_write(self.addr, 4, val as u64);
         self.skip(4);
         Ok(())
     }
@@ -145,10 +180,18 @@ impl<'m> Writer<'m> {
         };
         let val: u64 = val.into();
         trace!(8, self.memory.format_addr(self.addr), val, 16);
+        let watch = self
+            .memory
+            .find_watchpoint(self.addr, 8, WatchAccess::Write);
         let page = self
             .memory
             .page_by_indices_mut_unchecked(self.section_idx, self.page_idx);
+        let old_value = watch.map(|_| page.read_u64(self.page_off));
         page.write_u64(self.page_off, val);
+        if let Some((watch, old_value)) = watch.zip(old_value) {
+            self.record_watch(watch, 8, old_value, val);
+        }
+        trace::on_mem_write(self.addr, 8, val);
         self.skip(8);
         Ok(())
     }
@@ -170,6 +213,20 @@ impl<'m> Writer<'m> {
         self.write_u64(val)
     }
 
+    /// Record a write that hit a watchpoint
+    #[cold]
+    fn record_watch(&self, (watchpoint, break_on_hit): (usize, bool), len: u8, old: u64, new: u64) {
+        record_watch_access(WatchAccessRecord {
+            watchpoint,
+            access: WatchAccess::Write,
+            addr: self.addr,
+            size: len,
+            old_value: old,
+            new_value: new,
+            break_on_hit,
+        });
+    }
+
     /// Prepare a write, then operate on the page
     ///
     /// This must be done through a FnOnce closure because of borrowing rules
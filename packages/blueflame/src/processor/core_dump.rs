@@ -0,0 +1,744 @@
+use std::ops::Range;
+
+use crate::env::GameVer;
+use crate::memory::{AccessFlag, AccessFlags, Section};
+use crate::processor::{Cpu0, Flags, Process, RegName};
+
+// ELF constants used by the core dump
+const ELF_MAGIC: &[u8; 4] = b"\x7fELF";
+const ELFCLASS64: u8 = 2;
+const ELFDATA2LSB: u8 = 1;
+const EV_CURRENT: u8 = 1;
+const ET_CORE: u16 = 4;
+const EM_AARCH64: u16 = 183;
+const EHDR_SIZE: usize = 0x40;
+const PHDR_SIZE: usize = 0x38;
+const SHDR_SIZE: usize = 0x40;
+const PT_LOAD: u32 = 1;
+const PT_NOTE: u32 = 4;
+const PF_X: u32 = 0x1;
+const PF_W: u32 = 0x2;
+const PF_R: u32 = 0x4;
+const SHT_PROGBITS: u32 = 1;
+const SHT_STRTAB: u32 = 3;
+const SHF_WRITE: u64 = 0x1;
+const SHF_ALLOC: u64 = 0x2;
+const SHF_EXECINSTR: u64 = 0x4;
+const NT_PRSTATUS: u32 = 1;
+const NT_FPREGSET: u32 = 2;
+/// Type of the BlueFlame note, which has the main start and game version
+const NT_BLUEFLAME_ENV: u32 = 1;
+const NOTE_NAME_CORE: &str = "CORE";
+const NOTE_NAME_BLUEFLAME: &str = "BLUEFLAME";
+/// Size of `elf_prstatus` on aarch64
+const PRSTATUS_SIZE: usize = 392;
+/// Offset of `pr_reg` in `elf_prstatus` on aarch64
+const PRSTATUS_REG_OFFSET: usize = 112;
+/// Size of `user_fpsimd_state` on aarch64
+const FPREGSET_SIZE: usize = 528;
+
+/// Errors when reading a core dump
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CoreDumpError {
+    #[error("not a 64-bit little-endian AArch64 ELF core file")]
+    NotCoreFile,
+    #[error("the core file is truncated or has invalid offsets")]
+    Truncated,
+    #[error("the core file is missing the {0} note")]
+    MissingNote(&'static str),
+    #[error("unknown game version in the core file: {0}")]
+    UnknownGameVer(u32),
+}
+
+/// Register state in a core dump
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoreRegisters {
+    /// X0-X30
+    pub x: [u64; 31],
+    pub sp: u64,
+    pub pc: u64,
+    /// NZCV flags in the same bit positions as PSTATE
+    pub pstate: u64,
+    /// Q0-Q31 as (low, high)
+    pub v: [(u64, u64); 32],
+}
+
+impl CoreRegisters {
+    fn new(cpu: &Cpu0) -> Self {
+        let mut x = [0; 31];
+        for (i, x) in x.iter_mut().enumerate() {
+            *x = cpu.read(RegName::x(i as u8));
+        }
+        let mut v = [(0, 0); 32];
+        for (i, v) in v.iter_mut().enumerate() {
+            *v = cpu.read(RegName::q(i as u8));
+        }
+        let Flags {
+            n,
+            z,
+            c,
+            v: overflow,
+        } = cpu.flags;
+        let pstate = ((n as u64) << 31)
+            | ((z as u64) << 30)
+            | ((c as u64) << 29)
+            | ((overflow as u64) << 28);
+        Self {
+            x,
+            sp: cpu.read(RegName::sp()),
+            pc: cpu.pc,
+            pstate,
+            v,
+        }
+    }
+}
+
+/// A memory section in a core dump
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoreSegment {
+    /// Name of the section, which is the module name and the region
+    /// (for example `main.text`), or just the region for stack and heap
+    pub name: String,
+    /// Physical address of the start of the section
+    pub start: u64,
+    /// Permission and region flags of the section
+    pub flags: AccessFlags,
+    pub data: Vec<u8>,
+}
+
+impl CoreSegment {
+    fn new(section: &Section) -> Self {
+        let region = [
+            (AccessFlag::Text, "text"),
+            (AccessFlag::Rodata, "rodata"),
+            (AccessFlag::Data, "data"),
+        ]
+        .into_iter()
+        .find(|(flag, _)| section.flags.any(*flag));
+        let name = match region {
+            Some((_, region)) => format!("{}.{region}", section.module_name),
+            None => section.module_name.clone(),
+        };
+        let mut data = Vec::with_capacity(section.len_bytes() as usize);
+        for page in section.pages() {
+            data.extend_from_slice(page.as_bytes());
+        }
+        Self {
+            name,
+            start: section.start(),
+            flags: section.flags,
+            data,
+        }
+    }
+
+    /// Get the end physical address of the section
+    pub fn end(&self) -> u64 {
+        self.start + self.data.len() as u64
+    }
+
+    fn program_flags(&self) -> u32 {
+        let mut flags = 0;
+        if self.flags.any(AccessFlag::Read) {
+            flags |= PF_R;
+        }
+        if self.flags.any(AccessFlag::Write) {
+            flags |= PF_W;
+        }
+        if self.flags.any(AccessFlag::Execute) {
+            flags |= PF_X;
+        }
+        flags
+    }
+
+    fn section_flags(&self) -> u64 {
+        let mut flags = SHF_ALLOC;
+        if self.flags.any(AccessFlag::Write) {
+            flags |= SHF_WRITE;
+        }
+        if self.flags.any(AccessFlag::Execute) {
+            flags |= SHF_EXECINSTR;
+        }
+        flags
+    }
+}
+
+/// Snapshot of a process and the CPU registers, which can be exported
+/// as an ELF core file to be loaded in tools like Ghidra, IDA or gdb.
+///
+/// The core file has:
+/// - A `PT_LOAD` segment and a named section for each memory section
+/// - `NT_PRSTATUS` and `NT_FPREGSET` notes with the registers
+/// - A `BLUEFLAME` note with the main start and the game version
+///
+/// The raw flags of each memory section are stored in `sh_info`
+/// of the section, so the core dump can be re-imported with [`CoreDump::from_elf`]
+/// and compared with [`find_differences`](CoreDump::find_differences)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoreDump {
+    /// Physical address of the start of the main module
+    pub main_start: u64,
+    pub game_ver: GameVer,
+    pub registers: CoreRegisters,
+    /// Memory sections, sorted by start address
+    pub segments: Vec<CoreSegment>,
+}
+
+impl CoreDump {
+    /// Take a snapshot of the process memory and the CPU registers
+    pub fn new(proc: &Process, cpu: &Cpu0) -> Self {
+        let memory = proc.memory();
+        let mut segments: Vec<_> = memory.sections().map(CoreSegment::new).collect();
+        segments.sort_by_key(|s| s.start);
+        Self {
+            main_start: memory.main_start(),
+            game_ver: proc.env().game_ver,
+            registers: CoreRegisters::new(cpu),
+            segments,
+        }
+    }
+
+    /// Export as an ELF core file
+    pub fn to_elf(&self) -> Vec<u8> {
+        let notes = self.encode_notes();
+
+        // strtab for section names
+        let mut shstrtab = vec![0u8];
+        let mut name_offsets = Vec::with_capacity(self.segments.len());
+        for segment in &self.segments {
+            name_offsets.push(shstrtab.len() as u32);
+            shstrtab.extend_from_slice(segment.name.as_bytes());
+            shstrtab.push(0);
+        }
+        let shstrtab_name = shstrtab.len() as u32;
+        shstrtab.extend_from_slice(b".shstrtab\0");
+
+        // layout: ehdr, phdrs, notes, segment data, shstrtab, shdrs
+        let phnum = self.segments.len() + 1;
+        let shnum = self.segments.len() + 2; // null + segments + shstrtab
+        let notes_offset = EHDR_SIZE + PHDR_SIZE * phnum;
+        let mut data_offset = align_usize(notes_offset + notes.len(), 0x1000);
+        let mut segment_offsets = Vec::with_capacity(self.segments.len());
+        for segment in &self.segments {
+            segment_offsets.push(data_offset);
+            data_offset += segment.data.len();
+        }
+        let shstrtab_offset = data_offset;
+        let shdrs_offset = align_usize(shstrtab_offset + shstrtab.len(), 8);
+        let total_size = shdrs_offset + SHDR_SIZE * shnum;
+
+        let mut out = Vec::with_capacity(total_size);
+        // ELF header
+        out.extend_from_slice(ELF_MAGIC);
+        out.extend_from_slice(&[ELFCLASS64, ELFDATA2LSB, EV_CURRENT]);
+        out.resize(16, 0);
+        put_u16(&mut out, ET_CORE);
+        put_u16(&mut out, EM_AARCH64);
+        put_u32(&mut out, EV_CURRENT as u32);
+        put_u64(&mut out, 0); // e_entry
+        put_u64(&mut out, EHDR_SIZE as u64); // e_phoff
+        put_u64(&mut out, shdrs_offset as u64); // e_shoff
+        put_u32(&mut out, 0); // e_flags
+        put_u16(&mut out, EHDR_SIZE as u16);
+        put_u16(&mut out, PHDR_SIZE as u16);
+        put_u16(&mut out, phnum as u16);
+        put_u16(&mut out, SHDR_SIZE as u16);
+        put_u16(&mut out, shnum as u16);
+        put_u16(&mut out, (shnum - 1) as u16); // e_shstrndx
+
+        // program headers
+        put_phdr(
+            &mut out,
+            PT_NOTE,
+            0,
+            notes_offset as u64,
+            0,
+            notes.len() as u64,
+            4,
+        );
+        for (segment, offset) in self.segments.iter().zip(&segment_offsets) {
+            put_phdr(
+                &mut out,
+                PT_LOAD,
+                segment.program_flags(),
+                *offset as u64,
+                segment.start,
+                segment.data.len() as u64,
+                0x1000,
+            );
+        }
+
+        // notes and segment data
+        out.extend_from_slice(&notes);
+        for (segment, offset) in self.segments.iter().zip(&segment_offsets) {
+            out.resize(*offset, 0);
+            out.extend_from_slice(&segment.data);
+        }
+        out.extend_from_slice(&shstrtab);
+        out.resize(shdrs_offset, 0);
+
+        // section headers
+        out.resize(out.len() + SHDR_SIZE, 0); // null section
+        for ((segment, offset), name) in self
+            .segments
+            .iter()
+            .zip(&segment_offsets)
+            .zip(&name_offsets)
+        {
+            put_shdr(
+                &mut out,
+                *name,
+                SHT_PROGBITS,
+                segment.section_flags(),
+                segment.start,
+                *offset as u64,
+                segment.data.len() as u64,
+                u32::from(segment.flags),
+            );
+        }
+        put_shdr(
+            &mut out,
+            shstrtab_name,
+            SHT_STRTAB,
+            0,
+            0,
+            shstrtab_offset as u64,
+            shstrtab.len() as u64,
+            0,
+        );
+
+        debug_assert_eq!(out.len(), total_size);
+        out
+    }
+
+    fn encode_notes(&self) -> Vec<u8> {
+        let regs = &self.registers;
+        let mut prstatus = vec![0u8; PRSTATUS_REG_OFFSET];
+        for x in regs.x {
+            put_u64(&mut prstatus, x);
+        }
+        put_u64(&mut prstatus, regs.sp);
+        put_u64(&mut prstatus, regs.pc);
+        put_u64(&mut prstatus, regs.pstate);
+        put_u32(&mut prstatus, 1); // pr_fpvalid
+        prstatus.resize(PRSTATUS_SIZE, 0);
+
+        let mut fpregset = Vec::with_capacity(FPREGSET_SIZE);
+        for (lo, hi) in regs.v {
+            put_u64(&mut fpregset, lo);
+            put_u64(&mut fpregset, hi);
+        }
+        fpregset.resize(FPREGSET_SIZE, 0);
+
+        let mut env = Vec::with_capacity(16);
+        put_u64(&mut env, self.main_start);
+        put_u32(&mut env, game_ver_to_u32(self.game_ver));
+        put_u32(&mut env, 0);
+
+        let mut notes = Vec::new();
+        put_note(&mut notes, NOTE_NAME_CORE, NT_PRSTATUS, &prstatus);
+        put_note(&mut notes, NOTE_NAME_CORE, NT_FPREGSET, &fpregset);
+        put_note(&mut notes, NOTE_NAME_BLUEFLAME, NT_BLUEFLAME_ENV, &env);
+        notes
+    }
+
+    /// Read a core file exported with [`to_elf`](Self::to_elf)
+    pub fn from_elf(bytes: &[u8]) -> Result<Self, CoreDumpError> {
+        if bytes.len() < EHDR_SIZE
+            || &bytes[0..4] != ELF_MAGIC
+            || bytes[4] != ELFCLASS64
+            || bytes[5] != ELFDATA2LSB
+            || get_u16(bytes, 0x10)? != ET_CORE
+            || get_u16(bytes, 0x12)? != EM_AARCH64
+        {
+            return Err(CoreDumpError::NotCoreFile);
+        }
+        let phoff = get_usize(bytes, 0x20)?;
+        let shoff = get_usize(bytes, 0x28)?;
+        let phnum = get_u16(bytes, 0x38)? as usize;
+        let shnum = get_u16(bytes, 0x3c)? as usize;
+        let shstrndx = get_u16(bytes, 0x3e)? as usize;
+
+        let mut registers = None;
+        let mut fp = None;
+        let mut env = None;
+        let mut loads = Vec::new();
+        for i in 0..phnum {
+            let phdr = get_table_entry(bytes, phoff, i, PHDR_SIZE)?;
+            let p_type = get_u32(phdr, 0)?;
+            let offset = get_usize(phdr, 0x8)?;
+            let vaddr = get_u64(phdr, 0x10)?;
+            let filesz = get_usize(phdr, 0x20)?;
+            let data = get_slice(bytes, offset, filesz)?;
+            match p_type {
+                PT_NOTE => {
+                    for (name, n_type, desc) in NoteIter::new(data) {
+                        match (name?, n_type) {
+                            (NOTE_NAME_CORE, NT_PRSTATUS) => registers = Some(desc),
+                            (NOTE_NAME_CORE, NT_FPREGSET) => fp = Some(desc),
+                            (NOTE_NAME_BLUEFLAME, NT_BLUEFLAME_ENV) => env = Some(desc),
+                            _ => {}
+                        }
+                    }
+                }
+                PT_LOAD => {
+                    // the segment must not wrap around the address space
+                    if vaddr.checked_add(data.len() as u64).is_none() {
+                        return Err(CoreDumpError::Truncated);
+                    }
+                    loads.push((vaddr, data))
+                }
+                _ => {}
+            }
+        }
+
+        let prstatus = registers.ok_or(CoreDumpError::MissingNote("NT_PRSTATUS"))?;
+        let mut x = [0; 31];
+        for (i, x) in x.iter_mut().enumerate() {
+            *x = get_u64(prstatus, PRSTATUS_REG_OFFSET + i * 8)?;
+        }
+        let sp = get_u64(prstatus, PRSTATUS_REG_OFFSET + 31 * 8)?;
+        let pc = get_u64(prstatus, PRSTATUS_REG_OFFSET + 32 * 8)?;
+        let pstate = get_u64(prstatus, PRSTATUS_REG_OFFSET + 33 * 8)?;
+        let fpregset = fp.ok_or(CoreDumpError::MissingNote("NT_FPREGSET"))?;
+        let mut v = [(0, 0); 32];
+        for (i, v) in v.iter_mut().enumerate() {
+            *v = (get_u64(fpregset, i * 16)?, get_u64(fpregset, i * 16 + 8)?);
+        }
+        let env = env.ok_or(CoreDumpError::MissingNote(NOTE_NAME_BLUEFLAME))?;
+        let main_start = get_u64(env, 0)?;
+        let game_ver = game_ver_from_u32(get_u32(env, 8)?)?;
+
+        // names and flags are in the section headers
+        let shstrtab = if shnum > shstrndx {
+            let shdr = get_table_entry(bytes, shoff, shstrndx, SHDR_SIZE)?;
+            let offset = get_usize(shdr, 0x18)?;
+            let size = get_usize(shdr, 0x20)?;
+            get_slice(bytes, offset, size)?
+        } else {
+            &bytes[..0]
+        };
+        let mut section_info = Vec::new();
+        for i in 0..shnum {
+            let shdr = get_table_entry(bytes, shoff, i, SHDR_SIZE)?;
+            if get_u32(shdr, 0x4)? != SHT_PROGBITS {
+                continue;
+            }
+            let name = get_str(shstrtab, get_u32(shdr, 0)? as usize);
+            let addr = get_u64(shdr, 0x10)?;
+            let flags = get_u32(shdr, 0x2c)?;
+            section_info.push((addr, name, flags));
+        }
+
+        let mut segments = Vec::with_capacity(loads.len());
+        for (start, data) in loads {
+            let (name, flags) = section_info
+                .iter()
+                .find(|(addr, _, _)| *addr == start)
+                .map(|(_, name, flags)| (name.to_string(), AccessFlags::from(*flags)))
+                .unwrap_or_default();
+            segments.push(CoreSegment {
+                name,
+                start,
+                flags,
+                data: data.to_vec(),
+            });
+        }
+        segments.sort_by_key(|s| s.start);
+
+        Ok(Self {
+            main_start,
+            game_ver,
+            registers: CoreRegisters {
+                x,
+                sp,
+                pc,
+                pstate,
+                v,
+            },
+            segments,
+        })
+    }
+
+    /// Find the address ranges where the memory is different from the other core dump.
+    ///
+    /// Sections that only exist in one of the dumps are different in their entirety
+    pub fn find_differences(&self, other: &Self) -> Vec<Range<u64>> {
+        let mut differences = Vec::new();
+        for segment in &self.segments {
+            let Some(other_segment) = other.segments.iter().find(|s| s.start == segment.start)
+            else {
+                differences.push(segment.start..segment.end());
+                continue;
+            };
+            let mut diff_start = None;
+            let len = segment.data.len().max(other_segment.data.len());
+            for i in 0..len {
+                let same = segment.data.get(i) == other_segment.data.get(i);
+                match (same, diff_start) {
+                    (false, None) => diff_start = Some(i),
+                    (true, Some(start)) => {
+                        differences.push(segment.start + start as u64..segment.start + i as u64);
+                        diff_start = None;
+                    }
+                    _ => {}
+                }
+            }
+            if let Some(start) = diff_start {
+                differences.push(segment.start + start as u64..segment.start + len as u64);
+            }
+        }
+        for segment in &other.segments {
+            if !self.segments.iter().any(|s| s.start == segment.start) {
+                differences.push(segment.start..segment.end());
+            }
+        }
+        differences.sort_by_key(|r| r.start);
+        differences
+    }
+}
+
+fn game_ver_to_u32(ver: GameVer) -> u32 {
+    match ver {
+        GameVer::X150 => 150,
+        GameVer::X160 => 160,
+    }
+}
+
+fn game_ver_from_u32(ver: u32) -> Result<GameVer, CoreDumpError> {
+    match ver {
+        150 => Ok(GameVer::X150),
+        160 => Ok(GameVer::X160),
+        _ => Err(CoreDumpError::UnknownGameVer(ver)),
+    }
+}
+
+fn align_usize(x: usize, align: usize) -> usize {
+    x.div_ceil(align) * align
+}
+
+fn put_u16(out: &mut Vec<u8>, x: u16) {
+    out.extend_from_slice(&x.to_le_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, x: u32) {
+    out.extend_from_slice(&x.to_le_bytes());
+}
+
+fn put_u64(out: &mut Vec<u8>, x: u64) {
+    out.extend_from_slice(&x.to_le_bytes());
+}
+
+fn put_phdr(
+    out: &mut Vec<u8>,
+    p_type: u32,
+    flags: u32,
+    offset: u64,
+    vaddr: u64,
+    size: u64,
+    align: u64,
+) {
+    put_u32(out, p_type);
+    put_u32(out, flags);
+    put_u64(out, offset);
+    put_u64(out, vaddr); // p_vaddr
+    put_u64(out, vaddr); // p_paddr
+    put_u64(out, size); // p_filesz
+    put_u64(out, size); // p_memsz
+    put_u64(out, align);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn put_shdr(
+    out: &mut Vec<u8>,
+    name: u32,
+    sh_type: u32,
+    flags: u64,
+    addr: u64,
+    offset: u64,
+    size: u64,
+    info: u32,
+) {
+    put_u32(out, name);
+    put_u32(out, sh_type);
+    put_u64(out, flags);
+    put_u64(out, addr);
+    put_u64(out, offset);
+    put_u64(out, size);
+    put_u32(out, 0); // sh_link
+    put_u32(out, info);
+    put_u64(out, 1); // sh_addralign
+    put_u64(out, 0); // sh_entsize
+}
+
+fn put_note(out: &mut Vec<u8>, name: &str, n_type: u32, desc: &[u8]) {
+    put_u32(out, name.len() as u32 + 1);
+    put_u32(out, desc.len() as u32);
+    put_u32(out, n_type);
+    out.extend_from_slice(name.as_bytes());
+    out.push(0);
+    out.resize(align_usize(out.len(), 4), 0);
+    out.extend_from_slice(desc);
+    out.resize(align_usize(out.len(), 4), 0);
+}
+
+fn get_slice(bytes: &[u8], offset: usize, len: usize) -> Result<&[u8], CoreDumpError> {
+    offset
+        .checked_add(len)
+        .and_then(|end| bytes.get(offset..end))
+        .ok_or(CoreDumpError::Truncated)
+}
+
+/// Get entry `i` of a program or section header table
+fn get_table_entry(
+    bytes: &[u8],
+    table_offset: usize,
+    i: usize,
+    entry_size: usize,
+) -> Result<&[u8], CoreDumpError> {
+    let offset = i
+        .checked_mul(entry_size)
+        .and_then(|x| x.checked_add(table_offset))
+        .ok_or(CoreDumpError::Truncated)?;
+    get_slice(bytes, offset, entry_size)
+}
+
+fn get_u16(bytes: &[u8], offset: usize) -> Result<u16, CoreDumpError> {
+    let x = get_slice(bytes, offset, 2)?;
+    Ok(u16::from_le_bytes([x[0], x[1]]))
+}
+
+fn get_u32(bytes: &[u8], offset: usize) -> Result<u32, CoreDumpError> {
+    let x = get_slice(bytes, offset, 4)?;
+    Ok(u32::from_le_bytes([x[0], x[1], x[2], x[3]]))
+}
+
+fn get_u64(bytes: &[u8], offset: usize) -> Result<u64, CoreDumpError> {
+    let x = get_slice(bytes, offset, 8)?;
+    let mut buf = [0; 8];
+    buf.copy_from_slice(x);
+    Ok(u64::from_le_bytes(buf))
+}
+
+/// Get a 64-bit offset or size, which must fit in `usize`
+fn get_usize(bytes: &[u8], offset: usize) -> Result<usize, CoreDumpError> {
+    usize::try_from(get_u64(bytes, offset)?).map_err(|_| CoreDumpError::Truncated)
+}
+
+/// Get a null-terminated string from a string table
+fn get_str(strtab: &[u8], offset: usize) -> &str {
+    let bytes = strtab.get(offset..).unwrap_or_default();
+    let len = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    std::str::from_utf8(&bytes[..len]).unwrap_or_default()
+}
+
+/// Iterator over the notes in a `PT_NOTE` segment
+struct NoteIter<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> NoteIter<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    fn next_note(&mut self) -> Result<(&'a str, u32, &'a [u8]), CoreDumpError> {
+        let header = get_slice(self.data, self.offset, 12)?;
+        let namesz = get_u32(header, 0)? as usize;
+        let descsz = get_u32(header, 4)? as usize;
+        let n_type = get_u32(header, 8)?;
+        // the offsets are checked by get_slice, so they are within the data
+        let name_offset = self.offset + 12;
+        let name = get_slice(self.data, name_offset, namesz)?;
+        let name = get_str(name, 0);
+        let desc_offset = align_usize(name_offset + namesz, 4);
+        let desc = get_slice(self.data, desc_offset, descsz)?;
+        self.offset = align_usize(desc_offset + descsz, 4);
+        Ok((name, n_type, desc))
+    }
+}
+
+impl<'a> Iterator for NoteIter<'a> {
+    type Item = (Result<&'a str, CoreDumpError>, u32, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.data.len() {
+            return None;
+        }
+        match self.next_note() {
+            Ok((name, n_type, desc)) => Some((Ok(name), n_type, desc)),
+            Err(e) => {
+                // stop at the first invalid note
+                self.offset = self.data.len();
+                Some((Err(e), 0, &self.data[..0]))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::Ptr;
+
+    #[test]
+    pub fn test_core_dump_roundtrip() -> anyhow::Result<()> {
+        let mut proc = Process::new_for_test();
+        let mut cpu = Cpu0::default();
+        cpu.pc = 0x1234;
+        cpu.write(RegName::x(3), 0xabcd_u64);
+        cpu.write(RegName::q(5), (1u64, 2u64));
+        cpu.flags = Flags::from_nzcv(0b1010);
+        Ptr!(<u32>(0x500)).store(&0x11223344, proc.memory_mut())?;
+
+        let dump = CoreDump::new(&proc, &cpu);
+        let elf = dump.to_elf();
+        let imported = CoreDump::from_elf(&elf)?;
+        assert_eq!(imported, dump);
+        assert_eq!(imported.registers.x[3], 0xabcd);
+        assert_eq!(imported.registers.v[5], (1, 2));
+        assert_eq!(imported.registers.pstate, 0xa000_0000);
+
+        Ptr!(<u32>(0x504)).store(&1, proc.memory_mut())?;
+        let changed = CoreDump::new(&proc, &cpu);
+        assert_eq!(imported.find_differences(&changed), vec![0x504..0x505]);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_core_dump_invalid() {
+        assert_eq!(
+            CoreDump::from_elf(b"not an elf"),
+            Err(CoreDumpError::NotCoreFile)
+        );
+    }
+
+    #[test]
+    pub fn test_core_dump_malformed() {
+        let proc = Process::new_for_test();
+        let dump = CoreDump::new(&proc, &Cpu0::default());
+        let elf = dump.to_elf();
+        // truncated at any point. The section headers are at the end,
+        // and the last one is always read
+        let step = elf.len() / 256 + 1;
+        for len in (0..elf.len()).step_by(step).chain([elf.len() - 1]) {
+            assert!(CoreDump::from_elf(&elf[..len]).is_err(), "len={len}");
+        }
+        // program header table offset that overflows
+        let mut bad = elf.clone();
+        bad[0x20..0x28].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(CoreDump::from_elf(&bad), Err(CoreDumpError::Truncated));
+        // segment offset and size that overflow
+        let phoff = u64::from_le_bytes(elf[0x20..0x28].try_into().unwrap()) as usize;
+        let mut bad = elf.clone();
+        bad[phoff + 0x8..phoff + 0x10].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(CoreDump::from_elf(&bad), Err(CoreDumpError::Truncated));
+        let mut bad = elf.clone();
+        bad[phoff + 0x20..phoff + 0x28].copy_from_slice(&(u64::MAX - 1).to_le_bytes());
+        assert_eq!(CoreDump::from_elf(&bad), Err(CoreDumpError::Truncated));
+        // too many section headers
+        let mut bad = elf;
+        bad[0x3c..0x3e].copy_from_slice(&u16::MAX.to_le_bytes());
+        assert_eq!(CoreDump::from_elf(&bad), Err(CoreDumpError::Truncated));
+    }
+}
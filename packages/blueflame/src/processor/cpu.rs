@@ -5,14 +5,15 @@ use enum_map::EnumMap;
 
 use blueflame_deps::trace_call;
 
-use crate::env::{DataId, GameVer, ProxyId, enabled};
+use crate::env::{DataId, GameVer, ProxyId, enabled, symbol_map};
 use crate::game::gdt;
-use crate::memory::{MemObject, Ptr};
+use crate::memory::{MemObject, Ptr, clear_watch_records};
 use crate::processor::{
     BLOCK_COUNT_LIMIT, BLOCK_ITERATION_LIMIT, CrashReport, Error, ExecuteCache, HookChain,
-    HookProvider, Process, Registers, STACK_RESERVATION, StackTrace, reg,
+    HookProvider, Process, Registers, STACK_RESERVATION, StackTrace, collect_watch_hits, reg,
 };
 use crate::program::ArchivedProgram;
+use crate::trace::{self, TraceConfig};
 use crate::vm::VirtualMachine;
 
 const INTERNAL_RETURN_ADDRESS: u64 = 0xDEAD464C414D45AAu64;
@@ -274,7 +275,18 @@ impl Cpu2<'_, '_> {
 
     /// Run one block of execution
     pub fn execute_once(&mut self) -> Result<(), Error> {
+        let pc = self.pc;
+        let result = self.execute_once_internal();
+        // for attributing accesses from hooks, which don't
+        // go through the instruction executor
+        result.and(self.collect_watch_hits(pc))
+    }
+
+    fn execute_once_internal(&mut self) -> Result<(), Error> {
         let ver = self.proc.env().game_ver;
+        // for attributing writes from replace hooks, which don't
+        // go through the instruction executor
+        trace::set_pc(self.pc);
 
         let (fetch_max_bytes, is_hook) = match self.cpu1.cache[ver].get(self.pc) {
             Ok((exe, step)) => {
@@ -330,8 +342,42 @@ impl Cpu2<'_, '_> {
     pub fn execute_one_insn(&mut self) -> Result<(), Error> {
         // fetch one instruction directly from process and bypass size
         // check for hooks
+        trace::set_pc(self.pc);
+        let pc = self.pc;
         let (exe, _) = self.proc.fetch_execute_block(self.pc, None)?;
-        exe.execute_from(&mut self.cpu1.cpu0, self.proc, 0)
+        let result = exe.execute_from(&mut self.cpu1.cpu0, self.proc, 0);
+        result.and(self.collect_watch_hits(pc))
+    }
+
+    /// Collect the memory accesses that hit watchpoints, attributing them to `pc`
+    ///
+    /// Returns [`Error::Watchpoint`] if execution should break
+    #[inline]
+    fn collect_watch_hits(&mut self, pc: u64) -> Result<(), Error> {
+        if !self.proc.memory().has_watchpoints() {
+            return Ok(());
+        }
+        collect_watch_hits(&self.cpu1.cpu0, self.proc, pc)
+    }
+
+    /// Start recording a structured trace on the current thread,
+    /// see [`trace`](crate::trace)
+    pub fn start_trace(&self, config: TraceConfig) {
+        let hook_provider = Arc::clone(self.proc.hook_provider());
+        let env = self.proc.env();
+        trace::start(
+            config,
+            self.proc.main_start(),
+            Some(Box::new(move |main_offset| {
+                hook_provider.hook_name(main_offset, env)
+            })),
+        );
+    }
+
+    /// Stop recording the trace started with [`start_trace`](Self::start_trace),
+    /// and return the recorded bytes
+    pub fn finish_trace(&self) -> Option<Vec<u8>> {
+        trace::finish()
     }
 
     pub fn reset_stack(&mut self) {
@@ -348,7 +394,18 @@ impl Cpu2<'_, '_> {
         &mut self,
         f: F,
     ) -> Result<T, CrashReport> {
-        match f(self) {
+        let pc = self.pc;
+        // accesses made outside of a scope (for example, reading the state
+        // for the inventory views) are not part of this execution
+        clear_watch_records();
+        // accesses made by native code outside of execution are
+        // attributed to the PC when the scope started
+        let result = f(self);
+        let watch_result = self.collect_watch_hits(pc);
+        // make sure nothing is left for the next scope if execution
+        // stopped early or the watchpoints were removed
+        clear_watch_records();
+        match result.and_then(|x| watch_result.map(|_| x)) {
             Ok(result) => Ok(result),
             Err(e) => Err(self.make_crash_report(e)),
         }
@@ -361,7 +418,8 @@ impl Cpu2<'_, '_> {
     pub fn make_crash_report(&self, error: Error) -> CrashReport {
         let main_start = self.proc.main_start();
         let cpu0 = self.cpu1.cpu0.clone();
-        CrashReport::new(Box::new(cpu0), main_start, error)
+        let symbols = symbol_map(self.proc.env().game_ver);
+        CrashReport::new(Box::new(cpu0), main_start, error, symbols)
     }
 
     /// Allocate memory object on the stack
@@ -1,6 +1,8 @@
+use std::sync::Arc;
+
 use derive_more::derive::Constructor;
 
-use crate::env::DataId;
+use crate::env::{DataId, SymbolMap};
 use crate::memory;
 use crate::processor::{Cpu0, reg};
 
@@ -30,6 +32,8 @@ pub enum Error {
     BadInstruction(u32),
     #[error("[check-stack-corruption] stack object at 0x{0:016x} with size 0x{1:x} is corrupted")]
     StackCorruption(u64, u32),
+    #[error("watchpoint hit at 0x{0:016x}")]
+    Watchpoint(u64),
 
     #[error("Unrecognized conditional code: {0}")]
     UnhandledConditionCode(String),
@@ -48,6 +52,8 @@ pub struct CrashReport {
     pub cpu: Box<Cpu0>,
     pub main_start: u64,
     pub error: Error,
+    /// Symbols for the game version, if loaded
+    pub symbols: Option<Arc<SymbolMap>>,
 }
 
 impl std::fmt::Display for CrashReport {
@@ -88,17 +94,24 @@ impl std::fmt::Debug for CrashReport {
 
         writeln!(f)?;
         writeln!(f, "Main Start: 0x{:016x}", self.main_start)?;
-        writeln!(f, "PC: {}", format_address(self.cpu.pc, self.main_start))?;
+        let symbols = self.symbols.as_deref();
+        writeln!(
+            f,
+            "PC: {}",
+            format_address_with_symbols(self.cpu.pc, self.main_start, symbols)
+        )?;
         writeln!(
             f,
             "LR: {}",
-            format_address(self.cpu.read::<u64>(reg!(lr)), self.main_start)
+            format_address_with_symbols(self.cpu.read::<u64>(reg!(lr)), self.main_start, symbols)
         )?;
         writeln!(f, "Stack Trace: (top is most recent)")?;
         writeln!(
             f,
             "{}",
-            self.cpu.stack_trace.format_with_main_start(self.main_start)
+            self.cpu
+                .stack_trace
+                .format_with_symbols(self.main_start, symbols)
         )?;
 
         Ok(())
@@ -112,3 +125,20 @@ pub fn format_address(addr: u64, main_start: u64) -> String {
         format!("0x{addr:016x}                ")
     }
 }
+
+/// Format the address like [`format_address`], with the function name
+/// and offset appended if the address is in a known function
+pub fn format_address_with_symbols(
+    addr: u64,
+    main_start: u64,
+    symbols: Option<&SymbolMap>,
+) -> String {
+    let formatted = format_address(addr, main_start);
+    let name = symbols
+        .zip(u32::try_from(addr.wrapping_sub(main_start)).ok())
+        .and_then(|(symbols, main_offset)| symbols.symbolize(main_offset));
+    match name {
+        Some(name) => format!("{formatted} <{name}>"),
+        None => formatted,
+    }
+}
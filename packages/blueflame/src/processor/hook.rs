@@ -12,6 +12,11 @@ pub trait HookProvider: Any + Send + Sync + UnwindSafe + RefUnwindSafe {
     /// size of the hook
     #[allow(clippy::type_complexity)]
     fn fetch(&self, main_offset: u32, env: Environment) -> Result<Option<Hook>, Error>;
+
+    /// Get the name of the hook at the main offset, for tracing and debugging
+    fn hook_name(&self, _main_offset: u32, _env: Environment) -> Option<&'static str> {
+        None
+    }
 }
 
 pub enum Hook {
@@ -50,4 +55,10 @@ impl HookProvider for HookChain {
             x => Ok(x),
         }
     }
+
+    fn hook_name(&self, main_offset: u32, env: Environment) -> Option<&'static str> {
+        self.outer
+            .hook_name(main_offset, env)
+            .or_else(|| self.inner.hook_name(main_offset, env))
+    }
 }
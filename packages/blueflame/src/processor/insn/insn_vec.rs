@@ -8,8 +8,9 @@ use crate::env::enabled;
 use crate::processor::{
     insn::instruction_parse::ExecutableInstruction,
     insn::{Core, instruction_parse, op},
-    {BLOCK_ITERATION_LIMIT, Cpu0, Error, Execute, Process},
+    {BLOCK_ITERATION_LIMIT, Cpu0, Error, Execute, Process, collect_watch_hits},
 };
+use crate::trace;
 
 #[derive(Constructor)]
 pub struct HookedInsnVec {
@@ -93,10 +94,15 @@ impl Execute for InsnVec {
         } else {
             usize::MAX
         };
+        let tracing = trace::is_active();
+        let watching = proc.memory().has_watchpoints();
         for (i, x) in self.insns.iter().skip(step as usize).enumerate() {
             if i >= limit {
                 return Err(Error::BlockIterationLimitReached);
             }
+            if tracing {
+                trace::set_pc(cpu.pc);
+            }
             let (opcode, legacy_insn) = match x {
                 Entry::Nop => {
                     cpu.inc_pc();
@@ -107,8 +113,12 @@ impl Execute for InsnVec {
                 Entry::LegacyParse(opcode, legacy_insn) => (opcode, legacy_insn.as_ref()),
             };
 
+            let pc = cpu.pc;
             match op::execute(cpu, proc, *opcode) {
                 op::ExecResult::Handled => {
+                    if watching {
+                        collect_watch_hits(cpu, proc, pc)?;
+                    }
                     cpu.inc_pc();
                     continue;
                 }
@@ -127,6 +137,9 @@ impl Execute for InsnVec {
                 }
                 Some(x) => {
                     x.exec_on(&mut Core { cpu, proc })?;
+                    if watching {
+                        collect_watch_hits(cpu, proc, pc)?;
+                    }
                     cpu.inc_pc();
                 }
             }
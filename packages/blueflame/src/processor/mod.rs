@@ -1,3 +1,5 @@
+mod core_dump;
+pub use core_dump::*;
 mod cpu;
 pub use cpu::*;
 mod error;
@@ -12,6 +14,8 @@ mod hook;
 pub use hook::*;
 mod stack_trace;
 pub use stack_trace::*;
+mod watch;
+pub use watch::*;
 
 pub mod insn;
 
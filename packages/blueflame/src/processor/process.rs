@@ -1,20 +1,18 @@
 use std::ops::ControlFlow;
 use std::sync::Arc;
 
-use derive_more::derive::Constructor;
-
 use crate::env::Environment;
 use crate::game::Proxies;
-use crate::memory::{Memory, ProxyGuardMut, ProxyList, ProxyObject, access};
+use crate::memory::{Memory, ProxyGuardMut, ProxyList, ProxyObject, Watchpoint, access};
 use crate::processor::insn::{HookedInsnVec, InsnVec};
-use crate::processor::{Error, Execute, Hook, HookProvider};
+use crate::processor::{Error, Execute, Hook, HookProvider, WatchHit};
 
 /// The Process is the container for everything the core tracks
 /// that is not in the Processor.
 ///
 /// Cloning the process will `fork` the process, and the memory will
 /// be shared (clone on write)
-#[derive(Clone, Constructor)]
+#[derive(Clone)]
 pub struct Process {
     /// Main memory of the game
     memory: Arc<Memory>,
@@ -22,6 +20,9 @@ pub struct Process {
     proxies: Arc<Proxies>,
     /// Hooks for this process
     hook_provider: Arc<dyn HookProvider>,
+    /// Accesses that hit the watchpoints in memory, since the last time
+    /// they are taken
+    watch_hits: Vec<WatchHit>,
 }
 static_assertions::assert_impl_all!(Process: Send, Sync);
 
@@ -32,6 +33,19 @@ impl std::fmt::Debug for Process {
 }
 
 impl Process {
+    pub fn new(
+        memory: Arc<Memory>,
+        proxies: Arc<Proxies>,
+        hook_provider: Arc<dyn HookProvider>,
+    ) -> Self {
+        Self {
+            memory,
+            proxies,
+            hook_provider,
+            watch_hits: Vec::new(),
+        }
+    }
+
     /// Get the environment configuration
     pub fn env(&self) -> Environment {
         self.memory.env()
@@ -64,12 +78,36 @@ impl Process {
         proxy_list.write(memory)
     }
 
+    /// Replace the watchpoints on the memory of the process
+    pub fn set_watchpoints(&mut self, watchpoints: Vec<Watchpoint>) {
+        self.memory_mut().set_watchpoints(watchpoints);
+    }
+
+    /// Get the accesses that hit watchpoints since the last [`take_watch_hits`](Self::take_watch_hits)
+    pub fn watch_hits(&self) -> &[WatchHit] {
+        &self.watch_hits
+    }
+
+    /// Take the accesses that hit watchpoints
+    pub fn take_watch_hits(&mut self) -> Vec<WatchHit> {
+        std::mem::take(&mut self.watch_hits)
+    }
+
+    pub(crate) fn push_watch_hit(&mut self, hit: WatchHit) {
+        self.watch_hits.push(hit);
+    }
+
     /// Get the physical starting address of the main module
     pub fn main_start(&self) -> u64 {
         self.memory.main_start()
     }
 
     /// Get the hook provider
+    pub fn hook_provider(&self) -> &Arc<dyn HookProvider> {
+        &self.hook_provider
+    }
+
+    /// Get the hook provider for mutation
     pub fn hook_provider_mut(&mut self) -> &mut Arc<dyn HookProvider> {
         &mut self.hook_provider
     }
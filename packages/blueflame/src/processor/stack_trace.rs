@@ -1,5 +1,7 @@
+use crate::env::SymbolMap;
 use crate::env::enabled;
-use crate::processor::{Error, RegName, format_address};
+use crate::processor::{Error, RegName, format_address_with_symbols};
+use crate::trace::{self, CallKind};
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct StackTrace {
@@ -13,6 +15,7 @@ impl StackTrace {
 
     /// Push a jump to target address from native implementation
     pub fn push_native(&mut self, target: u64) {
+        trace::on_call(CallKind::Native, None, target);
         self.frames.push(Frame {
             jump_target: target,
             jump_type: FrameType::Native,
@@ -21,6 +24,7 @@ impl StackTrace {
 
     /// Push a jump to target address from BL instruction
     pub fn push_bl(&mut self, target: u64, from: u64) {
+        trace::on_call(CallKind::Bl, Some(from), target);
         self.frames.push(Frame {
             jump_target: target,
             jump_type: FrameType::Bl(from),
@@ -29,6 +33,7 @@ impl StackTrace {
 
     /// Push a jump to target address from BLR instruction
     pub fn push_blr(&mut self, target: u64, reg: RegName, from: u64) {
+        trace::on_call(CallKind::Blr, Some(from), target);
         self.frames.push(Frame {
             jump_target: target,
             jump_type: FrameType::Blr(from, reg),
@@ -37,6 +42,7 @@ impl StackTrace {
 
     /// Pop the stack frame
     pub fn pop_checked(&mut self, lr: u64) -> Result<(), Error> {
+        trace::on_return(lr);
         match self.frames.pop() {
             Some(frame) => {
                 if enabled!("check-return-address") {
@@ -61,9 +67,14 @@ impl StackTrace {
     }
 
     pub fn format_with_main_start(&self, main_start: u64) -> String {
+        self.format_with_symbols(main_start, None)
+    }
+
+    /// Format the stack trace, with function names if symbols are provided
+    pub fn format_with_symbols(&self, main_start: u64, symbols: Option<&SymbolMap>) -> String {
         let mut result = String::new();
         for frame in self.frames.iter().rev() {
-            result.push_str(&frame.format_with_main_start(main_start));
+            result.push_str(&frame.format_with_symbols(main_start, symbols));
             result.push('\n');
         }
         result
@@ -88,27 +99,33 @@ pub enum FrameType {
 
 impl Frame {
     pub fn format_with_main_start(&self, main_start: u64) -> String {
+        self.format_with_symbols(main_start, None)
+    }
+
+    /// Format the frame, with function names if symbols are provided
+    pub fn format_with_symbols(&self, main_start: u64, symbols: Option<&SymbolMap>) -> String {
+        let format_address = |addr| format_address_with_symbols(addr, main_start, symbols);
         match self.jump_type {
             FrameType::Bl(from) => {
                 format!(
                     "  {} BL      -> {}",
-                    format_address(from, main_start),
-                    format_address(self.jump_target, main_start)
+                    format_address(from),
+                    format_address(self.jump_target)
                 )
             }
             FrameType::Blr(from, reg_name) => {
                 let reg = format!("{:4}", reg_name.to_string());
                 format!(
                     "  {} BLR{} -> {}",
-                    format_address(from, main_start),
+                    format_address(from),
                     reg,
-                    format_address(self.jump_target, main_start)
+                    format_address(self.jump_target)
                 )
             }
             FrameType::Native => {
                 format!(
                     "                                   native jump -> {}",
-                    format_address(self.jump_target, main_start)
+                    format_address(self.jump_target)
                 )
             }
         }
@@ -0,0 +1,75 @@
+use crate::env::SymbolMap;
+use crate::memory::{WatchAccess, WatchAccessRecord, take_watch_records};
+use crate::processor::{Cpu0, Error, Process, StackTrace, format_address_with_symbols};
+
+/// A memory access that hit a watchpoint
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchHit {
+    /// Index of the watchpoint in the memory's watchpoints
+    pub watchpoint: usize,
+    pub access: WatchAccess,
+    /// Physical address that was accessed
+    pub addr: u64,
+    /// Size of the access in bytes
+    pub size: u8,
+    /// Value in memory before the access
+    pub old_value: u64,
+    /// Value in memory after the access (same as the old value for reads)
+    pub new_value: u64,
+    /// PC of the instruction (or the start of the hook) that made the access
+    pub pc: u64,
+    /// Stack trace when the access was made
+    pub stack_trace: StackTrace,
+}
+
+impl WatchHit {
+    fn new(record: WatchAccessRecord, pc: u64, stack_trace: &StackTrace) -> Self {
+        Self {
+            watchpoint: record.watchpoint,
+            access: record.access,
+            addr: record.addr,
+            size: record.size,
+            old_value: record.old_value,
+            new_value: record.new_value,
+            pc,
+            stack_trace: stack_trace.clone(),
+        }
+    }
+
+    /// Format the hit with the stack trace, with function names if symbols are provided
+    pub fn format_with_symbols(&self, main_start: u64, symbols: Option<&SymbolMap>) -> String {
+        let mut result = format!(
+            "{} of {} bytes at 0x{:016x}: 0x{:x} -> 0x{:x}\nPC: {}\n",
+            self.access,
+            self.size,
+            self.addr,
+            self.old_value,
+            self.new_value,
+            format_address_with_symbols(self.pc, main_start, symbols)
+        );
+        result.push_str(&self.stack_trace.format_with_symbols(main_start, symbols));
+        result
+    }
+}
+
+/// Collect the accesses that hit watchpoints since the last collection,
+/// attributing them to `pc` and the current stack trace of the CPU.
+///
+/// Returns [`Error::Watchpoint`] if any of the watchpoints hit should break execution
+pub(crate) fn collect_watch_hits(cpu: &Cpu0, proc: &mut Process, pc: u64) -> Result<(), Error> {
+    let records = take_watch_records();
+    if records.is_empty() {
+        return Ok(());
+    }
+    let mut break_addr = None;
+    for record in records {
+        if record.break_on_hit && break_addr.is_none() {
+            break_addr = Some(record.addr);
+        }
+        proc.push_watch_hit(WatchHit::new(record, pc, &cpu.stack_trace));
+    }
+    match break_addr {
+        Some(addr) => Err(Error::Watchpoint(addr)),
+        None => Ok(()),
+    }
+}
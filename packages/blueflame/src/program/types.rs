@@ -1,7 +1,7 @@
 // use deku::{DekuRead, DekuWrite};
 use rkyv::{Archive, Deserialize, Serialize};
 
-use crate::env::{DataId, GameVer};
+use crate::env::{DataId, GameVer, SymbolMap, SymbolMapError};
 
 /// Image of a program at runtime
 #[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
@@ -74,3 +74,13 @@ impl Data {
         }
     }
 }
+
+impl ArchivedProgram {
+    /// Parse the symbol map packed in the image, `None` if the image
+    /// does not have one
+    pub fn symbol_map(&self) -> Option<Result<SymbolMap, SymbolMapError>> {
+        let data = self.data.iter().find(|d| d.id == DataId::SymbolMap)?;
+        let text = String::from_utf8_lossy(data.bytes.as_slice());
+        Some(SymbolMap::parse(&text))
+    }
+}
@@ -0,0 +1,468 @@
+use std::io::{Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::env::SymbolMap;
+use crate::trace::Error;
+
+/// Version of the trace format, bumped when events change
+pub const TRACE_VERSION: u32 = 1;
+
+/// Magic bytes at the start of a binary trace
+pub const TRACE_MAGIC: &[u8; 4] = b"BFTR";
+
+/// One event in the execution trace
+///
+/// Code addresses (call targets, return addresses, PC) are stored
+/// relative to the start of the main module, so they can be looked up
+/// directly in a disassembler. Data addresses are absolute.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum TraceEvent {
+    /// First event of every trace
+    Header { version: u32, main_start: u64 },
+    /// A function call
+    Call {
+        kind: CallKind,
+        /// Address of the branch instruction, `None` for native jumps
+        from: Option<i64>,
+        target: i64,
+        /// Name of the game function at the target, if it is replaced by a hook
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        hook: Option<String>,
+    },
+    /// A function returned to the address
+    Return { to: i64 },
+    /// A write to memory in one of the traced ranges
+    MemWrite {
+        /// PC of the instruction (or hook) doing the write
+        pc: i64,
+        addr: u64,
+        /// Size of the write in bytes
+        size: u8,
+        value: u64,
+    },
+    /// An access to a GDT flag through the TriggerParam hooks
+    Gdt {
+        op: GdtOp,
+        /// Index of the flag in its flag list, if accessed by index
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        index: Option<i32>,
+        /// Name of the flag, if accessed by name
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        array_index: Option<i32>,
+        /// Hash of the flag, `None` if the flag was not found
+        hash: Option<i32>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CallKind {
+    /// Jump from native code (i.e. the VM or a hook)
+    Native,
+    Bl,
+    Blr,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GdtOp {
+    Get,
+    Set,
+    Reset,
+}
+
+const TAG_HEADER: u8 = 0;
+const TAG_CALL: u8 = 1;
+const TAG_RETURN: u8 = 2;
+const TAG_MEM_WRITE: u8 = 3;
+const TAG_GDT: u8 = 4;
+
+impl TraceEvent {
+    /// Get the `type` of the event as used in JSONL
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Header { .. } => "header",
+            Self::Call { .. } => "call",
+            Self::Return { .. } => "return",
+            Self::MemWrite { .. } => "mem-write",
+            Self::Gdt { .. } => "gdt",
+        }
+    }
+
+    /// Encode the event in the compact binary format
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Self::Header {
+                version,
+                main_start,
+            } => {
+                out.push(TAG_HEADER);
+                out.extend_from_slice(&version.to_le_bytes());
+                out.extend_from_slice(&main_start.to_le_bytes());
+            }
+            Self::Call {
+                kind,
+                from,
+                target,
+                hook,
+            } => {
+                out.push(TAG_CALL);
+                out.push(match kind {
+                    CallKind::Native => 0,
+                    CallKind::Bl => 1,
+                    CallKind::Blr => 2,
+                });
+                encode_opt_i64(out, *from);
+                out.extend_from_slice(&target.to_le_bytes());
+                encode_opt_str(out, hook.as_deref());
+            }
+            Self::Return { to } => {
+                out.push(TAG_RETURN);
+                out.extend_from_slice(&to.to_le_bytes());
+            }
+            Self::MemWrite {
+                pc,
+                addr,
+                size,
+                value,
+            } => {
+                out.push(TAG_MEM_WRITE);
+                out.extend_from_slice(&pc.to_le_bytes());
+                out.extend_from_slice(&addr.to_le_bytes());
+                out.push(*size);
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+            Self::Gdt {
+                op,
+                index,
+                name,
+                array_index,
+                hash,
+            } => {
+                out.push(TAG_GDT);
+                out.push(match op {
+                    GdtOp::Get => 0,
+                    GdtOp::Set => 1,
+                    GdtOp::Reset => 2,
+                });
+                encode_opt_i32(out, *index);
+                encode_opt_str(out, name.as_deref());
+                encode_opt_i32(out, *array_index);
+                encode_opt_i32(out, *hash);
+            }
+        }
+    }
+
+    /// Decode the next event in the compact binary format.
+    ///
+    /// Returns `None` if the reader is at the end
+    pub fn decode<R: Read>(r: &mut R) -> Result<Option<Self>, Error> {
+        let mut tag = [0u8; 1];
+        if r.read(&mut tag)? == 0 {
+            return Ok(None);
+        }
+        let event = match tag[0] {
+            TAG_HEADER => Self::Header {
+                version: u32::from_le_bytes(read_array(r)?),
+                main_start: u64::from_le_bytes(read_array(r)?),
+            },
+            TAG_CALL => {
+                let kind = match read_u8(r)? {
+                    0 => CallKind::Native,
+                    1 => CallKind::Bl,
+                    2 => CallKind::Blr,
+                    x => return Err(Error::InvalidData(format!("invalid call kind {x}"))),
+                };
+                Self::Call {
+                    kind,
+                    from: decode_opt_i64(r)?,
+                    target: i64::from_le_bytes(read_array(r)?),
+                    hook: decode_opt_str(r)?,
+                }
+            }
+            TAG_RETURN => Self::Return {
+                to: i64::from_le_bytes(read_array(r)?),
+            },
+            TAG_MEM_WRITE => Self::MemWrite {
+                pc: i64::from_le_bytes(read_array(r)?),
+                addr: u64::from_le_bytes(read_array(r)?),
+                size: read_u8(r)?,
+                value: u64::from_le_bytes(read_array(r)?),
+            },
+            TAG_GDT => {
+                let op = match read_u8(r)? {
+                    0 => GdtOp::Get,
+                    1 => GdtOp::Set,
+                    2 => GdtOp::Reset,
+                    x => return Err(Error::InvalidData(format!("invalid gdt op {x}"))),
+                };
+                Self::Gdt {
+                    op,
+                    index: decode_opt_i32(r)?,
+                    name: decode_opt_str(r)?,
+                    array_index: decode_opt_i32(r)?,
+                    hash: decode_opt_i32(r)?,
+                }
+            }
+            x => return Err(Error::InvalidData(format!("invalid event tag {x}"))),
+        };
+        Ok(Some(event))
+    }
+
+    /// Write the event as one line of JSON
+    pub fn write_jsonl<W: Write>(&self, w: &mut W) -> Result<(), Error> {
+        serde_json::to_writer(&mut *w, self)?;
+        w.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+/// Format a main-relative address for display
+pub fn format_main_offset(off: i64) -> String {
+    if off < 0 {
+        format!("main-0x{:08x}", off.unsigned_abs())
+    } else {
+        format!("main+0x{off:08x}")
+    }
+}
+
+/// Format a main-relative address like [`format_main_offset`], with the function
+/// name and offset appended if the address is in a known function
+pub fn format_main_offset_with_symbols(off: i64, symbols: Option<&SymbolMap>) -> String {
+    let formatted = format_main_offset(off);
+    let name = symbols
+        .zip(u32::try_from(off).ok())
+        .and_then(|(symbols, main_offset)| symbols.symbolize(main_offset));
+    match name {
+        Some(name) => format!("{formatted} <{name}>"),
+        None => formatted,
+    }
+}
+
+impl TraceEvent {
+    /// The main-relative code address the event is about, used for symbolizing.
+    /// This is the target for calls, the return address for returns,
+    /// and the PC for memory writes
+    pub fn code_offset(&self) -> Option<i64> {
+        match self {
+            Self::Call { target, .. } => Some(*target),
+            Self::Return { to } => Some(*to),
+            Self::MemWrite { pc, .. } => Some(*pc),
+            Self::Header { .. } | Self::Gdt { .. } => None,
+        }
+    }
+}
+
+impl std::fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Header {
+                version,
+                main_start,
+            } => write!(f, "trace v{version}, main_start=0x{main_start:016x}"),
+            Self::Call {
+                kind,
+                from,
+                target,
+                hook,
+            } => {
+                let kind = match kind {
+                    CallKind::Native => "native",
+                    CallKind::Bl => "bl",
+                    CallKind::Blr => "blr",
+                };
+                match from {
+                    Some(from) => write!(
+                        f,
+                        "{} {kind:<6} -> {}",
+                        format_main_offset(*from),
+                        format_main_offset(*target)
+                    )?,
+                    None => write!(f, "{:<18} {kind:<6} -> {}", "", format_main_offset(*target))?,
+                }
+                if let Some(hook) = hook {
+                    write!(f, " [{hook}]")?;
+                }
+                Ok(())
+            }
+            Self::Return { to } => write!(f, "{:<18} ret    -> {}", "", format_main_offset(*to)),
+            Self::MemWrite {
+                pc,
+                addr,
+                size,
+                value,
+            } => write!(
+                f,
+                "{} st{:<4} 0x{addr:016x} <= 0x{value:x}",
+                format_main_offset(*pc),
+                *size as u32 * 8
+            ),
+            Self::Gdt {
+                op,
+                index,
+                name,
+                array_index,
+                hash,
+            } => {
+                let op = match op {
+                    GdtOp::Get => "get",
+                    GdtOp::Set => "set",
+                    GdtOp::Reset => "reset",
+                };
+                write!(f, "gdt {op:<5}")?;
+                if let Some(name) = name {
+                    write!(f, " {name}")?;
+                }
+                if let Some(index) = index {
+                    write!(f, " idx={index}")?;
+                }
+                if let Some(array_index) = array_index {
+                    write!(f, " [{array_index}]")?;
+                }
+                match hash {
+                    Some(hash) => write!(f, " hash=0x{:08x}", *hash as u32),
+                    None => write!(f, " (not found)"),
+                }
+            }
+        }
+    }
+}
+
+fn encode_opt_i64(out: &mut Vec<u8>, v: Option<i64>) {
+    match v {
+        None => out.push(0),
+        Some(v) => {
+            out.push(1);
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+}
+
+fn encode_opt_i32(out: &mut Vec<u8>, v: Option<i32>) {
+    match v {
+        None => out.push(0),
+        Some(v) => {
+            out.push(1);
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+}
+
+fn encode_opt_str(out: &mut Vec<u8>, v: Option<&str>) {
+    match v {
+        None => out.push(0),
+        Some(v) => {
+            out.push(1);
+            let bytes = &v.as_bytes()[..v.len().min(u16::MAX as usize)];
+            out.extend_from_slice(&(bytes.len() as u16).to_le_bytes());
+            out.extend_from_slice(bytes);
+        }
+    }
+}
+
+fn read_array<R: Read, const N: usize>(r: &mut R) -> Result<[u8; N], Error> {
+    let mut buf = [0u8; N];
+    r.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn read_u8<R: Read>(r: &mut R) -> Result<u8, Error> {
+    Ok(read_array::<R, 1>(r)?[0])
+}
+
+fn decode_opt_i64<R: Read>(r: &mut R) -> Result<Option<i64>, Error> {
+    match read_u8(r)? {
+        0 => Ok(None),
+        _ => Ok(Some(i64::from_le_bytes(read_array(r)?))),
+    }
+}
+
+fn decode_opt_i32<R: Read>(r: &mut R) -> Result<Option<i32>, Error> {
+    match read_u8(r)? {
+        0 => Ok(None),
+        _ => Ok(Some(i32::from_le_bytes(read_array(r)?))),
+    }
+}
+
+fn decode_opt_str<R: Read>(r: &mut R) -> Result<Option<String>, Error> {
+    match read_u8(r)? {
+        0 => Ok(None),
+        _ => {
+            let len = u16::from_le_bytes(read_array(r)?) as usize;
+            let mut buf = vec![0u8; len];
+            r.read_exact(&mut buf)?;
+            Ok(Some(String::from_utf8_lossy(&buf).into_owned()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_roundtrip() {
+        let events = vec![
+            TraceEvent::Header {
+                version: TRACE_VERSION,
+                main_start: 0x1234_5000,
+            },
+            TraceEvent::Call {
+                kind: CallKind::Bl,
+                from: Some(0x96efb8),
+                target: 0x96f000,
+                hook: None,
+            },
+            TraceEvent::Call {
+                kind: CallKind::Native,
+                from: None,
+                target: 0x18001d0,
+                hook: Some("memcpy".to_string()),
+            },
+            TraceEvent::MemWrite {
+                pc: 0x18001d0,
+                addr: 0x3800_0010,
+                size: 4,
+                value: 0xdead,
+            },
+            TraceEvent::Gdt {
+                op: GdtOp::Set,
+                index: None,
+                name: Some("CurrentRupee".to_string()),
+                array_index: None,
+                hash: Some(-1),
+            },
+            TraceEvent::Return { to: 0x96efbc },
+        ];
+        let mut bytes = vec![];
+        for event in &events {
+            event.encode(&mut bytes);
+        }
+        let mut reader = bytes.as_slice();
+        let mut decoded = vec![];
+        while let Some(event) = TraceEvent::decode(&mut reader).unwrap() {
+            decoded.push(event);
+        }
+        assert_eq!(events, decoded);
+    }
+
+    #[test]
+    fn test_jsonl() {
+        let event = TraceEvent::Call {
+            kind: CallKind::Blr,
+            from: Some(0x100),
+            target: -0x10,
+            hook: None,
+        };
+        let mut out = vec![];
+        event.write_jsonl(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"type\":\"call\",\"kind\":\"blr\",\"from\":256,\"target\":-16}\n"
+        );
+    }
+}
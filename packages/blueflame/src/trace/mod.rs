@@ -0,0 +1,30 @@
+//! Structured execution trace
+//!
+//! Unlike the `trace-*` cargo features, which emit `log::trace!` lines,
+//! the tracer is selected at runtime and records events into a compact
+//! binary or JSONL trace that can be read back with [`TraceReader`].
+//!
+//! The tracer is per-thread, same as the features. Use
+//! [`Cpu2::start_trace`](crate::processor::Cpu2::start_trace)
+//! to start tracing on the thread that executes the processor.
+mod event;
+pub use event::*;
+mod reader;
+pub use reader::*;
+mod recorder;
+pub use recorder::*;
+
+/// Errors when reading or writing a trace
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("invalid trace data: {0}")]
+    InvalidData(String),
+    #[error("trace does not start with a header")]
+    MissingHeader,
+    #[error("unsupported trace version: {0}")]
+    UnsupportedVersion(u32),
+}
@@ -0,0 +1,169 @@
+use std::io::BufRead;
+use std::ops::Range;
+
+use crate::trace::{Error, TRACE_MAGIC, TraceEvent, TraceFormat};
+
+/// Reader for traces in either format
+pub struct TraceReader<R> {
+    inner: R,
+    format: TraceFormat,
+    main_start: u64,
+    line: String,
+}
+
+impl<R: BufRead> TraceReader<R> {
+    /// Create a reader, detecting the format and reading the header
+    pub fn new(mut inner: R) -> Result<Self, Error> {
+        let format = if inner.fill_buf()?.starts_with(TRACE_MAGIC) {
+            inner.consume(TRACE_MAGIC.len());
+            TraceFormat::Binary
+        } else {
+            TraceFormat::Jsonl
+        };
+        let mut reader = Self {
+            inner,
+            format,
+            main_start: 0,
+            line: String::new(),
+        };
+        match reader.next_event()? {
+            Some(TraceEvent::Header {
+                version,
+                main_start,
+            }) => {
+                if version != crate::trace::TRACE_VERSION {
+                    return Err(Error::UnsupportedVersion(version));
+                }
+                reader.main_start = main_start;
+            }
+            _ => return Err(Error::MissingHeader),
+        }
+        Ok(reader)
+    }
+
+    /// Format of the trace being read
+    pub fn format(&self) -> TraceFormat {
+        self.format
+    }
+
+    /// Physical address of the main module when the trace was recorded
+    pub fn main_start(&self) -> u64 {
+        self.main_start
+    }
+
+    /// Read the next event, `None` if at the end of the trace
+    pub fn next_event(&mut self) -> Result<Option<TraceEvent>, Error> {
+        match self.format {
+            TraceFormat::Binary => TraceEvent::decode(&mut self.inner),
+            TraceFormat::Jsonl => loop {
+                self.line.clear();
+                if self.inner.read_line(&mut self.line)? == 0 {
+                    return Ok(None);
+                }
+                let line = self.line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                return Ok(Some(serde_json::from_str(line)?));
+            },
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for TraceReader<R> {
+    type Item = Result<TraceEvent, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_event().transpose()
+    }
+}
+
+/// A frame in the call stack reconstructed from the trace
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceFrame {
+    /// Main-relative address of the function called
+    pub target: i64,
+    pub hook: Option<String>,
+}
+
+/// Filter for events in a trace
+///
+/// The filter also keeps track of the call stack, so it needs to
+/// see every event in order, even the ones that are filtered out.
+#[derive(Debug, Clone, Default)]
+pub struct TraceFilter {
+    /// Only keep events with these types (see [`TraceEvent::type_name`]).
+    /// Empty means all types
+    pub types: Vec<String>,
+    /// Only keep memory writes that overlap this address range
+    pub addr: Option<Range<u64>>,
+    /// Only keep GDT accesses to flags with this name or hash
+    pub flag: Option<String>,
+    /// Only keep events that happen inside a call to the function
+    /// at this main offset (including nested calls)
+    pub within: Option<i64>,
+
+    stack: Vec<TraceFrame>,
+}
+
+impl TraceFilter {
+    /// Feed the next event to the filter, returns if the event should be kept
+    pub fn accept(&mut self, event: &TraceEvent) -> bool {
+        // update the stack first, so calls are considered inside the function
+        // they call, and returns are considered inside the function they return from
+        let popped = match event {
+            TraceEvent::Call { target, hook, .. } => {
+                self.stack.push(TraceFrame {
+                    target: *target,
+                    hook: hook.clone(),
+                });
+                None
+            }
+            TraceEvent::Return { .. } => self.stack.pop(),
+            _ => None,
+        };
+        let in_function = match self.within {
+            None => true,
+            Some(within) => {
+                popped.as_ref().is_some_and(|f| f.target == within)
+                    || self.stack.iter().any(|f| f.target == within)
+            }
+        };
+        if !in_function {
+            return false;
+        }
+        if !self.types.is_empty() && !self.types.iter().any(|t| t == event.type_name()) {
+            return false;
+        }
+        match event {
+            TraceEvent::MemWrite { addr, size, .. } => match &self.addr {
+                None => true,
+                Some(range) => range.start < addr + *size as u64 && *addr < range.end,
+            },
+            TraceEvent::Gdt { name, hash, .. } => match &self.flag {
+                None => true,
+                Some(flag) => {
+                    name.as_deref() == Some(flag.as_str())
+                        || hash.is_some_and(|h| {
+                            flag_hash_matches(flag, h)
+                                || crc32fast::hash(flag.as_bytes()) as i32 == h
+                        })
+                }
+            },
+            _ => true,
+        }
+    }
+
+    /// The call stack at the last event passed to [`accept`](Self::accept),
+    /// innermost call last
+    pub fn stack(&self) -> &[TraceFrame] {
+        &self.stack
+    }
+}
+
+/// Check if the flag filter is a hash in hex (i.e. `0x1234abcd`) that matches
+fn flag_hash_matches(flag: &str, hash: i32) -> bool {
+    flag.strip_prefix("0x")
+        .and_then(|x| u32::from_str_radix(x, 16).ok())
+        .is_some_and(|x| x as i32 == hash)
+}
@@ -0,0 +1,190 @@
+use std::cell::{Cell, RefCell};
+use std::ops::Range;
+
+use crate::trace::{CallKind, GdtOp, TRACE_MAGIC, TRACE_VERSION, TraceEvent};
+
+/// Output format of the trace
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TraceFormat {
+    /// Compact binary format, prefixed with [`TRACE_MAGIC`]
+    #[default]
+    Binary,
+    /// One JSON object per line
+    Jsonl,
+}
+
+/// What to record in the trace
+#[derive(Debug, Clone, Default)]
+pub struct TraceConfig {
+    pub format: TraceFormat,
+    /// Record function calls and returns
+    pub calls: bool,
+    /// Record GDT flag accesses from the TriggerParam hooks
+    pub gdt: bool,
+    /// Record memory writes that overlap any of these (absolute) address ranges
+    pub mem_writes: Vec<Range<u64>>,
+}
+
+/// Function to look up the name of the hook at a main offset
+pub type HookNameFn = Box<dyn Fn(u32) -> Option<&'static str>>;
+
+struct Recorder {
+    config: TraceConfig,
+    main_start: u64,
+    /// PC of the instruction currently executing
+    pc: u64,
+    hook_names: Option<HookNameFn>,
+    out: Vec<u8>,
+}
+
+thread_local! {
+    // checked before every trace point, so it's separate from the recorder
+    static ACTIVE: Cell<bool> = const { Cell::new(false) };
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// Start recording a trace on the current thread
+///
+/// Any trace that is currently being recorded on this thread is discarded.
+/// The trace is recorded in memory and returned by [`finish`]
+pub fn start(config: TraceConfig, main_start: u64, hook_names: Option<HookNameFn>) {
+    let mut recorder = Recorder {
+        config,
+        main_start,
+        pc: 0,
+        hook_names,
+        out: Vec::new(),
+    };
+    if recorder.config.format == TraceFormat::Binary {
+        recorder.out.extend_from_slice(TRACE_MAGIC);
+    }
+    recorder.emit(TraceEvent::Header {
+        version: TRACE_VERSION,
+        main_start,
+    });
+    RECORDER.set(Some(recorder));
+    ACTIVE.set(true);
+}
+
+/// Stop recording the trace on the current thread, and return the recorded bytes
+///
+/// Returns `None` if no trace was being recorded
+pub fn finish() -> Option<Vec<u8>> {
+    ACTIVE.set(false);
+    RECORDER.take().map(|r| r.out)
+}
+
+/// Check if a trace is being recorded on the current thread
+#[inline(always)]
+pub fn is_active() -> bool {
+    ACTIVE.get()
+}
+
+#[inline(always)]
+fn with_recorder<F: FnOnce(&mut Recorder)>(f: F) {
+    if !is_active() {
+        return;
+    }
+    RECORDER.with_borrow_mut(|r| {
+        if let Some(r) = r.as_mut() {
+            f(r)
+        }
+    })
+}
+
+impl Recorder {
+    fn emit(&mut self, event: TraceEvent) {
+        match self.config.format {
+            TraceFormat::Binary => event.encode(&mut self.out),
+            TraceFormat::Jsonl => {
+                if let Err(e) = event.write_jsonl(&mut self.out) {
+                    log::error!("failed to write trace event: {e}");
+                }
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn rel(&self, addr: u64) -> i64 {
+        addr.wrapping_sub(self.main_start) as i64
+    }
+}
+
+/// Update the PC of the instruction being executed, for attributing memory writes
+#[inline(always)]
+pub(crate) fn set_pc(pc: u64) {
+    with_recorder(|r| r.pc = pc)
+}
+
+pub(crate) fn on_call(kind: CallKind, from: Option<u64>, target: u64) {
+    with_recorder(|r| {
+        if !r.config.calls {
+            return;
+        }
+        let target_rel = r.rel(target);
+        let hook = match (&r.hook_names, u32::try_from(target_rel)) {
+            (Some(f), Ok(main_offset)) => f(main_offset).map(str::to_string),
+            _ => None,
+        };
+        let event = TraceEvent::Call {
+            kind,
+            from: from.map(|x| r.rel(x)),
+            target: target_rel,
+            hook,
+        };
+        r.emit(event);
+    })
+}
+
+pub(crate) fn on_return(to: u64) {
+    with_recorder(|r| {
+        if !r.config.calls {
+            return;
+        }
+        let event = TraceEvent::Return { to: r.rel(to) };
+        r.emit(event);
+    })
+}
+
+pub(crate) fn on_mem_write(addr: u64, size: u8, value: u64) {
+    with_recorder(|r| {
+        let end = addr + size as u64;
+        if !r
+            .config
+            .mem_writes
+            .iter()
+            .any(|range| range.start < end && addr < range.end)
+        {
+            return;
+        }
+        let event = TraceEvent::MemWrite {
+            pc: r.rel(r.pc),
+            addr,
+            size,
+            value,
+        };
+        r.emit(event);
+    })
+}
+
+pub(crate) fn on_gdt(
+    op: GdtOp,
+    index: Option<i32>,
+    name: Option<&str>,
+    array_index: Option<i32>,
+    hash: Option<i32>,
+) {
+    with_recorder(|r| {
+        if !r.config.gdt {
+            return;
+        }
+        let event = TraceEvent::Gdt {
+            op,
+            index,
+            name: name.map(str::to_string),
+            array_index,
+            hash,
+        };
+        r.emit(event);
+    })
+}
@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::{Args, ValueEnum};
+use skybook_parser::search::LocalizedItemResolver;
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// Path to the script
+    script: PathBuf,
+
+    /// Format of the output
+    #[clap(short, long, default_value = "markdown")]
+    format: Format,
+
+    /// Path to the output file. Print to stdout if not specified
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Markdown,
+    Html,
+}
+
+pub fn run(args: ExportArgs, resolver: Option<&LocalizedItemResolver>) -> anyhow::Result<()> {
+    let (script, output) = crate::parse_script_file(&args.script, resolver)?;
+    let markdown = skybook_parser::notes::export_markdown(&script, &output);
+    let content = match args.format {
+        Format::Markdown => markdown,
+        Format::Html => {
+            let title = args
+                .script
+                .file_stem()
+                .map(|x| x.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            to_html(&title, &markdown)
+        }
+    };
+    match args.output {
+        Some(path) => std::fs::write(&path, content)
+            .with_context(|| format!("failed to write {}", path.display()))?,
+        None => print!("{content}"),
+    }
+    Ok(())
+}
+
+fn to_html(title: &str, markdown: &str) -> String {
+    let mut body = String::new();
+    pulldown_cmark::html::push_html(&mut body, pulldown_cmark::Parser::new(markdown));
+    let title_escaped = title
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title_escaped}</title>\n</head>\n<body>\n{body}</body>\n</html>\n"
+    )
+}
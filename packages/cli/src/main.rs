@@ -0,0 +1,91 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use skybook_parser::ParseOutput;
+use skybook_parser::include::FsScriptLoader;
+use skybook_parser::search::LocalizedItemResolver;
+
+mod export;
+mod run;
+
+/// Command line tools for scripts
+#[derive(Parser)]
+struct Cli {
+    /// Path to the localized item search data, used to resolve quoted items
+    /// like `"Royal Claymore"`. Defaults to the data generated by the localization package
+    #[clap(long, global = true)]
+    item_search: Option<PathBuf>,
+
+    #[clap(subcommand)]
+    subcommand: Sub,
+}
+#[derive(Subcommand)]
+enum Sub {
+    /// Export a script with notes to a markdown or HTML document
+    Export(export::ExportArgs),
+    /// Run a script and print the state at the end or at a label
+    Run(run::RunArgs),
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Cli::parse();
+    let resolver = load_item_resolver(args.item_search.as_deref())?;
+    let resolver = resolver.as_ref();
+    match args.subcommand {
+        Sub::Export(args) => export::run(args, resolver),
+        Sub::Run(args) => run::run(args, resolver),
+    }
+}
+
+/// Path to the item search data generated by the localization package
+const DEFAULT_ITEM_SEARCH_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../localization/src/generated/item-search.json"
+);
+
+/// Load the resolver for quoted items
+///
+/// If the path is not specified and the generated data doesn't exist,
+/// quoted items are not resolved
+fn load_item_resolver(path: Option<&Path>) -> anyhow::Result<Option<LocalizedItemResolver>> {
+    if let Some(path) = path {
+        let resolver = LocalizedItemResolver::load(path)
+            .with_context(|| format!("failed to load item search data {}", path.display()))?;
+        return Ok(Some(resolver));
+    }
+    match LocalizedItemResolver::load(DEFAULT_ITEM_SEARCH_PATH) {
+        Ok(resolver) => Ok(Some(resolver)),
+        Err(e) => {
+            eprintln!("warning: quoted items will not be resolved: {e}");
+            Ok(None)
+        }
+    }
+}
+
+/// Read and parse the script at the path
+///
+/// Included scripts are loaded relative to the directory of the script.
+/// Quoted items are not resolved if there is no resolver
+fn parse_script_file(
+    path: &Path,
+    resolver: Option<&LocalizedItemResolver>,
+) -> anyhow::Result<(String, ParseOutput)> {
+    let script = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read script {}", path.display()))?;
+    let base = path.parent().unwrap_or(Path::new("."));
+    let loader = FsScriptLoader::new(base);
+    let resolver =
+        |word: &str| std::future::ready(resolver.and_then(|resolver| resolver.search(word)));
+    let output = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .context("failed to create tokio runtime")?
+        .block_on(skybook_parser::parse_with_loader(
+            &resolver, &loader, &script,
+        ));
+    let error_count = output.errors.iter().filter(|x| !x.is_warning).count();
+    if error_count > 0 {
+        eprintln!("warning: the script has {error_count} error(s)");
+    }
+    Ok((script, output))
+}
@@ -0,0 +1,129 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Context, bail};
+use clap::Args;
+use skybook_parser::search::LocalizedItemResolver;
+use skybook_runtime::MaybeAborted;
+use skybook_runtime::exec::Spawner;
+use skybook_runtime::sim;
+
+#[derive(Args)]
+pub struct RunArgs {
+    /// Path to the script
+    script: PathBuf,
+
+    /// Path to the BlueFlame program image (.bfi)
+    #[clap(short, long)]
+    image: PathBuf,
+
+    /// Print the state at the label (defined with `label NAME` in the script),
+    /// instead of the state at the end of the script
+    #[clap(long)]
+    at: Option<String>,
+
+    /// Record a structured execution trace for each executed step into the directory.
+    /// The traces can be read with `blueflame-trace`
+    #[clap(long)]
+    trace: Option<PathBuf>,
+
+    /// Record function calls and returns in the trace
+    #[clap(long, requires = "trace")]
+    trace_calls: bool,
+
+    /// Record GDT flag accesses in the trace
+    #[clap(long, requires = "trace")]
+    trace_gdt: bool,
+
+    /// Record memory writes to the range in the trace, like 0xSTART-0xEND
+    /// (END is exclusive). Can be specified multiple times
+    #[clap(long, requires = "trace")]
+    trace_mem_write: Vec<String>,
+
+    /// Record the trace as JSONL instead of the binary format
+    #[clap(long, requires = "trace")]
+    trace_jsonl: bool,
+}
+
+pub fn run(args: RunArgs, resolver: Option<&LocalizedItemResolver>) -> anyhow::Result<()> {
+    let (_, parsed) = crate::parse_script_file(&args.script, resolver)?;
+    if parsed.steps.is_empty() {
+        bail!("the script has no steps");
+    }
+    let step = match &args.at {
+        Some(label) => {
+            if !parsed.labels.contains_key(label) {
+                bail!("label `{label}` is not defined in the script");
+            }
+            parsed.step_idx_from_label(label).with_context(|| {
+                format!("label `{label}` is before the first step, the state is the initial state")
+            })?
+        }
+        None => parsed.steps.len() - 1,
+    };
+
+    let image = std::fs::read(&args.image)
+        .with_context(|| format!("failed to read image {}", args.image.display()))?;
+    let runtime = sim::Runtime::new(Spawner::new()?);
+    runtime
+        .init(
+            &image,
+            1,
+            Some(&sim::RuntimeInitParams {
+                dlc: 3,
+                program_start: "".to_string(),
+                stack_start: "0x0000008888800000".to_string(),
+                stack_size: 0,
+                heap_free_size: 0,
+                pmdm_addr: "0x0000002222200000".to_string(),
+                features: vec![],
+                symbol_map: String::new(),
+                trace: sim::RuntimeTraceParams {
+                    calls: args.trace_calls,
+                    gdt: args.trace_gdt,
+                    mem_writes: args.trace_mem_write.clone(),
+                    jsonl: args.trace_jsonl,
+                },
+            }),
+        )
+        .context("failed to initialize runtime")?;
+
+    let run = sim::Run::new(Arc::new(sim::RunHandle::new()));
+    let output = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .context("failed to create tokio runtime")?
+        .block_on(run.run_parsed(&parsed, &runtime));
+    let MaybeAborted::Ok(output) = output else {
+        bail!("the run is aborted");
+    };
+
+    if let Some(dir) = &args.trace {
+        write_traces(dir, runtime.take_traces(), args.trace_jsonl)?;
+    }
+
+    for error in &output.errors {
+        let prefix = if error.is_warning { "warning" } else { "error" };
+        eprintln!("{prefix}: {}", error.error);
+    }
+    println!("{}", output.states[step].to_snapshot());
+    Ok(())
+}
+
+/// Write each trace to a file in the directory, named by the order
+/// they are recorded and the byte position of the step
+fn write_traces(dir: &Path, traces: Vec<sim::StepTrace>, jsonl: bool) -> anyhow::Result<()> {
+    if traces.is_empty() {
+        eprintln!("warning: no trace is recorded, since nothing to trace is specified");
+        return Ok(());
+    }
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("failed to create trace directory {}", dir.display()))?;
+    let ext = if jsonl { "jsonl" } else { "bin" };
+    for (i, trace) in traces.iter().enumerate() {
+        let path = dir.join(format!("{i:04}-pos{}.{ext}", trace.pos));
+        std::fs::write(&path, &trace.data)
+            .with_context(|| format!("failed to write trace {}", path.display()))?;
+    }
+    eprintln!("{} trace(s) written to {}", traces.len(), dir.display());
+    Ok(())
+}
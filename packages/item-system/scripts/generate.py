@@ -143,6 +143,14 @@ def process_actor(actor_file) -> tuple[str, dict] | None:
             data["armorStarNum"] = gparam["armorStarNum"]
         if "armorDefenceAddLevel" in gparam:
             data["armorDefenceAddLevel"] = gparam["armorDefenceAddLevel"]
+        upgrade_materials = []
+        for i in range(1, 6):
+            name_key = f"armorUpgradeItem{i}Name"
+            num_key = f"armorUpgradeItem{i}Num"
+            if name_key in gparam and gparam[name_key] and gparam.get(num_key, 0) > 0:
+                upgrade_materials.append([gparam[name_key], gparam[num_key]])
+        if upgrade_materials:
+            data["armorUpgradeMaterials"] = upgrade_materials
 
     if not data:
         return None
@@ -147,6 +147,12 @@ export const DefaultActorData = {
 
     /** [GParam] Defense for armor */
     armorDefenceAddLevel: 0 as number,
+
+    /**
+     * [GParam] Materials (actor name and amount) needed to upgrade
+     * the armor to the next star at a Great Fairy Fountain
+     */
+    armorUpgradeMaterials: [] as [string, number][],
 } as const;
 
 export type ActorData = typeof DefaultActorData;
@@ -1,4 +1,5 @@
 import yaml
+import json
 import os
 import multiprocessing
 import shutil
@@ -58,6 +59,18 @@ def main():
         with open(output_file, "w", encoding="utf-8", newline="\n") as f:
             yaml.dump(data[locale], f, sort_keys=True)
 
+    # names used for native localized item search in the parser
+    search_data = {}
+    for locale in data:
+        search_data[locale] = {
+            k: v for k, v in data[locale].items()
+            if (k.startswith("actor.") and k.endswith(".name")) or (k.startswith("cook.") and ".name" in k)
+        }
+    output_file = os.path.join(output_dir, "item-search.json")
+    print(f"writing {output_file}...")
+    with open(output_file, "w", encoding="utf-8", newline="\n") as f:
+        json.dump(search_data, f, sort_keys=True, ensure_ascii=False)
+
 
 def load_entries_for_actor(actor_file) -> dict[str, dict[str, str]] | None:
     """Load l10n entries from Actor/*.yaml"""
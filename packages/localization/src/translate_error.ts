@@ -47,6 +47,15 @@ export const translateParserError = (
             const index = error.data;
             return translator(key, { index });
         }
+        case "BlockArgCount": {
+            const [name, expected, actual] = error.data;
+            return translator(key, { name, expected, actual });
+        }
+        case "SyntaxExpected": {
+            const [expectation, command] = error.data;
+            const expected = translator(`syntax_expected.${expectation}`);
+            return translator(key, { expected, command });
+        }
         default: {
             if ("data" in error) {
                 return translator(key, { arg: error.data });
@@ -68,6 +77,15 @@ export const translateRuntimeInitError = (
             const [addr_ci, addr_script] = error.data;
             return translator(key, { addr_ci, addr_script });
         }
+        case "InvalidFeature": {
+            const feature = error.data;
+            return translator(key, { feature });
+        }
+        case "InvalidSymbolMap":
+            return translator(key, { message: error.data });
+        case "InvalidTraceRange":
+        case "InvalidWatchRange":
+            return translator(key, { range: error.data });
         default:
             return translator(key);
     }
@@ -84,6 +102,13 @@ export const translateRuntimeError = (
             const more = error.data;
             return translator(key, { more });
         }
+        case "EquipmentAutoEquipped":
+        case "EquipmentBroke":
+        case "GroundItemDespawned":
+        case "UnknownVendorPrice": {
+            const item = translateActorOrAsIs(error.data);
+            return translator(key, { item });
+        }
         case "ItemMismatch": {
             const [actual, expected] = error.data;
             return translator(key, {
@@ -98,10 +123,34 @@ export const translateRuntimeError = (
                 expected_category: translateCategory(expected),
             });
         }
+        case "PouchNotExpandable": {
+            const category = translateCategory(error.data, translator);
+            return translator(key, { category });
+        }
+        case "NotEnoughCurrency": {
+            const [currency, price, amount] = error.data;
+            return translator(key, { currency, price, amount });
+        }
         case "NotEnoughForAllBut": {
             const [need, actual] = error.data;
             return translator(key, { need, actual });
         }
+        case "NotEnoughMaterials": {
+            const [item, need, actual] = error.data;
+            return translator(key, {
+                item: translateActorOrAsIs(item),
+                need,
+                actual,
+            });
+        }
+        case "NotEnoughRupees": {
+            const [price, rupees] = error.data;
+            return translator(key, { price, rupees });
+        }
+        case "RequiresDlc": {
+            const [required, current] = error.data;
+            return translator(key, { required, current });
+        }
         case "SaveNotFound": {
             const name = error.data;
             return translator(key, { name });
@@ -118,6 +167,14 @@ export const translateRuntimeError = (
             const [flag, type, index] = error.data;
             return translator(key, { flag, type, index });
         }
+        case "InvalidFeature": {
+            const feature = error.data;
+            return translator(key, { feature });
+        }
+        case "InvalidDlcVersion": {
+            const version = error.data;
+            return translator(key, { version });
+        }
         default:
             return translator(key);
     }
@@ -0,0 +1,471 @@
+//! Expansion of `block`, `call`, `repeat` and `if`
+//!
+//! Blocks are expanded by replacing the parameters in the source of the body
+//! with the source of the arguments, then parsing the result again. This way,
+//! arguments can be anything that is valid where the parameter is used
+//! (for example, `5 apple` for `hold $items`)
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+
+use teleparse::ToSpan;
+
+use crate::cir;
+use crate::error::{ErrorReport, cir_error};
+use crate::search::{self, QuotedItemResolver};
+use crate::syn;
+
+/// Max depth of `call` and `repeat` nested inside each other
+pub const MAX_BLOCK_DEPTH: u32 = 16;
+
+/// Max number of steps that can be expanded from one statement
+pub const MAX_EXPANDED_STEPS: u32 = 10000;
+
+/// Blocks defined in the script with `block NAME(PARAMS) { ... }`
+#[derive(Debug, Default)]
+pub struct BlockDefs {
+    blocks: BTreeMap<String, BlockDef>,
+}
+
+#[derive(Debug)]
+struct BlockDef {
+    params: Vec<String>,
+    /// Source of the statements in the body
+    body: String,
+}
+
+impl BlockDefs {
+    /// Collect the blocks defined at the top level of the script.
+    ///
+    /// Since all definitions are collected before expanding, a block can
+    /// be called before it's defined
+    pub fn collect<'a>(
+        &mut self,
+        stmts: impl Iterator<Item = &'a syn::Statement>,
+        source: &str,
+        errors: &mut Vec<ErrorReport>,
+    ) {
+        let blocks = &mut self.blocks;
+        for stmt in stmts {
+            let syn::Command::Block(block) = &stmt.cmd else {
+                continue;
+            };
+            let name = block.name.to_string();
+            if blocks.contains_key(&name) {
+                errors.push(cir_error!(&block.name, DuplicateBlock(name)));
+                continue;
+            }
+            let mut params = Vec::new();
+            if let Some(block_params) = block.params.as_ref() {
+                for param in &block_params.params {
+                    params.push(param.name.to_string());
+                }
+            }
+            let body = &source[block.body.open.span().hi..block.body.close.span().lo];
+            blocks.insert(
+                name,
+                BlockDef {
+                    params,
+                    body: body.to_string(),
+                },
+            );
+        }
+    }
+
+    fn names(&self) -> impl Iterator<Item = &str> {
+        self.blocks.keys().map(|x| x.as_str())
+    }
+}
+
+/// A command expanded from a statement
+#[derive(Debug, Clone)]
+pub struct ExpandedCommand {
+    pub command: cir::Command,
+    /// True for commands from a called block or a statement with
+    /// expressions, since the spans inside those commands are in the expanded
+    /// source instead of the script
+    pub expanded: bool,
+    /// The branch of an `if` the command is in, as the number of
+    /// commands back to the `if` command, and the index of the branch
+    pub branch: Option<(usize, u32)>,
+}
+
+/// Expand a top-level statement into the commands to execute.
+///
+/// `call` and `repeat` are expanded into the commands in their bodies,
+/// and block definitions are expanded into nothing. Errors inside
+/// a called block are reported at the `call` statement
+///
+/// `if` is expanded into an [`If`](cir::Command::If) command followed by
+/// the commands in all of its branches, since the branch taken is only
+/// known when the script runs
+///
+/// Expressions in parentheses are evaluated with the variables defined so far.
+pub async fn expand_statement<R: QuotedItemResolver>(
+    command: &syn::Command,
+    source: &str,
+    blocks: &BlockDefs,
+    vars: &cir::Variables,
+    resolver: &R,
+    errors: &mut Vec<ErrorReport>,
+) -> Vec<ExpandedCommand> {
+    let mut expander = Expander {
+        blocks,
+        vars,
+        resolver,
+        commands: Vec::new(),
+        expanded_depth: 0,
+        branch: None,
+        stopped: false,
+    };
+    expander.expand(command, source, 0, errors).await;
+    expander.commands
+}
+
+struct Expander<'a, R> {
+    blocks: &'a BlockDefs,
+    vars: &'a cir::Variables,
+    resolver: &'a R,
+    commands: Vec<ExpandedCommand>,
+    /// Number of expanded sources being parsed, from `call`s or
+    /// statements with expressions
+    expanded_depth: u32,
+    /// The branch being expanded, as the index of the `if` command
+    /// in `commands` and the index of the branch
+    branch: Option<(usize, u32)>,
+    /// If the expansion is stopped because of the limits
+    stopped: bool,
+}
+
+impl<R: QuotedItemResolver> Expander<'_, R> {
+    /// Expand one command. This is boxed since it's recursive
+    fn expand<'b>(
+        &'b mut self,
+        command: &'b syn::Command,
+        source: &'b str,
+        depth: u32,
+        errors: &'b mut Vec<ErrorReport>,
+    ) -> Pin<Box<dyn Future<Output = ()> + 'b>> {
+        Box::pin(async move {
+            if self.stopped {
+                return;
+            }
+            match command {
+                syn::Command::Block(block) => {
+                    // top-level blocks are collected by BlockDefs
+                    if depth > 0 {
+                        errors.push(cir_error!(&block.lit, NestedBlock));
+                    }
+                }
+                syn::Command::Call(call) => {
+                    self.expand_call(call, source, depth, errors).await;
+                }
+                syn::Command::Repeat(repeat) => {
+                    self.expand_repeat(repeat, source, depth, errors).await;
+                }
+                syn::Command::Include(include) => {
+                    // top-level includes are loaded before expanding
+                    if depth > 0 {
+                        errors.push(cir_error!(&include.lit, NestedInclude));
+                    }
+                }
+                syn::Command::Label(label) => {
+                    // top-level labels are recorded when walking the script
+                    if depth > 0 {
+                        errors.push(cir_error!(&label.lit, NestedLabel));
+                    }
+                }
+                syn::Command::Let(let_cmd) => {
+                    // top-level variables are defined when walking the script
+                    if depth > 0 {
+                        errors.push(cir_error!(&let_cmd.lit, NestedLet));
+                    }
+                }
+                syn::Command::If(if_cmd) => {
+                    self.expand_if(if_cmd, source, depth, errors).await;
+                }
+                _ => {
+                    if !self.check_step_limit(command, errors) {
+                        return;
+                    }
+                    let error_count = errors.len();
+                    let substitution = self.vars.substitute(source, command.span(), errors);
+                    if errors.len() > error_count {
+                        return;
+                    }
+                    if let Some(substitution) = substitution {
+                        self.expand_substitution(&substitution, depth, errors).await;
+                        return;
+                    }
+                    if let Some(command) = cir::parse_command(command, self.resolver, errors).await
+                    {
+                        self.push(command);
+                    }
+                }
+            }
+        })
+    }
+
+    /// Check if another command can be added, and stop the expansion if not
+    fn check_step_limit<T: ToSpan>(&mut self, at: &T, errors: &mut Vec<ErrorReport>) -> bool {
+        if self.commands.len() < MAX_EXPANDED_STEPS as usize {
+            return true;
+        }
+        errors.push(cir_error!(at, TooManyExpandedSteps(MAX_EXPANDED_STEPS)));
+        self.stopped = true;
+        false
+    }
+
+    fn push(&mut self, command: cir::Command) {
+        let len = self.commands.len();
+        self.commands.push(ExpandedCommand {
+            command,
+            expanded: self.expanded_depth > 0,
+            branch: self.branch.map(|(i, branch)| (len - i, branch)),
+        });
+    }
+
+    async fn expand_if(
+        &mut self,
+        if_cmd: &syn::CmdIf,
+        source: &str,
+        depth: u32,
+        errors: &mut Vec<ErrorReport>,
+    ) {
+        let mut conditions = Vec::new();
+        let mut bodies = vec![&if_cmd.body];
+        let mut has_errors = false;
+        let mut has_else = false;
+        let first = cir::parse_condition(&if_cmd.condition, self.vars, self.resolver, errors).await;
+        match first {
+            Some(x) => conditions.push(x),
+            None => has_errors = true,
+        }
+        for clause in if_cmd.else_clauses.iter() {
+            if has_else {
+                errors.push(cir_error!(clause, ElseNotLast));
+                has_errors = true;
+                break;
+            }
+            match &clause.rest {
+                syn::ElseRest::If(else_if) => {
+                    let condition =
+                        cir::parse_condition(&else_if.condition, self.vars, self.resolver, errors)
+                            .await;
+                    match condition {
+                        Some(x) => conditions.push(x),
+                        None => has_errors = true,
+                    }
+                    bodies.push(&else_if.body);
+                }
+                syn::ElseRest::Else(body) => {
+                    has_else = true;
+                    bodies.push(body);
+                }
+            }
+        }
+        if has_errors {
+            return;
+        }
+        if depth >= MAX_BLOCK_DEPTH {
+            errors.push(cir_error!(if_cmd, BlockTooDeep(MAX_BLOCK_DEPTH)));
+            self.stopped = true;
+            return;
+        }
+        if !self.check_step_limit(if_cmd, errors) {
+            return;
+        }
+
+        let if_idx = self.commands.len();
+        self.push(cir::Command::If(conditions));
+        let outer_branch = self.branch;
+        for (i, body) in bodies.into_iter().enumerate() {
+            self.branch = Some((if_idx, i as u32));
+            for stmt in body.stmts.iter() {
+                self.expand(&stmt.cmd, source, depth + 1, errors).await;
+            }
+        }
+        self.branch = outer_branch;
+    }
+
+    async fn expand_call(
+        &mut self,
+        call: &syn::CmdCall,
+        source: &str,
+        depth: u32,
+        errors: &mut Vec<ErrorReport>,
+    ) {
+        let blocks = self.blocks;
+        let name = call.name.to_string();
+        let Some(block) = blocks.blocks.get(&name) else {
+            let suggestions = search::suggest_from(&name, blocks.names());
+            errors.push(cir_error!(&call.name, UndefinedBlock(name)).with_suggestions(suggestions));
+            return;
+        };
+        let mut args = Vec::new();
+        if let Some(call_args) = call.args.as_ref() {
+            for arg in &call_args.args {
+                let span = arg.span();
+                args.push(&source[span.lo..span.hi]);
+            }
+        }
+        if args.len() != block.params.len() {
+            errors.push(cir_error!(
+                call,
+                BlockArgCount(name, block.params.len() as u32, args.len() as u32)
+            ));
+            return;
+        }
+        if depth >= MAX_BLOCK_DEPTH {
+            errors.push(cir_error!(call, BlockTooDeep(MAX_BLOCK_DEPTH)));
+            self.stopped = true;
+            return;
+        }
+
+        let body = substitute_params(&block.body, &block.params, &args);
+        let mut body_errors = Vec::new();
+        self.expanded_depth += 1;
+        self.expand_source(&body, depth + 1, &mut body_errors).await;
+        self.expanded_depth -= 1;
+
+        // spans in the errors are in the expanded source,
+        // so they are reported at the call instead
+        let span = call.span();
+        for mut error in body_errors {
+            error.span = (span.lo, span.hi);
+            error.suggestions.clear();
+            errors.push(error);
+        }
+    }
+
+    async fn expand_repeat(
+        &mut self,
+        repeat: &syn::CmdRepeat,
+        source: &str,
+        depth: u32,
+        errors: &mut Vec<ErrorReport>,
+    ) {
+        let times = match &repeat.times {
+            syn::NumberOrParam::Number(times) => {
+                let span = times.span();
+                match self.vars.eval_number(times, span) {
+                    Ok(times) => match i32::try_from(times) {
+                        Ok(times) => times,
+                        Err(_) => {
+                            errors.push(cir_error!(span, IntRange(times.to_string())));
+                            return;
+                        }
+                    },
+                    Err(e) => {
+                        errors.push(e);
+                        return;
+                    }
+                }
+            }
+            syn::NumberOrParam::Param(param) => {
+                errors.push(cir_error!(param, UnboundParam(param.to_string())));
+                return;
+            }
+        };
+        if times < 0 {
+            errors.push(cir_error!(&repeat.times, InvalidRepeatCount(times)));
+            return;
+        }
+        if depth >= MAX_BLOCK_DEPTH {
+            errors.push(cir_error!(repeat, BlockTooDeep(MAX_BLOCK_DEPTH)));
+            self.stopped = true;
+            return;
+        }
+
+        // expanding the body always gives the same commands,
+        // so it's only expanded once, which also avoids duplicated errors
+        let start = self.commands.len();
+        for stmt in repeat.body.stmts.iter() {
+            self.expand(&stmt.cmd, source, depth + 1, errors).await;
+        }
+        if self.stopped {
+            return;
+        }
+        let body = self.commands.split_off(start);
+        // the count can overflow on 32-bit targets (i.e. wasm) with large repeats
+        let total = body
+            .len()
+            .checked_mul(times as usize)
+            .and_then(|x| x.checked_add(start));
+        if total.is_none_or(|x| x > MAX_EXPANDED_STEPS as usize) {
+            errors.push(cir_error!(repeat, TooManyExpandedSteps(MAX_EXPANDED_STEPS)));
+            self.stopped = true;
+            return;
+        }
+        for i in 0..times as usize {
+            // branches of an `if` outside of the body are further away in each copy
+            let shift = i * body.len();
+            self.commands
+                .extend(body.iter().enumerate().map(|(j, command)| {
+                    let mut command = command.clone();
+                    if let Some((distance, _)) = &mut command.branch
+                        && *distance > j
+                    {
+                        *distance += shift;
+                    }
+                    command
+                }));
+        }
+    }
+
+    /// Parse the source of a statement with the expressions replaced and expand it
+    async fn expand_substitution(
+        &mut self,
+        substitution: &cir::Substitution,
+        depth: u32,
+        errors: &mut Vec<ErrorReport>,
+    ) {
+        let mut statement_errors = Vec::new();
+        self.expanded_depth += 1;
+        self.expand_source(&substitution.source, depth, &mut statement_errors)
+            .await;
+        self.expanded_depth -= 1;
+
+        // spans in the errors are in the replaced source
+        for mut error in statement_errors {
+            error.span = substitution.map_span(error.span);
+            errors.push(error);
+        }
+    }
+
+    /// Parse the source of an expanded block and expand the statements in it
+    async fn expand_source(&mut self, source: &str, depth: u32, errors: &mut Vec<ErrorReport>) {
+        let recovered = crate::recover::parse_script(source, errors);
+        for stmt in recovered.stmts.iter() {
+            self.expand(&stmt.cmd, &recovered.source, depth, errors)
+                .await;
+        }
+    }
+}
+
+/// Replace `$PARAM` in the source of a block body with the source of the arguments
+///
+/// Unknown parameters are kept as-is, and are reported when the body is parsed
+fn substitute_params(body: &str, params: &[String], args: &[&str]) -> String {
+    let mut out = String::with_capacity(body.len());
+    let mut rest = body;
+    while let Some(i) = rest.find('$') {
+        out.push_str(&rest[..i]);
+        let after = &rest[i + 1..];
+        let len = after
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+            .unwrap_or(after.len());
+        let name = &after[..len];
+        match params.iter().position(|p| p == name) {
+            Some(j) => out.push_str(args[j]),
+            None => {
+                out.push('$');
+                out.push_str(name);
+            }
+        }
+        rest = &after[len..];
+    }
+    out.push_str(rest);
+    out
+}
@@ -1,5 +1,5 @@
 use enumset::EnumSet;
-use teleparse::{Root, ToSpan};
+use teleparse::{Root, Span, ToSpan};
 
 use crate::cir;
 use crate::error::{ErrorReport, cir_fail};
@@ -34,6 +34,46 @@ pub fn parse_category(category: &syn::CategoryName) -> Category {
     }
 }
 
+/// All words that can be parsed as a category.
+///
+/// Used for suggestions, must be kept in sync with the category keywords in `syn`
+pub const CATEGORY_NAMES: &[&str] = &[
+    "weapon",
+    "weapons",
+    "bow",
+    "bows",
+    "shield",
+    "shields",
+    "armor",
+    "armors",
+    "armor-head",
+    "head-armor",
+    "head-armors",
+    "armor-body",
+    "body-armor",
+    "body-armors",
+    "armor-chest",
+    "chest-armor",
+    "chest-armors",
+    "armor-upper",
+    "upper-armor",
+    "upper-armors",
+    "armor-leg",
+    "armor-legs",
+    "leg-armor",
+    "leg-armors",
+    "armor-lower",
+    "lower-armor",
+    "lower-armors",
+    "material",
+    "materials",
+    "food",
+    "foods",
+    "key-item",
+    "key-items",
+];
+
+/// Parse a category from a string. Returns `None` if the string is not a category
 pub fn parse_category_from_str(category: &str) -> Option<Category> {
     let category = syn::CategoryName::parse(category).ok()??;
     Some(parse_category(&category))
@@ -72,9 +112,23 @@ pub fn parse_times_clause(times: Option<&syn::TimesClause>) -> Result<i64, Error
     let Some(times) = times else {
         return Ok(1);
     };
-    let t = cir::parse_syn_int_str_i32(&times.times, times.span())?;
+    parse_times_num(&times.times, times.span())
+}
+
+/// Parse the number of times to do something, which must be at least 1
+pub fn parse_times_num(times: &syn::Number, span: Span) -> Result<i64, ErrorReport> {
+    let t = cir::parse_syn_int_str_i32(times, span)?;
     if t < 1 {
-        cir_fail!(times, InvalidTimesClause(t));
+        cir_fail!(span, InvalidTimesClause(t));
     }
     Ok(t as i64)
 }
+
+/// Parse a DLC version number, which must be 0-3
+pub fn parse_dlc_version(version: &syn::Number, span: Span) -> Result<u32, ErrorReport> {
+    let v = cir::parse_syn_int_str_i32(version, span)?;
+    if !(0..=3).contains(&v) {
+        cir_fail!(span, InvalidDlcVersion(v));
+    }
+    Ok(v as u32)
+}
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use teleparse::{Span, ToSpan};
 
 use crate::cir;
-use crate::error::{ErrorReport, absorb_error};
+use crate::error::{ErrorReport, absorb_error, cir_fail};
 use crate::search::QuotedItemResolver;
 use crate::syn;
 
@@ -16,34 +16,127 @@ pub struct Step {
     /// The notes associated with this step
     /// Note many steps can share the same note
     pub notes: Arc<str>,
+
+    /// Start byte position of the step in the main script
+    pos: usize,
+
+    /// Span of the `include` in the main script, if the step is in an included script
+    include: Option<Span>,
+
+    /// If the step is expanded from a block. The spans inside
+    /// the command are in the expanded source in this case,
+    /// and do not point to the script
+    expanded: bool,
+
+    /// The branch of an `if` this step is in
+    branch: Option<StepBranch>,
+}
+
+/// A branch of an `if` statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepBranch {
+    /// Index of the step with the [`If`](Command::If) command
+    pub step: usize,
+    /// Index of the branch. The `else` branch is the
+    /// number of conditions in the `if`
+    pub branch: u32,
 }
 
 impl Step {
     pub fn new(span: Span, command: cir::Command, notes: Arc<str>) -> Self {
         Self {
-            command: CommandWithSpan { command, span },
+            command: CommandWithSpan {
+                command,
+                span,
+                file: 0,
+            },
             notes,
+            pos: span.lo,
+            include: None,
+            expanded: false,
+            branch: None,
         }
     }
 
-    /// Get the start byte position of the step in source script
+    /// Set the step to be in an included script. `include` is the span
+    /// of the `include` in the main script
+    pub fn in_file(mut self, file: u32, include: Span) -> Self {
+        self.command.file = file;
+        self.pos = include.lo;
+        self.include = Some(include);
+        self
+    }
+
+    /// Mark the step as expanded from a block
+    pub fn expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self
+    }
+
+    /// Set the branch of an `if` the step is in. The step
+    /// is only executed if the branch is taken
+    pub fn in_branch(mut self, branch: Option<StepBranch>) -> Self {
+        self.branch = branch;
+        self
+    }
+
+    /// Get the branch of an `if` the step is in, if any
+    pub fn branch(&self) -> Option<StepBranch> {
+        self.branch
+    }
+
+    /// Get the start byte position of the step in the main script
+    ///
+    /// For steps in included scripts, this is the position of the `include`
     pub fn pos(&self) -> usize {
-        self.command.span.lo
+        self.pos
     }
 
+    /// Get the span of the step in the script it's in (see [`file`](Self::file))
     pub fn span(&self) -> Span {
         self.command.span
     }
 
+    /// Get the script the step is in. 0 is the main script,
+    /// see [`ParseOutput::files`](crate::ParseOutput::files)
+    pub fn file(&self) -> u32 {
+        self.command.file
+    }
+
     pub fn command(&self) -> &Command {
         &self.command.command
     }
+
+    /// Update an error produced by executing this step to point
+    /// to the right place
+    ///
+    /// Errors in included scripts are not visible in the main script,
+    /// so a copy of the error at the `include` is also returned, like
+    /// errors from parsing the included script
+    pub fn locate_error<E: Clone>(
+        &self,
+        mut error: skybook_api::ErrorReport<E>,
+    ) -> impl Iterator<Item = skybook_api::ErrorReport<E>> {
+        if self.expanded {
+            let span = self.span();
+            error.span = (span.lo, span.hi);
+            error.suggestions.clear();
+        }
+        let at_include = self.include.map(|include| {
+            let mut error = error.clone().in_file(0);
+            error.span = (include.lo, include.hi);
+            error.suggestions.clear();
+            error
+        });
+        std::iter::once(error.in_file(self.file())).chain(at_include)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CommandWithSpan {
     command: Command,
     span: Span,
+    file: u32,
 }
 
 /// The command to be executed in the simulator
@@ -51,6 +144,11 @@ pub struct CommandWithSpan {
 pub enum Command {
     /// Multiple commands acting as one
     Multi(Vec<cir::Command>),
+    /// See [`syn::CmdIf`], with the condition of each branch
+    ///
+    /// The branch taken is the first one whose condition is true,
+    /// or the `else` branch if none of them are
+    If(Vec<cir::Condition>),
 
     /// Disable performance optimization that may be inaccurate
     CoAccuratelySimulate,
@@ -58,10 +156,15 @@ pub enum Command {
     Get(Vec<cir::ItemSpec>),
     /// See [`syn::CmdPickUp`]
     PickUp(Vec<cir::ItemSelectSpec>),
+    /// See [`syn::CmdReceive`]
+    ///
+    /// Items are boxed as a slice to keep the size of the command
+    Receive(Box<[cir::ItemSpec]>, cir::ReceiveSource, cir::ReceiveTiming),
     /// `:pause-during` annotation.
     ///
     /// - Get: Assumes an item text box will appear for the next command,
     ///   and open the pause menu during such text box
+    /// - Receive: Open the pause menu during the item text box
     /// - Throw/Display/OvDrop: Delay removal of the weapon til after dialog closes
     ///   (for making translucent items)
     CoPauseDuring,
@@ -101,10 +204,14 @@ pub enum Command {
     Equip(Vec<cir::ItemSelectSpec>),
     /// See [`syn::CmdUnequip`]
     Unequip(Vec<cir::ItemSelectSpec>),
-    /// See [`syn::CmdUse`] and [`crate::syn::CmdShoot`], second arg is times
+    /// See [`syn::CmdUse`], second arg is times
     Use(Box<cir::ItemNameSpec>, usize),
+    /// See [`syn::CmdShoot`], first arg is the arrow to equip, second arg is times
+    Shoot(Option<Box<cir::ItemSelectSpec>>, usize),
     /// See [`syn::CmdCoPerUse`]
     CoPerUse(i32),
+    /// Specify another equipment should not be equipped when one breaks
+    CoNoAutoEquip,
     /// Specify the throwing action should not break the weapon
     CoNonBreaking,
     /// Specify the throwing action should break the weapon
@@ -114,14 +221,18 @@ pub enum Command {
     /// See [`syn::CmdDisplay`]
     Display(Vec<cir::ItemSelectSpec>),
 
-    /// See [`syn::CmdOpenShop`]
-    OpenShop,
+    /// See [`syn::CmdOpenShop`], with the name of the NPC if specified
+    OpenShop(Option<String>),
     /// See [`syn::CmdCloseShop`]
     CloseShop,
     /// See [`syn::CmdBuy`]
     Buy(Vec<cir::ItemSpec>),
     /// See [`syn::CmdSell`]
     Sell(Vec<cir::ItemSelectSpec>),
+    /// See [`syn::CmdUpgrade`]
+    Upgrade(Box<cir::ItemSelectSpec>),
+    /// See [`syn::CmdExpand`]
+    Expand(cir::Category),
     /// `:same-dialog` annotation.
     ///
     /// Buy from the same NPC right after selling without
@@ -136,6 +247,8 @@ pub enum Command {
     CloseGame,
     /// `new-game` - Start a new game
     NewGame,
+    /// See [`syn::CmdWait`], the duration is in real-time seconds
+    Wait(u32),
 
     /// See [`syn::CmdSuBreak`]
     SuBreak(i32),
@@ -161,6 +274,12 @@ pub enum Command {
     ///
     /// First arg is flag name
     SuSetGdt(String, Box<cir::GdtMeta>),
+    /// See [`syn::CmdCoFeatures`]
+    CoFeatures(Box<cir::FeatureMeta>),
+    /// See [`syn::CmdCoGroundLimit`]
+    CoGroundLimit(Box<cir::GroundLimitMeta>),
+    /// See [`syn::CmdCoDlc`], the version is 0-3
+    CoDlc(u32),
 
     /// See [`syn::CmdRoast`] and [`crate::syn::CmdBake`]
     Roast(Vec<cir::ItemSelectSpec>),
@@ -226,6 +345,14 @@ pub async fn parse_command<R: QuotedItemResolver>(
         C::PickUp(cmd) => Some(X::PickUp(
             cir::parse_item_list_constrained(&cmd.items, resolver, errors).await,
         )),
+        C::Receive(cmd) => {
+            let (source, timing) = absorb_error(
+                errors,
+                cir::parse_receive_source(&cmd.source, cmd.source.span()),
+            )?;
+            let items = cir::parse_item_list_finite(&cmd.items, resolver, errors).await;
+            Some(X::Receive(items.into_boxed_slice(), source, timing))
+        }
         A![PauseDuring(_)] => Some(X::CoPauseDuring),
         //////////////////////////////////////////////////////////////////
         C::OpenInv(_) => Some(X::OpenInv),
@@ -272,11 +399,27 @@ pub async fn parse_command<R: QuotedItemResolver>(
             Some(X::Use(Box::new(item), times as usize))
         }
         C::Shoot(cmd) => {
-            let times = absorb_error(errors, cir::parse_times_clause(cmd.times.as_ref()))?;
-            Some(X::Use(
-                Box::new(cir::ItemNameSpec::Category(cir::Category::Bow)),
-                times as usize,
-            ))
+            let (arrow, times) = match cmd.args.as_ref() {
+                None => (None, 1),
+                Some(syn::ShootArgs::Numbered(args)) => {
+                    let times = absorb_error(errors, cir::parse_times_num(&args.num, args.span()))?;
+                    match &args.rest {
+                        syn::ShootNumberedRest::Times(_) => (None, times),
+                        syn::ShootNumberedRest::Arrow(arrow) => (Some(arrow), times),
+                    }
+                }
+                Some(syn::ShootArgs::Arrow(args)) => {
+                    let times = absorb_error(errors, cir::parse_times_clause(args.times.as_ref()))?;
+                    (Some(&args.arrow), times)
+                }
+            };
+            let arrow = match arrow {
+                None => None,
+                Some(arrow) => Some(Box::new(
+                    cir::parse_one_item_constrained(arrow, resolver, errors).await?,
+                )),
+            };
+            Some(X::Shoot(arrow, times as usize))
         }
         A![PerUse(cmd)] => {
             let amount = absorb_error(
@@ -285,6 +428,7 @@ pub async fn parse_command<R: QuotedItemResolver>(
             )?;
             Some(X::CoPerUse(amount))
         }
+        A![NoAutoEquip(_)] => Some(X::CoNoAutoEquip),
         A![NonBreaking(_)] => Some(X::CoNonBreaking),
         A![Breaking(_)] => Some(X::CoBreaking),
         C::ThrowWeapon(_) => Some(X::ThrowWeapon),
@@ -292,7 +436,7 @@ pub async fn parse_command<R: QuotedItemResolver>(
             cir::parse_item_list_constrained(&cmd.items, resolver, errors).await,
         )),
         //////////////////////////////////////////////////////////////////
-        C::OpenShop(_) => Some(X::OpenShop),
+        C::OpenShop(cmd) => Some(X::OpenShop(cmd.name.as_ref().map(|x| x.to_string()))),
         C::CloseShop(_) => Some(X::CloseShop),
         C::Buy(cmd) => Some(X::Buy(
             cir::parse_item_list_finite(&cmd.items, resolver, errors).await,
@@ -300,13 +444,40 @@ pub async fn parse_command<R: QuotedItemResolver>(
         C::Sell(cmd) => Some(X::Sell(
             cir::parse_item_list_constrained(&cmd.items, resolver, errors).await,
         )),
+        C::Upgrade(cmd) => Some(X::Upgrade(Box::new(
+            cir::parse_one_item_constrained(&cmd.item, resolver, errors).await?,
+        ))),
+        C::Expand(cmd) => {
+            let category = absorb_error(
+                errors,
+                cir::parse_category_in(
+                    &cmd.category,
+                    cir::Category::Weapon | cir::Category::Bow | cir::Category::Shield,
+                ),
+            )?;
+            Some(X::Expand(category))
+        }
         A![SameDialog(_)] => Some(X::CoSameDialog),
         //////////////////////////////////////////////////////////////////
         C::Save(_) => Some(X::Save(None)),
-        C::SaveAs(cmd) => Some(X::Save(Some(parse_save_name(&cmd.name)))),
-        C::Reload(cmd) => Some(X::Reload(cmd.name.as_ref().map(parse_save_name))),
+        C::SaveAs(cmd) => {
+            absorb_error(errors, parse_save_name(&cmd.name)).map(|x| X::Save(Some(x)))
+        }
+        C::Reload(cmd) => match cmd.name.as_ref() {
+            None => Some(X::Reload(None)),
+            Some(name) => absorb_error(errors, parse_save_name(name)).map(|x| X::Reload(Some(x))),
+        },
         C::CloseGame(_) => Some(X::CloseGame),
         C::NewGame(_) => Some(X::NewGame),
+        C::Wait(cmd) => absorb_error(errors, cir::parse_wait_duration(cmd)).map(X::Wait),
+        // blocks, includes and ifs are expanded before getting here
+        C::Block(_)
+        | C::Call(_)
+        | C::Repeat(_)
+        | C::Include(_)
+        | C::Label(_)
+        | C::Let(_)
+        | C::If(_) => None,
         //////////////////////////////////////////////////////////////////
         C::SuBreak(cmd) => absorb_error(
             errors,
@@ -366,6 +537,17 @@ pub async fn parse_command<R: QuotedItemResolver>(
             }
             Some(X::Multi(cmds))
         }
+        A![Features(cmd)] => Some(X::CoFeatures(Box::new(cir::parse_feature_meta(
+            &cmd.meta, errors,
+        )))),
+        A![GroundLimit(cmd)] => Some(X::CoGroundLimit(Box::new(cir::parse_ground_limit_meta(
+            &cmd.meta, errors,
+        )))),
+        A![Dlc(cmd)] => absorb_error(
+            errors,
+            cir::parse_dlc_version(&cmd.version, cmd.version.span()),
+        )
+        .map(X::CoDlc),
 
         //////////////////////////////////////////////////////////////////
         syn::Command::Roast(cmd) => Some(cir::Command::Roast(
@@ -408,10 +590,11 @@ pub async fn parse_command<R: QuotedItemResolver>(
 
 /// Parse a save name. Note that "empty string" is allowed
 /// and is different from the manual save
-fn parse_save_name(name: &syn::ItemName) -> String {
+fn parse_save_name(name: &syn::ItemName) -> Result<String, ErrorReport> {
     match name {
-        syn::ItemName::Word(word) => word.to_string(),
-        syn::ItemName::Quoted(quoted) => quoted.as_str().trim_matches('"').to_string(),
-        syn::ItemName::Angle(word) => word.name.to_string(),
+        syn::ItemName::Word(word) => Ok(word.to_string()),
+        syn::ItemName::Quoted(quoted) => Ok(quoted.as_str().trim_matches('"').to_string()),
+        syn::ItemName::Angle(word) => Ok(word.name.to_string()),
+        syn::ItemName::Param(param) => cir_fail!(param, UnboundParam(param.to_string())),
     }
 }
@@ -0,0 +1,89 @@
+//! Conditions in `if`, which are checked when the script runs
+
+use teleparse::ToSpan;
+
+use crate::cir;
+use crate::error::{ErrorReport, absorb_error};
+use crate::search::QuotedItemResolver;
+use crate::syn;
+
+/// A condition to check against the state, see [`syn::Condition`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Condition {
+    /// Compare the number of the item, or the number of slots
+    /// if it's a category
+    Count(Box<cir::ItemSelectSpec>, Compare, i64),
+    /// Compare the number of items in the pouch (`mCount`)
+    MCount(Compare, i64),
+    /// The gamedata flag has the value. First arg is the flag name
+    Gdt(String, Box<cir::GdtMeta>),
+}
+
+/// Comparison operator in a condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compare {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Compare {
+    /// Check if `actual OP expected` is true
+    pub fn check(self, actual: i64, expected: i64) -> bool {
+        match self {
+            Self::Eq => actual == expected,
+            Self::Ne => actual != expected,
+            Self::Lt => actual < expected,
+            Self::Le => actual <= expected,
+            Self::Gt => actual > expected,
+            Self::Ge => actual >= expected,
+        }
+    }
+}
+
+impl From<&syn::Comparison> for Compare {
+    fn from(value: &syn::Comparison) -> Self {
+        match value {
+            syn::Comparison::Eq(_) => Self::Eq,
+            syn::Comparison::Ne(_) => Self::Ne,
+            syn::Comparison::Le(_) => Self::Le,
+            syn::Comparison::Ge(_) => Self::Ge,
+            syn::Comparison::Lt(_) => Self::Lt,
+            syn::Comparison::Gt(_) => Self::Gt,
+        }
+    }
+}
+
+/// Parse a condition. The value to compare with can be an expression
+pub async fn parse_condition<R: QuotedItemResolver>(
+    condition: &syn::Condition,
+    vars: &cir::Variables,
+    resolver: &R,
+    errors: &mut Vec<ErrorReport>,
+) -> Option<Condition> {
+    match condition {
+        syn::Condition::Count(cond) => {
+            let value = vars.eval_number(&cond.value, cond.value.span());
+            let value = absorb_error(errors, value);
+            let item = cir::parse_one_item_constrained(&cond.item, resolver, errors).await?;
+            Some(Condition::Count(
+                Box::new(item),
+                Compare::from(&cond.op),
+                value?,
+            ))
+        }
+        syn::Condition::MCount(cond) => {
+            let value = vars.eval_number(&cond.value, cond.value.span());
+            let value = absorb_error(errors, value)?;
+            Some(Condition::MCount(Compare::from(&cond.op), value))
+        }
+        syn::Condition::Gdt(cond) => {
+            let meta = cir::parse_gdt_meta(&cond.props, errors)?;
+            let flag_name = cond.flag_name.name.to_string();
+            Some(Condition::Gdt(flag_name, Box::new(meta)))
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use teleparse::ToSpan;
+
+use crate::cir;
+use crate::error::{ErrorReport, cir_fail};
+use crate::syn;
+
+/// Parse the duration of `wait` into real-time seconds
+///
+/// In-game time passes 60 times faster than real time,
+/// i.e. one in-game minute is one real-time second. In-game
+/// durations are rounded up to the next real-time second
+pub fn parse_wait_duration(cmd: &syn::CmdWait) -> Result<u32, ErrorReport> {
+    let amount = cir::parse_syn_int_str_i32(&cmd.amount, cmd.amount.span())?;
+    if amount < 0 {
+        cir_fail!(cmd.amount.span(), InvalidDuration(amount));
+    }
+    let seconds_per_unit: u64 = match cmd.unit.to_ascii_lowercase().as_str() {
+        "s" | "sec" | "secs" | "second" | "seconds" => 1,
+        "m" | "min" | "mins" | "minute" | "minutes" => 60,
+        "h" | "hr" | "hrs" | "hour" | "hours" => 3600,
+        "d" | "day" | "days" => 86400,
+        _ => cir_fail!(cmd.unit.span(), InvalidDurationUnit(cmd.unit.to_string())),
+    };
+    let mut seconds = amount as u64 * seconds_per_unit;
+    if cmd.in_game.is_some() {
+        seconds = seconds.div_ceil(60);
+    }
+    match u32::try_from(seconds) {
+        Ok(x) => Ok(x),
+        Err(_) => cir_fail!(cmd.span(), IntRange(seconds.to_string())),
+    }
+}
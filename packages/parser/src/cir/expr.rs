@@ -0,0 +1,220 @@
+//! Evaluation of `let` and integer expressions
+//!
+//! Like block parameters, expressions are evaluated by replacing them in the
+//! source of the statement with their values, then parsing the result again.
+//! This way, an expression can be used anywhere a number can be used
+
+use std::collections::BTreeMap;
+
+use teleparse::{Root, Span, ToSpan};
+
+use crate::cir;
+use crate::error::{ErrorReport, absorb_error, cir_error, cir_fail};
+use crate::search;
+use crate::syn;
+
+/// Variables defined in the script with `let NAME = EXPR`
+#[derive(Debug, Default)]
+pub struct Variables {
+    values: BTreeMap<String, i64>,
+}
+
+impl Variables {
+    /// Define a variable, or change the value of an existing one
+    pub fn set(&mut self, name: String, value: i64) {
+        self.values.insert(name, value);
+    }
+
+    /// Evaluate a number, which is either a literal or an expression
+    /// in parentheses, e.g. `(count - 2)`
+    pub fn eval_number(&self, number: &str, span: Span) -> Result<i64, ErrorReport> {
+        if !number.starts_with('(') {
+            return cir::parse_syn_int_str(number, span);
+        }
+        let Ok(Some(expr)) = syn::ParenExpr::parse(number) else {
+            cir_fail!(span, IntFormat(number.to_string()));
+        };
+        self.eval_expr(&expr.expr, number, span.lo)
+    }
+
+    /// Evaluate an expression. The spans in the expression are in `source`,
+    /// which starts at `offset` in the script
+    pub fn eval_expr(
+        &self,
+        expr: &syn::Expr,
+        source: &str,
+        offset: usize,
+    ) -> Result<i64, ErrorReport> {
+        let overflow = || {
+            let span = expr.span();
+            let text = source[span.lo..span.hi].to_string();
+            cir_error!(shift_span(span, offset), IntRange(text))
+        };
+        // `*` and `/` are applied to the current term, which is added
+        // to the sum when the next `+` or `-` is seen
+        let mut sum = 0i64;
+        let mut term = self.eval_operand(&expr.first, offset)?;
+        for rest in expr.rest.iter() {
+            let value = self.eval_operand(&rest.operand, offset)?;
+            term = match &rest.op {
+                syn::Operator::Add(_) => {
+                    sum = sum.checked_add(term).ok_or_else(overflow)?;
+                    value
+                }
+                syn::Operator::Sub(_) => {
+                    sum = sum.checked_add(term).ok_or_else(overflow)?;
+                    value.checked_neg().ok_or_else(overflow)?
+                }
+                syn::Operator::Mul(_) => term.checked_mul(value).ok_or_else(overflow)?,
+                syn::Operator::Div(_) => {
+                    if value == 0 {
+                        let span = shift_span(rest.operand.span(), offset);
+                        cir_fail!(span, DivideByZero);
+                    }
+                    term.checked_div(value).ok_or_else(overflow)?
+                }
+            };
+        }
+        sum.checked_add(term).ok_or_else(overflow)
+    }
+
+    fn eval_operand(&self, operand: &syn::Operand, offset: usize) -> Result<i64, ErrorReport> {
+        let span = shift_span(operand.span(), offset);
+        match operand {
+            syn::Operand::Number(x) => cir::parse_syn_int_str(x, span),
+            syn::Operand::Variable(x) => {
+                let name = x.to_string();
+                match self.values.get(&name) {
+                    Some(value) => Ok(*value),
+                    None => {
+                        let names = self.values.keys().map(|x| x.as_str());
+                        let suggestions = search::suggest_from(&name, names);
+                        Err(cir_error!(span, UndefinedVariable(name)).with_suggestions(suggestions))
+                    }
+                }
+            }
+            syn::Operand::Param(x) => Err(cir_error!(span, UnboundParam(x.to_string()))),
+        }
+    }
+
+    /// Replace the expressions in the source of a statement with their values.
+    ///
+    /// `span` is the span of the statement in `source`. Returns `None` if
+    /// the statement has no expressions, or if any expression has errors
+    pub fn substitute(
+        &self,
+        source: &str,
+        span: Span,
+        errors: &mut Vec<ErrorReport>,
+    ) -> Option<Substitution> {
+        let statement = &source[span.lo..span.hi];
+        let exprs = find_paren_exprs(statement);
+        if exprs.is_empty() {
+            return None;
+        }
+        let mut out = String::with_capacity(statement.len());
+        let mut replaced = Vec::with_capacity(exprs.len());
+        let mut has_errors = false;
+        let mut last = 0;
+        for (lo, hi) in exprs {
+            out.push_str(&statement[last..lo]);
+            last = hi;
+            let expr_span = Span::new(span.lo + lo, span.lo + hi);
+            let value = self.eval_number(&statement[lo..hi], expr_span);
+            let Some(value) = absorb_error(errors, value) else {
+                has_errors = true;
+                continue;
+            };
+            let new_lo = out.len();
+            out.push_str(&value.to_string());
+            replaced.push((Span::new(lo, hi), Span::new(new_lo, out.len())));
+        }
+        out.push_str(&statement[last..]);
+        if has_errors {
+            return None;
+        }
+        Some(Substitution {
+            source: out,
+            offset: span.lo,
+            replaced,
+        })
+    }
+}
+
+/// Source of a statement with the expressions replaced by their values
+#[derive(Debug)]
+pub struct Substitution {
+    /// The source of the statement after replacing
+    pub source: String,
+    /// Position of the statement in the original source
+    offset: usize,
+    /// Span of each replaced expression in the statement, before and after replacing
+    replaced: Vec<(Span, Span)>,
+}
+
+impl Substitution {
+    /// Map a span in the replaced source back to the original source.
+    ///
+    /// The span is extended to the whole expression if it's inside a replaced value
+    pub fn map_span(&self, span: (usize, usize)) -> (usize, usize) {
+        (self.map_pos(span.0, false), self.map_pos(span.1, true))
+    }
+
+    fn map_pos(&self, pos: usize, is_end: bool) -> usize {
+        let mut mapped = pos;
+        for (original, new) in &self.replaced {
+            let (before, inside) = if is_end {
+                (pos <= new.lo, pos <= new.hi)
+            } else {
+                (pos < new.lo, pos < new.hi)
+            };
+            if before {
+                break;
+            }
+            if inside {
+                let mapped = if is_end { original.hi } else { original.lo };
+                return self.offset + mapped;
+            }
+            mapped = original.hi + (pos - new.hi);
+        }
+        self.offset + mapped
+    }
+}
+
+fn shift_span(span: Span, offset: usize) -> Span {
+    Span::new(span.lo + offset, span.hi + offset)
+}
+
+/// Find the `(...)` in the source of a statement, skipping quoted words,
+/// block literals and comments
+///
+/// Only statements that are not `block` or `call` are checked, so
+/// parentheses are always expressions
+fn find_paren_exprs(source: &str) -> Vec<(usize, usize)> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while let Some(offset) = source[i..].find(['(', '"', '\'', '/', '#']) {
+        let start = i + offset;
+        let rest = &source[start..];
+        let skipped = if rest.starts_with('(') {
+            let len = rest.find(')').map(|x| x + 1);
+            if let Some(len) = len {
+                out.push((start, start + len));
+            }
+            len
+        } else if rest.starts_with('"') {
+            rest[1..].find('"').map(|x| x + 2)
+        } else if rest.starts_with("'''") {
+            rest[3..].find("'''").map(|x| x + 6)
+        } else if rest.starts_with("//") || rest.starts_with('#') {
+            rest.find('\n').map(|x| x + 1)
+        } else {
+            Some(1)
+        };
+        let Some(skipped) = skipped else {
+            break;
+        };
+        i = start + skipped;
+    }
+    out
+}
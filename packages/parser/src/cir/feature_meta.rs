@@ -0,0 +1,46 @@
+use teleparse::{Span, tp};
+
+use crate::cir;
+use crate::error::{ErrorReport, cir_error};
+use crate::syn;
+
+use super::MetaParser;
+
+/// Metadata for the `:features` annotation
+///
+/// The feature names are not validated by the parser, since
+/// the features are defined by the runtime
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FeatureMeta {
+    /// Kebab-case feature names and if the feature should be on or off,
+    /// in the order they are specified
+    pub features: Vec<(String, bool)>,
+}
+
+/// Parse the meta for `:features` annotation
+pub fn parse_feature_meta(meta: &syn::Meta, errors: &mut Vec<ErrorReport>) -> FeatureMeta {
+    cir::parse_meta(meta, FeatureMeta::default(), errors)
+}
+
+impl MetaParser for FeatureMeta {
+    type Output = Self;
+
+    fn visit_entry(
+        &mut self,
+        key: &tp::String<syn::MetaKey>,
+        value: Option<&syn::MetaValue>,
+        v_span: Span, // key span if value doesn't exist
+        errors: &mut Vec<ErrorReport>,
+    ) {
+        let key_str = key.to_ascii_lowercase();
+        match super::meta::__parse_fn::optional(&key_str, value, v_span) {
+            Ok(cir::MetaValue::Bool(x)) => self.features.push((key_str, x)),
+            Ok(mv) => errors.push(cir_error!(v_span, InvalidMetaValue(key_str, mv))),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    fn visit_end(self, _meta: &syn::Meta, _errors: &mut Vec<ErrorReport>) -> Self::Output {
+        self
+    }
+}
@@ -191,7 +191,12 @@ impl MetaParser for &mut ItemMeta {
                     }
                     match search::search_item_by_ident(&x) {
                         Some(item) => self.ingredients.push(item.actor),
-                        None => errors.push(cir_error!(v_span, InvalidItem(x))),
+                        None => {
+                            let suggestions = search::suggest_item_by_ident(&x);
+                            errors.push(
+                                cir_error!(v_span, InvalidItem(x)).with_suggestions(suggestions),
+                            );
+                        }
                     }
                 },
                 angled(x) => {
@@ -251,7 +256,10 @@ impl MetaParser for &mut ItemMeta {
             "category" => required {
                 words(x) => {
                     let Some(category) = cir::parse_category_from_str(&x) else {
-                        errors.push(cir_error!(v_span, InvalidCategoryName(x)));
+                        let suggestions = search::suggest_from(&x, cir::CATEGORY_NAMES.iter().copied());
+                        errors.push(
+                            cir_error!(v_span, InvalidCategoryName(x)).with_suggestions(suggestions),
+                        );
                         return;
                     };
                     match self.position.take() {
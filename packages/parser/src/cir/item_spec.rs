@@ -272,7 +272,10 @@ async fn parse_item_name<R: QuotedItemResolver>(
         syn::ItemName::Word(word) => {
             let result = search::search_item_by_ident(word);
             if result.is_none() {
-                errors.push(cir_error!(word, InvalidItem(word.to_string())));
+                errors.push(
+                    cir_error!(word, InvalidItem(word.to_string()))
+                        .with_suggestions(search::suggest_item_by_ident(word)),
+                );
             }
             result
         }
@@ -284,7 +287,10 @@ async fn parse_item_name<R: QuotedItemResolver>(
             }
             let result = resolver.resolve_quoted(name).await;
             if result.is_none() {
-                errors.push(cir_error!(quoted_word, InvalidItem(name.to_string())));
+                errors.push(
+                    cir_error!(quoted_word, InvalidItem(name.to_string()))
+                        .with_suggestions(search::suggest_item_by_ident(name)),
+                );
             }
             result
         }
@@ -297,6 +303,10 @@ async fn parse_item_name<R: QuotedItemResolver>(
                 Some(ResolvedItem::new(name.to_string()))
             }
         }
+        syn::ItemName::Param(param) => {
+            errors.push(cir_error!(param, UnboundParam(param.to_string())));
+            None
+        }
     }
 }
 
@@ -82,7 +82,15 @@ macro_rules! cir_match_meta_key_value {
                 Err(e) => $a_errors.push(e),
             }
         ),*
-        _ => $a_errors.push($crate::error::cir_warning!($a_key, UnusedMetaKey($l_key_str)))
+        _ => {
+            let suggestions = $crate::search::suggest_from(
+                &$l_key_str, [$( $( $i_key_lit ),* ),*]
+            );
+            $a_errors.push(
+                $crate::error::cir_warning!($a_key, UnusedMetaKey($l_key_str))
+                    .with_suggestions(suggestions)
+            )
+        }
         }
     };
 }
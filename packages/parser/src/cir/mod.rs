@@ -1,3 +1,9 @@
+mod block;
+pub use block::*;
+mod expr;
+pub use expr::*;
+mod condition;
+pub use condition::*;
 mod category;
 pub use category::*;
 mod command;
@@ -8,6 +14,10 @@ mod item_spec;
 pub use item_spec::*;
 mod trial;
 pub use trial::*;
+mod receive;
+pub use receive::*;
+mod duration;
+pub use duration::*;
 mod gdt;
 pub use gdt::*;
 mod meta;
@@ -15,5 +25,9 @@ pub use meta::*;
 mod enum_name;
 mod inventory_meta;
 pub use inventory_meta::*;
+mod feature_meta;
+pub use feature_meta::*;
+mod overworld_meta;
+pub use overworld_meta::*;
 
 pub use skybook_api::parser::cir::*;
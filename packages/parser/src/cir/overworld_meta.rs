@@ -0,0 +1,69 @@
+use teleparse::{Span, tp};
+
+use crate::cir;
+use crate::error::{ErrorReport, cir_error};
+use crate::syn;
+
+use super::MetaParser;
+
+/// Metadata for the `:ground-limit` annotation
+///
+/// Limits that are not specified are unchanged. When specified,
+/// a limit of `0` means no limit
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GroundLimitMeta {
+    pub materials: Option<usize>,
+    pub weapons: Option<usize>,
+    pub arrows: Option<usize>,
+}
+
+/// Parse the meta for `:ground-limit` annotation
+pub fn parse_ground_limit_meta(meta: &syn::Meta, errors: &mut Vec<ErrorReport>) -> GroundLimitMeta {
+    cir::parse_meta(meta, GroundLimitMeta::default(), errors)
+}
+
+impl MetaParser for GroundLimitMeta {
+    type Output = Self;
+
+    fn visit_entry(
+        &mut self,
+        key: &tp::String<syn::MetaKey>,
+        value: Option<&syn::MetaValue>,
+        v_span: Span,
+        errors: &mut Vec<ErrorReport>,
+    ) {
+        super::cir_match_meta_key_value! { (key, key_str, value, v_span, errors):
+            "material" | "materials" => required {
+                int(x) => {
+                    if x < 0 {
+                        errors.push(cir_error!(v_span, IntRange(x.to_string())));
+                        return;
+                    }
+                    self.materials = Some(x as usize);
+                }
+            },
+            "weapon" | "weapons" => required {
+                int(x) => {
+                    if x < 0 {
+                        errors.push(cir_error!(v_span, IntRange(x.to_string())));
+                        return;
+                    }
+                    self.weapons = Some(x as usize);
+                }
+            },
+            "arrow" | "arrows" => required {
+                int(x) => {
+                    if x < 0 {
+                        errors.push(cir_error!(v_span, IntRange(x.to_string())));
+                        return;
+                    }
+                    self.arrows = Some(x as usize);
+                }
+            },
+        }
+    }
+
+    fn visit_end(self, _meta: &syn::Meta, _errors: &mut Vec<ErrorReport>) -> Self::Output {
+        self
+    }
+}
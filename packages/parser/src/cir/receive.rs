@@ -0,0 +1,53 @@
+use teleparse::Span;
+
+use crate::error::{ErrorReport, cir_fail};
+
+/// Where the items in a `receive` command come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReceiveSource {
+    /// Opening a chest. The dialog is the item text box
+    Chest,
+    /// Talking to an NPC (including quest rewards). The dialog is the
+    /// talking dialog, followed by the item text box
+    Npc,
+}
+
+/// When the items in a `receive` command are added, relative to
+/// the dialog of the source closing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReceiveTiming {
+    /// The items are added while the dialog is still open
+    BeforeClose,
+    /// The items are added after the dialog closes
+    AfterClose,
+}
+
+impl ReceiveSource {
+    /// Get the timing used when the source does not specify one
+    pub fn default_timing(self) -> ReceiveTiming {
+        match self {
+            Self::Chest => ReceiveTiming::BeforeClose,
+            Self::Npc => ReceiveTiming::AfterClose,
+        }
+    }
+}
+
+/// Parse the source of `receive`, which is `chest` or `npc`, optionally
+/// followed by `-before` or `-after` to specify the timing
+pub fn parse_receive_source(
+    source: &str,
+    span: Span,
+) -> Result<(ReceiveSource, ReceiveTiming), ErrorReport> {
+    let lower = source.to_ascii_lowercase();
+    let (name, timing) = match lower.rsplit_once('-') {
+        Some((name, "before")) => (name, Some(ReceiveTiming::BeforeClose)),
+        Some((name, "after")) => (name, Some(ReceiveTiming::AfterClose)),
+        _ => (lower.as_str(), None),
+    };
+    let source = match name {
+        "chest" => ReceiveSource::Chest,
+        "npc" => ReceiveSource::Npc,
+        _ => cir_fail!(span, InvalidReceiveSource(source.to_string())),
+    };
+    Ok((source, timing.unwrap_or(source.default_timing())))
+}
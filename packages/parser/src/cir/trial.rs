@@ -1,6 +1,7 @@
 use teleparse::Span;
 
-use crate::error::{ErrorReport, cir_fail};
+use crate::error::{ErrorReport, cir_error};
+use crate::search;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Trial {
@@ -15,6 +16,20 @@ pub enum Trial {
     RefightWind,
 }
 
+/// Names of the trials that can be parsed, used for suggestions
+const TRIAL_NAMES: &[&str] = &[
+    "eventide",
+    "tots",
+    "trial-of-the-sword",
+    "beginning-trial",
+    "middle-trial",
+    "final-trial",
+    "thunderblight-refight",
+    "waterblight-refight",
+    "fireblight-refight",
+    "windblight-refight",
+];
+
 pub fn parse_trial(trial_name: &str, span: &Span) -> Result<Trial, ErrorReport> {
     let space_removed = trial_name.to_ascii_lowercase().replace('_', "-");
     match space_removed.as_str() {
@@ -27,6 +42,10 @@ pub fn parse_trial(trial_name: &str, span: &Span) -> Result<Trial, ErrorReport>
         "waterblight-refight" => Ok(Trial::RefightWater),
         "fireblight-refight" => Ok(Trial::RefightFire),
         "windblight-refight" => Ok(Trial::RefightWind),
-        _ => cir_fail!(span, InvalidTrial(trial_name.to_string())),
+        _ => {
+            let suggestions = search::suggest_from(&space_removed, TRIAL_NAMES.iter().copied());
+            Err(cir_error!(span, InvalidTrial(trial_name.to_string()))
+                .with_suggestions(suggestions))
+        }
     }
 }
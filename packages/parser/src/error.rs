@@ -4,6 +4,7 @@ use crate::syn;
 
 pub type Error = skybook_api::parser::error::ParserError;
 pub type ErrorReport = skybook_api::ErrorReport<Error>;
+pub type SyntaxExpectation = skybook_api::parser::error::SyntaxExpectation;
 pub trait IntoErrorReport {
     fn into_error_report(self) -> ErrorReport;
 }
@@ -0,0 +1,59 @@
+use std::future::Future;
+use std::path::PathBuf;
+
+/// A trait for loading scripts included with `include "PATH"`
+///
+/// The parser itself does not access any file system, so the caller
+/// must provide an implementation, for example, reading from the disk
+/// natively, or from a virtual file system in the browser.
+pub trait ScriptLoader {
+    type Future: Future<Output = Option<String>>;
+
+    /// Load the script at the path. The input does not contain the quotes.
+    ///
+    /// Return `None` if the script cannot be loaded
+    fn load_script(&self, path: &str) -> Self::Future;
+}
+
+impl<T: Fn(&str) -> F, F: Future<Output = Option<String>>> ScriptLoader for T {
+    type Future = F;
+
+    fn load_script(&self, path: &str) -> Self::Future {
+        self(path)
+    }
+}
+
+/// Loader that cannot load any script, so every `include` is an error
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoScriptLoader;
+
+impl ScriptLoader for NoScriptLoader {
+    type Future = std::future::Ready<Option<String>>;
+
+    fn load_script(&self, _: &str) -> Self::Future {
+        std::future::ready(None)
+    }
+}
+
+/// Loader that reads scripts from the disk
+///
+/// Paths are relative to the base directory, including
+/// paths in scripts that are included by other included scripts
+#[derive(Debug, Clone)]
+pub struct FsScriptLoader {
+    base: PathBuf,
+}
+
+impl FsScriptLoader {
+    pub fn new(base: impl Into<PathBuf>) -> Self {
+        Self { base: base.into() }
+    }
+}
+
+impl ScriptLoader for FsScriptLoader {
+    type Future = std::future::Ready<Option<String>>;
+
+    fn load_script(&self, path: &str) -> Self::Future {
+        std::future::ready(std::fs::read_to_string(self.base.join(path)).ok())
+    }
+}
@@ -6,17 +6,27 @@ pub mod syn;
 /// Item searcher
 pub mod search;
 
+/// Loading scripts for `include`
+pub mod include;
+
+/// Notes from `note` block literals
+pub mod notes;
+
 mod parse_output;
+mod recover;
 pub use parse_output::ParseOutput;
+pub use parse_output::StepDisplay;
 pub use parse_output::parse_script as parse;
+pub use parse_output::parse_script_with_loader as parse_with_loader;
 pub use parse_output::{parse_semantic, parse_tokens};
 
 mod semantic_token;
 pub use semantic_token::SemanticToken;
 
 mod error;
-pub use error::{Error, ErrorReport};
+pub use error::{Error, ErrorReport, SyntaxExpectation};
 mod util;
+pub use util::{get_armor_star, get_armor_with_star};
 
 /// Generated data
 mod generated {
@@ -0,0 +1,113 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::ParseOutput;
+use crate::cir;
+
+/// Consecutive steps that share the same note
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteSection {
+    /// The note in markdown
+    pub note: Arc<str>,
+    /// Range of the step indices the note is in effect for
+    pub steps: Range<usize>,
+}
+
+impl ParseOutput {
+    /// Get the note in effect for each step, in markdown
+    ///
+    /// The note is empty if the step does not have a note
+    pub fn step_notes(&self) -> Vec<Arc<str>> {
+        self.steps.iter().map(|x| Arc::clone(&x.notes)).collect()
+    }
+
+    /// Get the sections of steps that have notes
+    ///
+    /// Steps without notes are not in any section
+    pub fn note_sections(&self) -> Vec<NoteSection> {
+        let mut sections: Vec<NoteSection> = Vec::new();
+        for (i, step) in self.steps.iter().enumerate() {
+            if step.notes.is_empty() {
+                continue;
+            }
+            if let Some(last) = sections.last_mut() {
+                // steps with the same note share the same Arc
+                if last.steps.end == i && Arc::ptr_eq(&last.note, &step.notes) {
+                    last.steps.end = i + 1;
+                    continue;
+                }
+            }
+            sections.push(NoteSection {
+                note: Arc::clone(&step.notes),
+                steps: i..i + 1,
+            });
+        }
+        sections
+    }
+}
+
+/// Export the script as a markdown document, with the notes as text
+/// and the commands in code blocks
+///
+/// `script` must be the main script that is parsed into `output`
+pub fn export_markdown(script: &str, output: &ParseOutput) -> String {
+    let mut out = String::new();
+    let mut code = String::new();
+    let mut last_note: Option<&Arc<str>> = None;
+    let mut last_statement = None;
+    for step in &output.steps {
+        let note = (!step.notes.is_empty()).then_some(&step.notes);
+        let is_same_note = match (last_note, note) {
+            (None, None) => true,
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        };
+        if !is_same_note {
+            flush_code_block(&mut out, &mut code);
+            if let Some(note) = note {
+                out.push_str(note);
+                out.push_str("\n\n");
+            }
+            last_note = note;
+        }
+        // steps expanded from the same statement are exported once,
+        // and steps from an included script are exported once as the include
+        let span = step.span();
+        let statement = (step.pos(), (step.file() == 0).then_some((span.lo, span.hi)));
+        if last_statement == Some(statement) {
+            continue;
+        }
+        last_statement = Some(statement);
+        code.push_str(statement_source(script, step));
+        code.push('\n');
+    }
+    flush_code_block(&mut out, &mut code);
+    out
+}
+
+fn flush_code_block(out: &mut String, code: &mut String) {
+    if code.is_empty() {
+        return;
+    }
+    out.push_str("```skybook\n");
+    out.push_str(code);
+    out.push_str("```\n\n");
+    code.clear();
+}
+
+/// Get the source of the statement of the step in the main script
+///
+/// For steps in included scripts, this is the line of the `include`
+fn statement_source<'a>(script: &'a str, step: &cir::Step) -> &'a str {
+    if step.file() == 0 {
+        let span = step.span();
+        return script[span.lo..span.hi].trim();
+    }
+    let pos = step.pos();
+    let start = script[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let end = script[pos..]
+        .find('\n')
+        .map(|i| pos + i)
+        .unwrap_or(script.len());
+    script[start..end].trim()
+}
@@ -1,10 +1,13 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use teleparse::{Parser, Span, ToSpan};
 
 use crate::SemanticToken;
 use crate::cir;
-use crate::error::{ErrorReport, IntoErrorReport, cir_error};
+use crate::error::{ErrorReport, cir_error};
+use crate::include::{NoScriptLoader, ScriptLoader};
+use crate::recover::Statements;
 use crate::search::QuotedItemResolver;
 use crate::syn;
 
@@ -20,21 +23,35 @@ pub struct ParseOutput {
     /// Length of the script
     pub script_len: usize,
 
-    /// [WIP] Indices of the first command in each page
+    /// Indices of the first step in each page
     ///
+    /// A new page starts at a `page` block literal in the main script.
     /// If steps is empty, this is also empty. Otherwise, the first element
     /// is 0, and the number of elements equals the number of pages
     pub pages: Vec<usize>,
 
-    /// [WIP] List of steps to display in the step list, with the index
+    /// List of steps to display in the step list, with the index
     /// of the actual step in the `steps` vec
     ///
     /// The indices may not be continuous because one display step
-    /// can include multiple actual steps
+    /// can include multiple actual steps. For example, all the commands
+    /// on the same line are displayed as one step. For [`StepDisplay::Text`],
+    /// the index is the step after the text.
     pub display: Vec<(StepDisplay, usize)>,
 
     /// Errors encountered during parsing
     pub errors: Vec<ErrorReport>,
+
+    /// Labels defined with `label NAME`, with the number of steps before the label
+    ///
+    /// Use [`step_idx_from_label`](Self::step_idx_from_label) to get the step
+    pub labels: BTreeMap<String, usize>,
+
+    /// Paths of the scripts included with `include`
+    ///
+    /// File id `i + 1` in the steps and errors is the script at `files[i]`,
+    /// and file id 0 is the main script
+    pub files: Vec<String>,
 }
 
 impl ParseOutput {
@@ -70,117 +87,393 @@ impl ParseOutput {
         x
     }
     /// Get the step index by the byte pos in the script
+    ///
+    /// If multiple steps are expanded from the same statement,
+    /// the last one is returned
     pub fn step_idx_from_pos(&self, pos: usize) -> Option<usize> {
-        let i = match self.steps.binary_search_by_key(&pos, |x| x.pos()) {
-            Ok(i) => i,
-            Err(i) => i.saturating_sub(1),
-        };
+        let i = self
+            .steps
+            .partition_point(|x| x.pos() <= pos)
+            .saturating_sub(1);
         if i < self.steps.len() { Some(i) } else { None }
     }
+
+    /// Get the step index by the name of a label
+    ///
+    /// The step is the last one before the label, same as [`step_idx_from_pos`](Self::step_idx_from_pos)
+    /// with a position between the steps before and after the label.
+    /// `None` is returned if the label is not defined, or if there are no steps
+    /// before the label, in which case the state at the label is the initial state
+    pub fn step_idx_from_label(&self, label: &str) -> Option<usize> {
+        let count = *self.labels.get(label)?;
+        count.checked_sub(1)
+    }
 }
 
 /// Type of step to display in the step list
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StepDisplay {
-    /// Display raw text
+    /// Display raw text, from `note` and `page` block literals
     Text(String),
-    /// Display a command (maps to one actual step)
+    /// Display a command (maps to the actual steps until the next display step)
     Command,
 }
 
 /// Parse the script and get the simulation steps and errors
+///
+/// Every `include` is an error. Use [`parse_script_with_loader`]
+/// to parse scripts that include other scripts
 pub async fn parse_script<R: QuotedItemResolver>(resolver: &R, script: &str) -> ParseOutput {
-    let full_span = Span::new(0, script.len());
+    parse_script_with_loader(resolver, &NoScriptLoader, script).await
+}
+
+/// Parse the script and get the simulation steps and errors,
+/// loading scripts included with `include` using the loader
+///
+/// Steps from an included script are placed where the `include` is,
+/// and have the position of the `include` in the main script
+pub async fn parse_script_with_loader<R: QuotedItemResolver, L: ScriptLoader>(
+    resolver: &R,
+    loader: &L,
+    script: &str,
+) -> ParseOutput {
     let mut output = ParseOutput {
         script_len: script.len(),
         ..Default::default()
     };
-    let mut parser = match Parser::new(script) {
-        Err(e) => {
-            let errors = &mut output.errors;
-            errors.push(cir_error!(full_span, Unexpected(e.to_string())));
-            return output;
+
+    // the main script is file 0
+    let main = SourceFile::parse(String::new(), script.to_string(), &mut output.errors);
+    let mut files = vec![main];
+    load_includes(&mut files, loader, &mut output.errors).await;
+    output.files = files.iter().skip(1).map(|f| f.path.clone()).collect();
+
+    // blocks defined in any script can be called
+    let mut blocks = cir::BlockDefs::default();
+    for (id, file) in files.iter().enumerate() {
+        let mut errors = Vec::new();
+        blocks.collect(file.stmts.iter(), &file.source, &mut errors);
+        output
+            .errors
+            .extend(errors.into_iter().map(|e| e.in_file(id as u32)));
+    }
+
+    // parse each command. Blocks are expanded into multiple steps,
+    // which all have the span of the statement. Included scripts are
+    // walked in place of the include
+    //
+    // (file id, statement index, span of the include in the main script, report errors)
+    let mut stack = vec![(0u32, 0usize, None, true)];
+    // variables are defined in the order the statements are walked
+    let mut vars = cir::Variables::default();
+    // errors in a script included multiple times are only reported once
+    let mut walked = vec![false; files.len()];
+    walked[0] = true;
+    while let Some((id, idx, include_span, report)) = stack.pop() {
+        let file = &files[id as usize];
+        let Some(stmt) = file.stmts.get(idx) else {
+            continue;
+        };
+        stack.push((id, idx + 1, include_span, report));
+        let span = stmt.span();
+        match &stmt.cmd {
+            syn::Command::Include(_) => {
+                if let Some(&included) = file.includes.get(&idx) {
+                    let first = !walked[included as usize];
+                    walked[included as usize] = true;
+                    stack.push((included, 0, Some(include_span.unwrap_or(span)), first));
+                }
+                continue;
+            }
+            syn::Command::Label(label) => {
+                let name = label.name.to_string();
+                if output.labels.contains_key(&name) {
+                    if report {
+                        let error = cir_error!(&label.name, DuplicateLabel(name)).in_file(id);
+                        output.errors.push(error);
+                    }
+                } else {
+                    output.labels.insert(name, output.steps.len());
+                }
+                continue;
+            }
+            syn::Command::Let(let_cmd) => {
+                match vars.eval_expr(&let_cmd.value, &file.source, 0) {
+                    Ok(value) => vars.set(let_cmd.name.to_string(), value),
+                    Err(e) => {
+                        if report {
+                            output.errors.push(e.in_file(id));
+                        }
+                    }
+                }
+                continue;
+            }
+            _ => {}
         }
-        Ok(p) => p,
-    };
-    let parsed_script = match parser.parse::<syn::Script>() {
-        Err(e) => {
-            let errors = &mut output.errors;
-            errors.push(cir_error!(full_span, Unexpected(e.to_string())));
-            return output;
+        let mut errors = Vec::new();
+        let commands = cir::expand_statement(
+            &stmt.cmd,
+            &file.source,
+            &blocks,
+            &vars,
+            resolver,
+            &mut errors,
+        )
+        .await;
+        if report {
+            output
+                .errors
+                .extend(errors.into_iter().map(|e| e.in_file(id)));
+        }
+        if commands.is_empty() {
+            continue;
+        }
+        let note = find_note(&file.notes, &file.source, span.lo);
+        for expanded in commands {
+            let idx = output.steps.len();
+            let branch = expanded.branch.map(|(distance, branch)| cir::StepBranch {
+                step: idx - distance,
+                branch,
+            });
+            let mut step = cir::Step::new(span, expanded.command, Arc::clone(&note))
+                .expanded(expanded.expanded)
+                .in_branch(branch);
+            if let Some(include_span) = include_span {
+                step = step.in_file(id, include_span);
+            }
+            output.steps.push(step);
         }
-        Ok(pt) => pt,
-    };
-
-    // extract syntax errors
-    for error in std::mem::take(&mut parser.info_mut().errors) {
-        output.errors.push(error.into_error_report());
     }
 
-    let Some(parsed_script) = parsed_script else {
-        return output;
-    };
+    build_display(&mut output, &files[0]);
 
-    // parse all the block literals and get their positions
-    let mut notes = Vec::new();
-    for token in &parser.info().extracted_tokens {
-        if token.ty != syn::TT::BlockLiteral {
-            continue;
+    // errors in included scripts are not visible in the main script,
+    // so they are also reported at the include
+    let mut has_errors = vec![false; files.len()];
+    for error in &output.errors {
+        if !error.is_warning {
+            has_errors[error.file as usize] = true;
         }
-        match syn::parse_block_literal_with_tag(token.src(script), "note") {
-            Some(note) => {
-                notes.push((token.span, Some(Arc::<str>::from(note))));
+    }
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (id, file) in files.iter().enumerate() {
+            if has_errors[id] {
+                continue;
             }
-            None => {
-                notes.push((token.span, None));
+            if file.includes.values().any(|x| has_errors[*x as usize]) {
+                has_errors[id] = true;
+                changed = true;
             }
+        }
+    }
+    for (id, file) in files.iter().enumerate() {
+        for (idx, included) in &file.includes {
+            if !has_errors[*included as usize] {
+                continue;
+            }
+            let Some(stmt) = file.stmts.get(*idx) else {
+                continue;
+            };
+            let path = files[*included as usize].path.clone();
+            output
+                .errors
+                .push(cir_error!(stmt.span(), IncludeHasErrors(path)).in_file(id as u32));
+        }
+    }
+
+    output
+}
+
+/// A script being parsed, either the main script or an included script
+struct SourceFile {
+    /// Path used to include the script, empty for the main script
+    path: String,
+    /// Source of the script, with the statements that have syntax errors
+    /// replaced by spaces
+    source: String,
+    stmts: Statements,
+    /// Spans of the block literals, with the content if it's a note
+    notes: Vec<(Span, Option<Arc<str>>)>,
+    /// Spans of the `page` block literals, with the title of the page
+    pages: Vec<(Span, String)>,
+    /// Index of each `include` statement that is loaded, to the id of the included script
+    includes: BTreeMap<usize, u32>,
+}
+
+impl SourceFile {
+    fn parse(path: String, source: String, errors: &mut Vec<ErrorReport>) -> Self {
+        let mut file = Self {
+            path,
+            source,
+            stmts: Statements::default(),
+            notes: Vec::new(),
+            pages: Vec::new(),
+            includes: BTreeMap::new(),
         };
+        // statements with syntax errors are skipped, and the rest of the
+        // script is still parsed
+        let recovered = crate::recover::parse_script(&file.source, errors);
+
+        // parse all the block literals and get their positions
+        let mut notes = Vec::new();
+        let mut pages = Vec::new();
+        for (span, ty) in &recovered.extracted_tokens {
+            if *ty != syn::TT::BlockLiteral {
+                continue;
+            }
+            let src = &recovered.source[span.lo..span.hi];
+            if let Some(title) = syn::parse_block_literal_with_tag(src, "page") {
+                pages.push((*span, title.trim().to_string()));
+            }
+            match syn::parse_block_literal_with_tag(src, "note") {
+                Some(note) => {
+                    notes.push((*span, Some(Arc::<str>::from(note))));
+                }
+                None => {
+                    notes.push((*span, None));
+                }
+            };
+        }
+
+        file.source = recovered.source;
+        file.stmts = recovered.stmts;
+        file.notes = notes;
+        file.pages = pages;
+        file
     }
+}
 
-    // parse each command
-    for stmt in parsed_script.stmts.iter() {
-        let span = stmt.span();
-        let pos = span.lo;
-        let Some(command) = cir::parse_command(&stmt.cmd, resolver, &mut output.errors).await
-        else {
+/// Load the scripts included by the main script (file 0), and the scripts
+/// included by those scripts, and so on
+///
+/// Each path is only loaded once. Including a script that is still
+/// being included (directly or indirectly) is an error
+async fn load_includes<L: ScriptLoader>(
+    files: &mut Vec<SourceFile>,
+    loader: &L,
+    errors: &mut Vec<ErrorReport>,
+) {
+    // scripts currently being included, with the next statement to check
+    let mut stack = vec![(0u32, 0usize)];
+    while let Some((id, idx)) = stack.pop() {
+        let (path, span) = {
+            let file = &files[id as usize];
+            let Some(stmt) = file.stmts.get(idx) else {
+                continue;
+            };
+            stack.push((id, idx + 1));
+            let syn::Command::Include(include) = &stmt.cmd else {
+                continue;
+            };
+            (include.path.trim_matches('"').to_string(), stmt.span())
+        };
+        // the main script does not have a path, so it can't be in a cycle
+        let is_cycle = stack
+            .iter()
+            .any(|(x, _)| *x != 0 && files[*x as usize].path == path);
+        if is_cycle {
+            errors.push(cir_error!(span, IncludeCycle(path)).in_file(id));
+            continue;
+        }
+        if let Some(loaded) = files.iter().skip(1).position(|x| x.path == path) {
+            files[id as usize].includes.insert(idx, loaded as u32 + 1);
+            continue;
+        }
+        let Some(source) = loader.load_script(&path).await else {
+            errors.push(cir_error!(span, IncludeNotFound(path)).in_file(id));
             continue;
         };
-        // find the notes associated with this command,
-        // which is the closest note block before the command,
-        // but not across an empty line or a non-note block literal
-        let note = match notes.binary_search_by_key(&pos, |x| x.0.lo) {
-            Ok(i) => Some(&notes[i]),
-            Err(i) => {
-                if i == 0 {
-                    None
-                } else {
-                    Some(&notes[i - 1])
-                }
+        let new_id = files.len() as u32;
+        let mut file_errors = Vec::new();
+        let file = SourceFile::parse(path, source, &mut file_errors);
+        errors.extend(file_errors.into_iter().map(|e| e.in_file(new_id)));
+        files.push(file);
+        files[id as usize].includes.insert(idx, new_id);
+        stack.push((new_id, 0));
+    }
+}
+
+/// Split the steps into pages and build the step list to display,
+/// using the `note` and `page` block literals in the main script
+fn build_display(output: &mut ParseOutput, main: &SourceFile) {
+    if output.steps.is_empty() {
+        return;
+    }
+    // text to display before the steps, by position in the script.
+    // `true` for page breaks
+    let mut texts = Vec::new();
+    for (span, note) in &main.notes {
+        if let Some(note) = note {
+            texts.push((span.lo, false, note.to_string()));
+        }
+    }
+    for (span, title) in &main.pages {
+        texts.push((span.lo, true, title.clone()));
+    }
+    texts.sort_by_key(|x| x.0);
+    let mut texts = texts.into_iter().peekable();
+
+    // start of each line, to find the line of the steps
+    let line_starts = std::iter::once(0)
+        .chain(main.source.match_indices('\n').map(|(i, _)| i + 1))
+        .collect::<Vec<_>>();
+    let mut last_line = None;
+
+    output.pages.push(0);
+    for (i, step) in output.steps.iter().enumerate() {
+        let pos = step.pos();
+        while let Some((_, is_page, text)) = texts.next_if(|x| x.0 < pos) {
+            if is_page && output.pages.last() != Some(&i) {
+                output.pages.push(i);
             }
-        };
-        let note = match note {
-            None => Arc::from(""),
-            Some((_, None)) => Arc::from(""),
-            Some((note_span, Some(note))) => {
-                // check if an empty line exists between the notes and the command
-                if note_span.hi < pos {
-                    // trim_start since space between the note and the command is allowed
-                    let text_between = script[note_span.hi..pos].trim_start();
-                    if text_between.contains("\n\n") || text_between.contains("\n\r\n") {
-                        Arc::from("")
-                    } else {
-                        Arc::clone(note)
-                    }
+            if !text.is_empty() {
+                output.display.push((StepDisplay::Text(text), i));
+            }
+            // commands after the text are not grouped with the ones before it
+            last_line = None;
+        }
+        let line = line_starts.partition_point(|x| *x <= pos);
+        if last_line != Some(line) {
+            output.display.push((StepDisplay::Command, i));
+            last_line = Some(line);
+        }
+    }
+}
+
+/// Find the notes associated with the command at pos,
+/// which is the closest note block before the command,
+/// but not across an empty line or a non-note block literal
+fn find_note(notes: &[(Span, Option<Arc<str>>)], source: &str, pos: usize) -> Arc<str> {
+    let note = match notes.binary_search_by_key(&pos, |x| x.0.lo) {
+        Ok(i) => Some(&notes[i]),
+        Err(i) => {
+            if i == 0 {
+                None
+            } else {
+                Some(&notes[i - 1])
+            }
+        }
+    };
+    match note {
+        None => Arc::from(""),
+        Some((_, None)) => Arc::from(""),
+        Some((note_span, Some(note))) => {
+            // check if an empty line exists between the notes and the command
+            if note_span.hi < pos {
+                // trim_start since space between the note and the command is allowed
+                let text_between = source[note_span.hi..pos].trim_start();
+                if text_between.contains("\n\n") || text_between.contains("\n\r\n") {
+                    Arc::from("")
                 } else {
                     Arc::clone(note)
                 }
+            } else {
+                Arc::clone(note)
             }
-        };
-        let step = cir::Step::new(span, command, note);
-        output.steps.push(step);
+        }
     }
-
-    output
 }
 
 /// Parse the script and extract the semantic tokens in the given range
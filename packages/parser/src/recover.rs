@@ -0,0 +1,352 @@
+//! Recovering from syntax errors by skipping the statement with the error
+//!
+//! The parser stops producing statements at the first syntax error it cannot
+//! get past. To keep the rest of the script, the statement with the error is replaced
+//! with spaces and the rest of the script is parsed again, starting after the last
+//! statement that was parsed. The statements before that are kept from the previous
+//! attempt. The source parsed again has the same length, with everything before
+//! the start also replaced with spaces, so the spans in the output still point to
+//! the original source.
+
+use teleparse::{Parser, Span, ToSpan};
+
+use crate::error::{Error, ErrorReport, IntoErrorReport, SyntaxExpectation, cir_error};
+use crate::syn;
+
+/// Max number of statements to skip in one script. Errors after
+/// that are reported as-is.
+///
+/// Each skip parses the rest of the script after the last parsed statement
+const MAX_SKIPPED_STATEMENTS: usize = 1024;
+
+/// Output of [`parse_script`]
+pub struct Recovered {
+    /// The parsed statements, which do not have the skipped statements
+    pub stmts: Statements,
+    /// The source with the skipped statements replaced by spaces
+    pub source: String,
+    /// Tokens not in the syntax tree, such as comments and block literals
+    pub extracted_tokens: Vec<(Span, syn::TT)>,
+}
+
+/// Statements parsed from a script, in the order they are in the script
+///
+/// Each attempt of parsing the script adds a part, with the number of
+/// statements in the part that are before the next skipped statement
+#[derive(Default)]
+pub struct Statements {
+    parts: Vec<(syn::Script, usize)>,
+}
+
+impl Statements {
+    /// Get the statement at the index
+    pub fn get(&self, mut idx: usize) -> Option<&syn::Statement> {
+        for (script, len) in &self.parts {
+            if idx < *len {
+                return script.stmts.get(idx);
+            }
+            idx -= len;
+        }
+        None
+    }
+
+    /// Iterate over the statements
+    pub fn iter(&self) -> impl Iterator<Item = &syn::Statement> {
+        self.parts
+            .iter()
+            .flat_map(|(script, len)| script.stmts.iter().take(*len))
+    }
+
+    /// Add the statements of an attempt that end before `end`
+    fn push(&mut self, script: Option<syn::Script>, end: usize) {
+        let Some(script) = script else {
+            return;
+        };
+        let len = script
+            .stmts
+            .iter()
+            .take_while(|stmt| stmt.span().hi <= end)
+            .count();
+        self.parts.push((script, len));
+    }
+}
+
+/// Result of parsing the source once
+struct Attempt {
+    /// Position where the parsing starts. The source before
+    /// it is replaced with spaces
+    start: usize,
+    script: Option<syn::Script>,
+    errors: Vec<teleparse::syntax::Error<syn::TT>>,
+    tokens: Vec<(Span, syn::TT)>,
+    extracted_tokens: Vec<(Span, syn::TT)>,
+}
+
+impl Attempt {
+    /// Parse the source, starting at `start`
+    fn parse(source: &str, start: usize) -> Result<Self, String> {
+        let blanked;
+        let input = if start == 0 {
+            source
+        } else {
+            blanked = blank_region(source, 0, start);
+            blanked.as_str()
+        };
+        let mut parser = Parser::new(input).map_err(|e| e.to_string())?;
+        let script = parser.parse::<syn::Script>().map_err(|e| e.to_string())?;
+        let mut errors = std::mem::take(&mut parser.info_mut().errors);
+        errors.sort_by_key(|e| e.span().lo);
+        let info = parser.info();
+        let tokens = info.tokens.iter().map(|t| (t.span, t.ty)).collect();
+        let extracted_tokens = info
+            .extracted_tokens
+            .iter()
+            .map(|t| (t.span, t.ty))
+            .collect();
+        Ok(Self {
+            start,
+            script,
+            errors,
+            tokens,
+            extracted_tokens,
+        })
+    }
+
+    /// Position of the first syntax error
+    fn first_error_pos(&self) -> Option<usize> {
+        self.errors.first().map(|e| e.span().lo)
+    }
+
+    /// Get the end of the last statement that ends before `pos`,
+    /// or the start of the attempt if there is none
+    fn end_of_stmts_before(&self, pos: usize) -> usize {
+        let Some(script) = &self.script else {
+            return self.start;
+        };
+        script
+            .stmts
+            .iter()
+            .map(|stmt| stmt.span().hi)
+            .take_while(|hi| *hi <= pos)
+            .last()
+            .unwrap_or(self.start)
+    }
+}
+
+/// Parse the script, skipping the statements with syntax errors
+///
+/// Only the first error of each skipped statement is reported, since
+/// the others are usually caused by the first one
+pub fn parse_script(source: &str, errors: &mut Vec<ErrorReport>) -> Recovered {
+    let mut source = source.to_string();
+    let mut stmts = Statements::default();
+    let mut extracted_tokens = Vec::new();
+    let mut attempt = match Attempt::parse(&source, 0) {
+        Ok(x) => x,
+        Err(e) => {
+            errors.push(cir_error!(Span::new(0, source.len()), Unexpected(e)));
+            return Recovered {
+                stmts,
+                source,
+                extracted_tokens,
+            };
+        }
+    };
+    for _ in 0..MAX_SKIPPED_STATEMENTS {
+        let Some(pos) = attempt.first_error_pos() else {
+            break;
+        };
+        let Some((next_source, next_attempt)) = skip_statement(&source, &attempt, pos) else {
+            break;
+        };
+        let error = attempt.errors.remove(0);
+        errors.push(error_report(error, &source, &attempt.tokens));
+        // keep what is before the start of the next attempt
+        let end = next_attempt.start;
+        extracted_tokens.extend(
+            attempt
+                .extracted_tokens
+                .iter()
+                .filter(|(span, _)| span.lo < end)
+                .copied(),
+        );
+        stmts.push(attempt.script, end);
+        source = next_source;
+        attempt = next_attempt;
+    }
+    for error in attempt.errors {
+        errors.push(error_report(error, &source, &attempt.tokens));
+    }
+    extracted_tokens.extend(attempt.extracted_tokens);
+    stmts.push(attempt.script, source.len());
+
+    Recovered {
+        stmts,
+        source,
+        extracted_tokens,
+    }
+}
+
+/// Replace the statement with the error at `pos` with spaces and parse
+/// again, starting after the last statement before it
+///
+/// If the error is at the start of a statement (or the end of input), the
+/// previous statement is probably incomplete, so that is tried first.
+/// Otherwise, the rest of the line (up to a new line or `;`) is skipped.
+/// The attempt is only taken if the parser gets past `pos`.
+fn skip_statement(source: &str, attempt: &Attempt, pos: usize) -> Option<(String, Attempt)> {
+    let mut regions = Vec::with_capacity(2);
+    let prev_start = attempt
+        .tokens
+        .iter()
+        .rev()
+        .find(|(span, ty)| span.lo < pos && is_statement_start(source, *span, *ty));
+    if let Some((prev_span, _)) = prev_start
+        && is_at_statement_boundary(source, &attempt.tokens, pos)
+    {
+        regions.push((prev_span.lo, pos));
+    }
+    let (lo, hi) = line_region(source, &attempt.extracted_tokens, pos);
+    // statements kept from the previous attempts are not skipped
+    regions.push((lo.max(attempt.start), hi));
+
+    for (lo, hi) in regions {
+        if source[lo..hi].trim().is_empty() {
+            continue;
+        }
+        let blanked = blank_region(source, lo, hi);
+        let start = attempt.end_of_stmts_before(lo);
+        let Ok(next) = Attempt::parse(&blanked, start) else {
+            continue;
+        };
+        if next.first_error_pos().is_none_or(|next_pos| next_pos > pos) {
+            return Some((blanked, next));
+        }
+    }
+    None
+}
+
+/// Check if the token can start a statement. Annotations
+/// are only statements when they come after `:`
+fn is_statement_start(source: &str, span: Span, ty: syn::TT) -> bool {
+    match ty {
+        syn::TT::Command | syn::TT::SuperCommand => true,
+        syn::TT::Annotation => source[..span.lo].ends_with(':'),
+        _ => false,
+    }
+}
+
+/// Check if `pos` is the end of input, a token that starts a statement,
+/// or the first token on its line
+fn is_at_statement_boundary(source: &str, tokens: &[(Span, syn::TT)], pos: usize) -> bool {
+    let Some((span, ty)) = tokens.iter().find(|(span, _)| span.lo == pos) else {
+        return true;
+    };
+    if is_statement_start(source, *span, *ty) {
+        return true;
+    }
+    let line_start = source[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    source[line_start..pos].trim().is_empty()
+}
+
+/// Get the region from the start of the statement at `pos` to the next new line or `;`,
+/// including the new line or `;`
+///
+/// The region stops before comments and block literals, since
+/// they can span multiple lines
+fn line_region(source: &str, extracted_tokens: &[(Span, syn::TT)], pos: usize) -> (usize, usize) {
+    let lo = source[..pos].rfind(['\n', ';']).map(|i| i + 1).unwrap_or(0);
+    let mut hi = source.len();
+    let mut in_quote = false;
+    for (i, c) in source[pos..].char_indices() {
+        match c {
+            '"' => in_quote = !in_quote,
+            '\n' | ';' if !in_quote => {
+                // the `;` of the skipped statement is also skipped,
+                // so it doesn't become an empty statement
+                hi = pos + i + 1;
+                break;
+            }
+            _ => {}
+        }
+    }
+    if let Some((span, _)) = extracted_tokens
+        .iter()
+        .find(|(span, _)| span.lo >= pos && span.lo < hi)
+    {
+        hi = span.lo;
+    }
+    (lo, hi)
+}
+
+/// Replace the region with spaces, keeping the new lines and the byte length
+fn blank_region(source: &str, lo: usize, hi: usize) -> String {
+    let mut out = String::with_capacity(source.len());
+    out.push_str(&source[..lo]);
+    for c in source[lo..hi].chars() {
+        if c == '\n' {
+            out.push('\n');
+        } else {
+            out.extend(std::iter::repeat_n(' ', c.len_utf8()));
+        }
+    }
+    out.push_str(&source[hi..]);
+    out
+}
+
+/// Convert the syntax error to a report. If the error is right after
+/// a command that needs an argument, the report says what was expected
+fn error_report(
+    error: teleparse::syntax::Error<syn::TT>,
+    source: &str,
+    tokens: &[(Span, syn::TT)],
+) -> ErrorReport {
+    let span = error.span();
+    let mut report = error.into_error_report();
+    if !matches!(
+        report.error,
+        Error::SyntaxUnexpected | Error::SyntaxUnexpectedEof
+    ) {
+        return report;
+    }
+    let Some((prev_span, prev_ty)) = tokens.iter().rev().find(|(s, _)| s.hi <= span.lo) else {
+        return report;
+    };
+    if !is_statement_start(source, *prev_span, *prev_ty) {
+        return report;
+    }
+    let mut command = source[prev_span.lo..prev_span.hi].to_string();
+    if *prev_ty == syn::TT::Annotation {
+        command.insert(0, ':');
+    }
+    if let Some(expected) = expected_after(&command) {
+        report.error = Error::SyntaxExpected(expected, command);
+    }
+    report
+}
+
+/// Get what is expected after a command keyword. Commands
+/// with optional arguments are not included
+///
+/// When updating syntax, keep this in sync with the keywords in syn::token
+/// and the arguments of the commands in syn::command. The `parse_recovery_expected_after`
+/// test fails if a keyword that needs an argument is missing
+fn expected_after(command: &str) -> Option<SyntaxExpectation> {
+    use SyntaxExpectation as E;
+    let expected = match command {
+        "get" | "pick-up" | "receive" | "hold" | "dnp" | "eat" | "equip" | "unequip"
+        | "display" | "buy" | "sell" | "roast" | "bake" | "boil" | "freeze" | "!remove" => {
+            E::ItemList
+        }
+        "entangle" | "upgrade" | "use" | ":targeting" | "!swap" => E::Item,
+        "throw" | "expand" | "sort" => E::Category,
+        "wait" | "repeat" | ":per-use" | ":dlc" | "!break" => E::Number,
+        "save-as" | "block" | "call" | "label" | "let" | "enter" => E::Name,
+        "include" => E::Path,
+        "if" => E::Condition,
+        "!write" | ":discovered" | ":features" | ":ground-limit" => E::Properties,
+        "!set-gdt" => E::FlagName,
+        _ => return None,
+    };
+    Some(expected)
+}
@@ -0,0 +1,69 @@
+/// How far from the start of the text a match can be before the score
+/// becomes 1 (same as the default `distance` option of Fuse.js)
+const MATCH_DISTANCE: f64 = 100.0;
+
+/// Compute how well `pattern` matches anywhere in `text`, ignoring case.
+///
+/// This mimics the scoring of Fuse.js used by the localized search in the app,
+/// so the same query gives the same result natively and in the browser.
+/// Both inputs should already be lowercased.
+///
+/// The score is between 0 (exact match at the start) and 1 (worst). `None` is returned
+/// if the best score is above the threshold.
+pub fn fuzzy_score(pattern: &[char], text: &[char], threshold: f64) -> Option<f64> {
+    let m = pattern.len();
+    let n = text.len();
+    if m == 0 {
+        return None;
+    }
+
+    // approximate substring matching (Sellers), tracking
+    // where the match that ends at each position starts
+    let mut prev_errors: Vec<usize> = vec![0; n + 1];
+    let mut prev_start: Vec<usize> = (0..=n).collect();
+    let mut errors: Vec<usize> = vec![0; n + 1];
+    let mut start: Vec<usize> = vec![0; n + 1];
+    for (i, p) in pattern.iter().enumerate() {
+        errors[0] = i + 1;
+        start[0] = 0;
+        for j in 1..=n {
+            let substitute = prev_errors[j - 1] + usize::from(*p != text[j - 1]);
+            let skip_pattern = prev_errors[j] + 1;
+            let skip_text = errors[j - 1] + 1;
+            if substitute <= skip_pattern && substitute <= skip_text {
+                errors[j] = substitute;
+                start[j] = prev_start[j - 1];
+            } else if skip_pattern <= skip_text {
+                errors[j] = skip_pattern;
+                start[j] = prev_start[j];
+            } else {
+                errors[j] = skip_text;
+                start[j] = start[j - 1];
+            }
+        }
+        std::mem::swap(&mut prev_errors, &mut errors);
+        std::mem::swap(&mut prev_start, &mut start);
+    }
+
+    let mut best: Option<f64> = None;
+    for j in 0..=n {
+        let accuracy = prev_errors[j] as f64 / m as f64;
+        let proximity = prev_start[j] as f64 / MATCH_DISTANCE;
+        let score = accuracy + proximity;
+        if score > threshold {
+            continue;
+        }
+        if best.is_none_or(|b| score < b) {
+            best = Some(score);
+        }
+    }
+
+    // longer names are penalized, same as the field-length norm in Fuse.js
+    let word_count = text
+        .split(|c: &char| c.is_whitespace())
+        .filter(|w| !w.is_empty())
+        .count()
+        .max(1);
+    let norm = (1000.0 / (word_count as f64).sqrt()).round() / 1000.0;
+    best.map(|score| score.max(f64::EPSILON).powf(norm))
+}
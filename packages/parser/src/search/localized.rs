@@ -0,0 +1,387 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::util;
+
+use super::{QuotedItemResolver, ResolvedItem, fuzzy_score};
+
+/// Cook effects that can be part of a localized cooked item name,
+/// and their CookEffectId values
+const COOK_EFFECTS: &[(&str, i32)] = &[
+    ("LifeMaxUp", 2),
+    ("ResistHot", 4),
+    ("ResistCold", 5),
+    ("ResistElectric", 6),
+    ("AttackUp", 10),
+    ("DefenseUp", 11),
+    ("Quietness", 12),
+    ("AllSpeed", 13),
+    ("GutsRecover", 14),
+    ("ExGutsMaxUp", 15),
+    ("Fireproof", 16),
+];
+
+/// Placeholders in actor names that are replaced by the cook effect name
+const EFFECT_PLACEHOLDERS: &[&str] = &[
+    "effect",
+    "effect_feminine",
+    "effect_masculine",
+    "effect_neuter",
+    "effect_plural",
+];
+
+/// Characters that can be put after an armor name to specify the number of upgrades
+const STAR_CHARS: &[char] = &['★', '☆', '*'];
+
+/// Native implementation of localized item search for quoted items like `"Royal Claymore"`
+///
+/// This loads the localized item names generated by the localization package
+/// (`localization/src/generated/item-search.json`) and does the same fuzzy search
+/// as the app, so scripts resolve to the same items natively and in the browser.
+///
+/// Like the app, the query can be prefixed with a language tag (e.g. `de:Apfel`)
+/// to only search in one language.
+#[derive(Debug)]
+pub struct LocalizedItemResolver {
+    /// Searchers, one per language tag (e.g. `en`, `zh`)
+    searchers: BTreeMap<String, LocalizedSearcher>,
+}
+
+/// Error when loading the localized search data
+#[derive(Debug, thiserror::Error)]
+pub enum LocalizedSearchLoadError {
+    #[error("failed to read localized search data: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to parse localized search data: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("localized search data has no languages")]
+    Empty,
+}
+
+impl LocalizedItemResolver {
+    /// Load the resolver from the generated data file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LocalizedSearchLoadError> {
+        let data = std::fs::read_to_string(path)?;
+        Self::from_json(&data)
+    }
+
+    /// Create the resolver from the content of the generated data file
+    ///
+    /// The data is a map from locale (e.g. `en-US`) to translation entries
+    /// (e.g. `actor.Item_Fruit_A.name` to `Apple`)
+    pub fn from_json(data: &str) -> Result<Self, LocalizedSearchLoadError> {
+        let data: BTreeMap<String, BTreeMap<String, String>> = serde_json::from_str(data)?;
+        Self::from_translations(&data)
+    }
+
+    fn from_translations(
+        data: &BTreeMap<String, BTreeMap<String, String>>,
+    ) -> Result<Self, LocalizedSearchLoadError> {
+        // group the locales by language tag, i.e. zh-CN and zh-TW
+        // are searched together
+        let mut groups: BTreeMap<String, Vec<&BTreeMap<String, String>>> = BTreeMap::new();
+        for (locale, translation) in data {
+            let tag = locale
+                .split(['-', '_'])
+                .next()
+                .unwrap_or(locale)
+                .to_ascii_lowercase();
+            groups.entry(tag).or_default().push(translation);
+        }
+        if groups.is_empty() {
+            return Err(LocalizedSearchLoadError::Empty);
+        }
+
+        let searchers = groups
+            .into_iter()
+            .map(|(tag, translations)| {
+                let threshold = if tag == "zh" { 0.35 } else { 0.3 };
+                let searcher = LocalizedSearcher::new(&tag, threshold, &translations);
+                (tag, searcher)
+            })
+            .collect();
+
+        Ok(Self { searchers })
+    }
+
+    /// Search for an item, returning the best match
+    ///
+    /// A number of stars (`★`) after the name of an upgradable armor
+    /// selects the upgraded armor
+    pub fn search(&self, query: &str) -> Option<ResolvedItem> {
+        let trimmed = query.trim_end();
+        let without_stars = trimmed.trim_end_matches(STAR_CHARS);
+        let star = trimmed[without_stars.len()..].chars().count();
+        if star == 0 {
+            return self.search_internal(query);
+        }
+        // stars are only valid for upgradable armors,
+        // otherwise they could be part of the name
+        let armor = self
+            .search_internal(without_stars)
+            .filter(|result| util::get_armor_star(&result.actor).is_some());
+        let Some(mut armor) = armor else {
+            return self.search_internal(query);
+        };
+        if let Some(meta) = armor.meta.as_mut() {
+            meta.star = Some(star as i32);
+        }
+        Some(armor)
+    }
+
+    fn search_internal(&self, query: &str) -> Option<ResolvedItem> {
+        let query = query.trim();
+        if query.is_empty() {
+            return None;
+        }
+        let mut results: Vec<(f64, &SearchEntry)> = vec![];
+        match query.split_once(':') {
+            Some((tag, query)) => {
+                // unknown tag is an error in the app
+                let searcher = self.searchers.get(tag)?;
+                searcher.search(query, &mut results);
+            }
+            None => {
+                if is_cjk(query) {
+                    // Japanese and Chinese characters overlap,
+                    // so we search both
+                    for tag in ["ja", "zh"] {
+                        if let Some(searcher) = self.searchers.get(tag) {
+                            searcher.search(query, &mut results);
+                        }
+                    }
+                } else {
+                    for searcher in self.searchers.values() {
+                        searcher.search(query, &mut results);
+                    }
+                }
+            }
+        }
+
+        // stable sort to keep the order of entries with the same score
+        results.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let (_, entry) = results.first()?;
+        Some(ResolvedItem::with_effect_id(
+            entry.actor.clone(),
+            entry.cook_effect,
+        ))
+    }
+}
+
+impl QuotedItemResolver for LocalizedItemResolver {
+    type Future = std::future::Ready<Option<ResolvedItem>>;
+
+    fn resolve_quoted(&self, word: &str) -> Self::Future {
+        std::future::ready(self.search(word))
+    }
+}
+
+/// Searcher for one language
+#[derive(Debug)]
+struct LocalizedSearcher {
+    threshold: f64,
+    entries: Vec<SearchEntry>,
+}
+
+/// One item that can be found by the searcher
+#[derive(Debug)]
+struct SearchEntry {
+    actor: String,
+    /// Cook effect id, 0 for none
+    cook_effect: i32,
+    /// All localized names of the entry, lowercased
+    names: Vec<Vec<char>>,
+}
+
+impl LocalizedSearcher {
+    fn new(tag: &str, threshold: f64, translations: &[&BTreeMap<String, String>]) -> Self {
+        log::info!("initializing localized searcher for \"{tag}\"");
+        let mut entries = vec![];
+        let Some(first) = translations.first() else {
+            return Self { threshold, entries };
+        };
+
+        let actors: BTreeSet<&str> = first
+            .keys()
+            .filter_map(|key| {
+                let actor = key.strip_prefix("actor.")?.strip_suffix(".name")?;
+                is_searchable_actor(actor).then_some(actor)
+            })
+            .collect();
+
+        for actor in actors {
+            let key = format!("actor.{actor}.name");
+            let actor_names: Vec<&str> = translations
+                .iter()
+                .map(|t| t.get(&key).map(String::as_str).unwrap_or_default())
+                .collect();
+            if !actor_names.iter().any(|name| name.contains("{{effect")) {
+                entries.push(SearchEntry::new(actor, 0, actor_names.into_iter()));
+                continue;
+            }
+            // add one entry for every effect
+            for (effect_name, effect_id) in COOK_EFFECTS {
+                let mut names = BTreeSet::new();
+                for t in translations {
+                    for actor_name in &actor_names {
+                        let mut name = actor_name.to_string();
+                        for placeholder in EFFECT_PLACEHOLDERS {
+                            let suffix = placeholder.strip_prefix("effect").unwrap_or_default();
+                            let value = t
+                                .get(&format!("cook.{effect_name}.name{suffix}"))
+                                .map(String::as_str)
+                                .unwrap_or_default();
+                            name = name.replace(&format!("{{{{{placeholder}}}}}"), value);
+                        }
+                        names.insert(name);
+                    }
+                }
+                entries.push(SearchEntry::new(
+                    actor,
+                    *effect_id,
+                    names.iter().map(String::as_str),
+                ));
+            }
+        }
+
+        log::info!("initialized {} search entries for \"{tag}\"", entries.len());
+        Self { threshold, entries }
+    }
+
+    fn search<'a>(&'a self, query: &str, results: &mut Vec<(f64, &'a SearchEntry)>) {
+        let query: Vec<char> = query.to_lowercase().chars().collect();
+        if query.is_empty() {
+            return;
+        }
+        for entry in &self.entries {
+            let score = entry
+                .names
+                .iter()
+                .filter_map(|name| fuzzy_score(&query, name, self.threshold))
+                .min_by(f64::total_cmp);
+            if let Some(score) = score {
+                results.push((score, entry));
+            }
+        }
+    }
+}
+
+impl SearchEntry {
+    fn new<'a>(actor: &str, cook_effect: i32, names: impl Iterator<Item = &'a str>) -> Self {
+        Self {
+            actor: actor.to_string(),
+            cook_effect,
+            names: names
+                .filter(|name| !name.is_empty())
+                .map(|name| name.to_lowercase().chars().collect())
+                .collect(),
+        }
+    }
+}
+
+/// Check if the text contains Japanese or Chinese characters
+fn is_cjk(text: &str) -> bool {
+    text.chars().any(|c| {
+        matches!(c as u32,
+            0x3000..=0x303f // Japanese Symbols and Punctuation
+            | 0x3041..=0x3096 // Hiragana
+            | 0x30a0..=0x30ff // Katakana
+            | 0x3300..=0x33ff // CJK Compatibility
+            | 0x3400..=0x4dbf // CJK Unified Ideographs Extension A
+            | 0x4e00..=0x9fff // CJK Unified Ideographs
+            | 0xf900..=0xfaff // CJK Compatibility Ideographs
+            | 0xfe30..=0xfe4f // CJK Compatibility Forms
+            | 0xff00..=0xffef // Full-width roman characters and half-width katakana
+            | 0x20000..=0x2a6df // CJK Unified Ideographs Extension B
+            | 0x2a700..=0x2ceaf // CJK Unified Ideographs Extension C-E
+            | 0x2f800..=0x2fa1f // CJK Compatibility Ideographs Supplement
+        )
+    })
+}
+
+/// Actors that are searchable but not covered by the prefix rules
+const ADDITIONAL_SEARCHABLE_ACTORS: &[&str] = &[
+    "AncientArrow",
+    "BombArrow_A",
+    "ElectricArrow",
+    "FireArrow",
+    "IceArrow",
+    "NormalArrow",
+    // itemized animals
+    "Animal_Insect_A",
+    "Animal_Insect_AA",
+    "Animal_Insect_AB",
+    "Animal_Insect_B",
+    "Animal_Insect_C",
+    "Animal_Insect_E",
+    "Animal_Insect_F",
+    "Animal_Insect_G",
+    "Animal_Insect_H",
+    "Animal_Insect_I",
+    "Animal_Insect_M",
+    "Animal_Insect_N",
+    "Animal_Insect_P",
+    "Animal_Insect_Q",
+    "Animal_Insect_R",
+    "Animal_Insect_S",
+    "Animal_Insect_T",
+    "Animal_Insect_X",
+    "BeeHome",
+    "Get_TwnObj_DLC_MemorialPicture_A_01",
+    "Obj_Armor_115_Head",
+    "Obj_DungeonClearSeal",
+    "Obj_FireWoodBundle",
+    "Obj_KorokNuts",
+    "Obj_Maracas",
+    "Obj_ProofBook",
+    "Obj_ProofGiantKiller",
+    "Obj_ProofSandwormKiller",
+    "Obj_ProofGolemKiller",
+    "Obj_ProofKorok",
+    "Obj_WarpDLC",
+    "Obj_DRStone_Get",
+    "PlayerStole2",
+];
+
+/// Check if the actor should be searchable. Must be kept in sync
+/// with `filterActorName` in `localization/src/localized_search.ts`
+fn is_searchable_actor(actor: &str) -> bool {
+    if actor.is_empty() || actor.ends_with("_00") {
+        return false;
+    }
+    if actor.starts_with("Weapon_Sword_") {
+        // cutscene and other unobtainable swords
+        return !["_071", "_072", "_080", "_081", "_500", "_501", "_503"]
+            .iter()
+            .any(|suffix| actor.ends_with(suffix));
+    }
+    if (actor.starts_with("Weapon_Bow_") || actor.starts_with("Weapon_Spear_"))
+        && actor.ends_with("_080")
+    {
+        return false;
+    }
+    if actor.starts_with("Weapon_") {
+        return true;
+    }
+    if actor.starts_with("Armor_") {
+        // borrowed snow boots
+        return actor != "Armor_140_Lower"
+            && !actor.starts_with("Armor_Default")
+            && !actor.ends_with("_B");
+    }
+    if actor.starts_with("Item_") {
+        // placed octo balloon
+        return actor != "Item_Enemy_Put_57";
+    }
+    const PREFIXES: &[&str] = &[
+        "GameRomHorseReins_",
+        "GameRomHorseSaddle_",
+        "Obj_DLC_HeroSeal_",
+        "Obj_DLC_HeroSoul_",
+        "Obj_HeroSoul_",
+    ];
+    if PREFIXES.iter().any(|prefix| actor.starts_with(prefix)) {
+        return true;
+    }
+    ADDITIONAL_SEARCHABLE_ACTORS.contains(&actor)
+}
@@ -9,3 +9,16 @@ pub use search_impl::*;
 
 mod resolver_types;
 pub use resolver_types::*;
+
+mod suggest;
+pub use suggest::*;
+
+#[cfg(feature = "localized")]
+mod fuzzy;
+#[cfg(feature = "localized")]
+pub use fuzzy::*;
+
+#[cfg(feature = "localized")]
+mod localized;
+#[cfg(feature = "localized")]
+pub use localized::*;
@@ -3,7 +3,8 @@ use std::collections::BTreeSet;
 use crate::cir;
 use crate::search::SearchResult;
 
-use super::ResolvedItem;
+use super::suggest::max_edit_distance;
+use super::{MAX_SUGGESTIONS, ResolvedItem};
 
 /// Search for an item by V4 identifier such as `royal_claymore`. Returns all matches ordered by
 /// score (best match first)
@@ -81,6 +82,29 @@ pub fn search_item_by_ident(search_str: &str) -> Option<ResolvedItem> {
     do_search_item_by_ident(search_str, all_item)
 }
 
+/// Get "did you mean" suggestions for an item identifier that cannot be resolved.
+///
+/// Returns similar identifiers, ranked by edit distance and then by the same
+/// priority used for searching. A cook effect prefix in the input is kept.
+pub fn suggest_item_by_ident(search_str: &str) -> Vec<String> {
+    let search_str = search_str.to_ascii_lowercase();
+    let search_str = search_str.trim_matches(|c| c == ' ' || c == '_' || c == '-');
+    if search_str.is_empty() {
+        return vec![];
+    }
+    if let Some((_, rest_search_str)) = split_and_search_effect(search_str) {
+        let prefix = &search_str[..search_str.len() - rest_search_str.len()];
+        let suggestions = do_suggest_item_by_ident(rest_search_str, is_cook_item);
+        if !suggestions.is_empty() {
+            return suggestions
+                .into_iter()
+                .map(|s| format!("{prefix}{s}"))
+                .collect();
+        }
+    }
+    do_suggest_item_by_ident(search_str, all_item)
+}
+
 /// Create an item for speed food. (for backward compability with V2 item)
 fn speed_food() -> ResolvedItem {
     ResolvedItem {
@@ -168,6 +192,31 @@ fn do_search_item_by_ident_all(
         .collect()
 }
 
+fn do_suggest_item_by_ident(search_str: &str, filter: impl Fn(&str) -> bool) -> Vec<String> {
+    let input = search_str
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '_' | '-'))
+        .collect::<String>();
+    let max_distance = max_edit_distance(&input);
+    let mut ranked = crate::generated::ITEM_NAMES
+        .iter()
+        .filter(|n| filter(n.actor))
+        .filter_map(|n| {
+            let distance = textdistance::str::levenshtein(&input, n.id());
+            (distance <= max_distance).then(|| (distance, n.to_result(search_str)))
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+    let mut seen = BTreeSet::new();
+    ranked
+        .into_iter()
+        .filter(|(_, r)| seen.insert(r.result.actor))
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, r)| r.result.id().to_string())
+        .collect()
+}
+
 fn all_item(_: &str) -> bool {
     true
 }
@@ -0,0 +1,44 @@
+use std::collections::BTreeSet;
+
+/// Max number of suggestions attached to an error
+pub const MAX_SUGGESTIONS: usize = 5;
+
+/// Rank candidates by similarity to the input, for "did you mean" suggestions
+///
+/// Comparison ignores case and treats `-` and `_` the same. Candidates that are
+/// too different from the input are excluded. Returns at most [`MAX_SUGGESTIONS`]
+/// candidates, best first, in their original form.
+pub fn suggest_from<'a>(input: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let input = normalize(input);
+    if input.is_empty() {
+        return vec![];
+    }
+    let max_distance = max_edit_distance(&input);
+    let mut ranked = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let distance = textdistance::str::levenshtein(&input, &normalize(candidate));
+            (distance <= max_distance).then_some((distance, candidate))
+        })
+        .collect::<Vec<_>>();
+    // stable sort to keep the candidate order for the same distance
+    ranked.sort_by_key(|(distance, _)| *distance);
+
+    let mut seen = BTreeSet::new();
+    ranked
+        .into_iter()
+        .filter(|(_, candidate)| seen.insert(*candidate))
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate.to_string())
+        .collect()
+}
+
+/// Get the maximum edit distance for a candidate to be considered similar
+/// to the (normalized) input
+pub(crate) fn max_edit_distance(input: &str) -> usize {
+    (input.chars().count() / 3).max(1)
+}
+
+fn normalize(s: &str) -> String {
+    s.to_lowercase().replace('_', "-")
+}
@@ -29,6 +29,8 @@ pub enum Command {
     Get(CmdGet),
     /// `pick-up ITEMS`
     PickUp(CmdPickUp),
+    /// `receive ITEMS from SOURCE`
+    Receive(CmdReceive),
 
     // ==== inventory screen & holding ====
     /// `pause`
@@ -57,7 +59,7 @@ pub enum Command {
     Unequip(CmdUnequip),
     /// `use CATEGORY [X times]`
     Use(CmdUse),
-    /// `shoot [X times]`
+    /// `shoot [X times]` or `shoot [X] ARROW`
     Shoot(CmdShoot),
     /// `throw weapon`
     ThrowWeapon((syn::KwThrow, syn::KwWeapon)),
@@ -73,6 +75,10 @@ pub enum Command {
     Buy(CmdBuy),
     /// `sell ITEMS`
     Sell(CmdSell),
+    /// `upgrade ARMOR`
+    Upgrade(CmdUpgrade),
+    /// `expand CATEGORY`
+    Expand(CmdExpand),
 
     // ==== saves/game state ====
     /// `save`
@@ -85,6 +91,24 @@ pub enum Command {
     CloseGame(syn::KwCloseGame),
     /// `new-game`
     NewGame(syn::KwNewGame),
+    /// `wait DURATION`
+    Wait(CmdWait),
+
+    // ==== blocks ====
+    /// `block NAME(PARAMS) { ... }`
+    Block(CmdBlock),
+    /// `call NAME(ARGS)`
+    Call(CmdCall),
+    /// `repeat X { ... }`
+    Repeat(CmdRepeat),
+    /// `include "PATH"`
+    Include(CmdInclude),
+    /// `label NAME`
+    Label(CmdLabel),
+    /// `let NAME = EXPR`
+    Let(CmdLet),
+    /// `if CONDITION { ... } else { ... }`
+    If(CmdIf),
 
     // ==== memory editing ===
     /// `!break X slots`
@@ -156,8 +180,12 @@ pub enum Annotation {
     Breaking(syn::KwBreaking),
     Dpad(syn::KwDpad),
     PerUse(CmdCoPerUse),
+    NoAutoEquip(syn::KwNoAutoEquip),
     Slots(CmdCoSlots),
     Discovered(CmdCoDiscovered),
+    Features(CmdCoFeatures),
+    GroundLimit(CmdCoGroundLimit),
+    Dlc(CmdCoDlc),
 }
 
 ///////////////////////////////////////////////////////////
@@ -178,6 +206,17 @@ pub struct CmdPickUp {
     pub items: syn::ItemListConstrained,
 }
 
+/// `receive ITEMS from SOURCE` - items come from a chest or an NPC,
+/// with an item text box
+#[derive_syntax]
+#[derive(Debug)]
+pub struct CmdReceive {
+    pub lit: syn::KwReceive,
+    pub items: syn::ItemListFinite,
+    pub kw_from: syn::KwFrom,
+    pub source: tp::String<syn::Word>,
+}
+
 ///////////////////////////////////////////////////////////
 
 /// `open-inventory`
@@ -290,10 +329,43 @@ pub struct CmdUse {
 }
 
 /// `shoot [X times]` is shorthand for `use bow X times`
+///
+/// `shoot [X] ARROW` or `shoot ARROW [X times]` equips the arrow
+/// before shooting
 #[derive_syntax]
 #[derive(Debug)]
 pub struct CmdShoot {
     pub lit: syn::KwShoot,
+    pub args: tp::Option<ShootArgs>,
+}
+
+#[derive_syntax]
+#[derive(Debug)]
+pub enum ShootArgs {
+    /// `X times` or `X ARROW`
+    Numbered(ShootNumbered),
+    /// `ARROW [X times]`
+    Arrow(ShootArrow),
+}
+
+#[derive_syntax]
+#[derive(Debug)]
+pub struct ShootNumbered {
+    pub num: syn::Number,
+    pub rest: ShootNumberedRest,
+}
+
+#[derive_syntax]
+#[derive(Debug)]
+pub enum ShootNumberedRest {
+    Times(syn::Time),
+    Arrow(syn::ItemOrCategory),
+}
+
+#[derive_syntax]
+#[derive(Debug)]
+pub struct ShootArrow {
+    pub arrow: syn::ItemOrCategory,
     pub times: tp::Option<syn::TimesClause>,
 }
 
@@ -349,6 +421,22 @@ pub struct CmdSell {
     pub items: syn::ItemListConstrained,
 }
 
+/// `upgrade ARMOR` - upgrade armor at a Great Fairy Fountain
+#[derive_syntax]
+#[derive(Debug)]
+pub struct CmdUpgrade {
+    pub lit: syn::KwUpgrade,
+    pub item: syn::ItemOrCategory,
+}
+
+/// `expand weapon|bow|shield` - expand a pouch with Korok Seeds
+#[derive_syntax]
+#[derive(Debug)]
+pub struct CmdExpand {
+    pub lit: syn::KwExpand,
+    pub category: syn::CategoryName,
+}
+
 ///////////////////////////////////////////////////////////
 
 /// `save-as NAME` - save the game to a named slot
@@ -369,6 +457,188 @@ pub struct CmdReload {
     pub name: tp::Option<syn::ItemName>,
 }
 
+/// `wait DURATION` - let time pass in the overworld
+///
+/// The duration is a number followed by a unit (seconds, minutes, hours or days),
+/// which is real time unless `in-game` is specified, e.g. `wait 10 minutes`
+/// or `wait 3 in-game hours`
+#[derive_syntax]
+#[derive(Debug)]
+pub struct CmdWait {
+    pub lit: syn::KwWait,
+    pub amount: syn::Number,
+    pub in_game: tp::Option<syn::KwInGame>,
+    pub unit: tp::String<syn::Word>,
+}
+
+///////////////////////////////////////////////////////////
+
+/// `block NAME(PARAMS) { ... }` - define a named block of statements,
+/// which can be invoked with `call`
+///
+/// The parameters are referenced in the body as `$PARAM`, and are
+/// replaced by the arguments when the block is called.
+/// The parentheses can be omitted if the block has no parameters
+#[derive_syntax]
+#[derive(Debug)]
+pub struct CmdBlock {
+    pub lit: syn::KwBlock,
+    pub name: tp::String<syn::Word>,
+    pub params: tp::Option<BlockParams>,
+    pub body: BlockBody,
+}
+
+/// `(PARAM1, PARAM2, ...)` in a block definition
+#[derive_syntax]
+#[derive(Debug)]
+pub struct BlockParams {
+    pub open: syn::SymLParen,
+    pub params: tp::Punct<BlockParam, syn::SymComma>,
+    pub close: syn::SymRParen,
+}
+
+/// Name of a parameter in a block definition
+#[derive_syntax]
+#[derive(Debug)]
+pub struct BlockParam {
+    #[teleparse(semantic(Variable))]
+    pub name: tp::String<syn::Word>,
+}
+
+/// `{ ... }` - statements in a block or a `repeat`
+#[derive_syntax]
+#[derive(Debug)]
+pub struct BlockBody {
+    pub open: syn::SymLBrace,
+    pub stmts: tp::Loop<Statement>,
+    pub close: syn::SymRBrace,
+}
+
+/// `call NAME(ARGS)` - invoke a block defined with `block`
+#[derive_syntax]
+#[derive(Debug)]
+pub struct CmdCall {
+    pub lit: syn::KwCall,
+    pub name: tp::String<syn::Word>,
+    pub args: tp::Option<BlockArgs>,
+}
+
+/// `(ARG1, ARG2, ...)` when calling a block
+#[derive_syntax]
+#[derive(Debug)]
+pub struct BlockArgs {
+    pub open: syn::SymLParen,
+    pub args: tp::Punct<BlockArg, syn::SymComma>,
+    pub close: syn::SymRParen,
+}
+
+/// One argument when calling a block, e.g. `apple`, `5` or `5 apple 3 banana`
+///
+/// The source of the argument replaces the parameter in the block as-is,
+/// so it is only checked after the block is expanded
+#[derive_syntax]
+#[derive(Debug)]
+pub struct BlockArg {
+    pub parts: tp::Nev<BlockArgPart>,
+}
+
+#[derive_syntax]
+#[derive(Debug)]
+pub enum BlockArgPart {
+    Number(syn::Number),
+    Item(syn::Item),
+    Category(syn::Category),
+    All(syn::KwAll),
+    But(syn::KwBut),
+}
+
+/// `repeat X { ... }` - repeat the statements X times
+#[derive_syntax]
+#[derive(Debug)]
+pub struct CmdRepeat {
+    pub lit: syn::KwRepeat,
+    pub times: NumberOrParam,
+    pub body: BlockBody,
+}
+
+/// `include "PATH"` - run the statements in another script, as if they were here
+///
+/// How the path is resolved depends on the `ScriptLoader` used for parsing
+#[derive_syntax]
+#[derive(Debug)]
+pub struct CmdInclude {
+    pub lit: syn::KwInclude,
+    pub path: tp::String<syn::QuotedWord>,
+}
+
+/// `label NAME` - name the point in the script between the steps
+/// before and after it
+#[derive_syntax]
+#[derive(Debug)]
+pub struct CmdLabel {
+    pub lit: syn::KwLabel,
+    pub name: tp::String<syn::Word>,
+}
+
+/// `let NAME = EXPR` - define a variable that can be used in expressions
+/// in the rest of the script, e.g. `drop (count - 2) apple`
+#[derive_syntax]
+#[derive(Debug)]
+pub struct CmdLet {
+    pub lit: syn::KwLet,
+    #[teleparse(semantic(Variable))]
+    pub name: tp::String<syn::Word>,
+    pub eq: syn::SymEqual,
+    pub value: syn::Expr,
+}
+
+/// `if CONDITION { ... } else if CONDITION { ... } else { ... }` - run
+/// the statements in the first branch whose condition is true
+///
+/// The conditions are checked against the state when the script runs
+#[derive_syntax]
+#[derive(Debug)]
+pub struct CmdIf {
+    pub lit: syn::KwIf,
+    pub condition: syn::Condition,
+    pub body: BlockBody,
+    pub else_clauses: tp::Loop<ElseClause>,
+}
+
+/// `else if CONDITION { ... }` or `else { ... }`
+///
+/// `else { ... }` must be the last clause
+#[derive_syntax]
+#[derive(Debug)]
+pub struct ElseClause {
+    pub lit: syn::KwElse,
+    pub rest: ElseRest,
+}
+
+#[derive_syntax]
+#[derive(Debug)]
+pub enum ElseRest {
+    If(ElseIf),
+    Else(BlockBody),
+}
+
+#[derive_syntax]
+#[derive(Debug)]
+pub struct ElseIf {
+    pub lit: syn::KwIf,
+    pub condition: syn::Condition,
+    pub body: BlockBody,
+}
+
+/// A number, or a parameter inside a block
+#[derive_syntax]
+#[derive(Debug)]
+pub enum NumberOrParam {
+    Number(syn::Number),
+    #[teleparse(semantic(Variable))]
+    Param(tp::String<syn::ParamWord>),
+}
+
 ///////////////////////////////////////////////////////////
 
 /// `!break X slots` - break X slots magically
@@ -459,6 +729,33 @@ pub struct CmdCoDiscovered {
     pub meta: syn::Meta,
 }
 
+/// `:features [FEATURE=true|false, ...]` - Turn BlueFlame features on or off
+/// for the rest of the run
+#[derive_syntax]
+#[derive(Debug)]
+pub struct CmdCoFeatures {
+    pub lit: syn::KwFeatures,
+    pub meta: syn::Meta,
+}
+
+/// `:ground-limit [materials=X, weapons=X]` - Set how many items can be
+/// on the ground before the oldest ones despawn
+#[derive_syntax]
+#[derive(Debug)]
+pub struct CmdCoGroundLimit {
+    pub lit: syn::KwGroundLimit,
+    pub meta: syn::Meta,
+}
+
+/// `:dlc X` - Set the DLC version (0-3) used to check DLC items and trials
+/// for the rest of the run
+#[derive_syntax]
+#[derive(Debug)]
+pub struct CmdCoDlc {
+    pub lit: syn::KwDlc,
+    pub version: syn::Number,
+}
+
 ///////////////////////////////////////////////////////////
 
 /// `roast ITEMS` - roast items on the ground or in inventory
@@ -0,0 +1,55 @@
+//! Syntax for conditions in `if`, e.g. `if apple >= 3 { ... }`
+
+use teleparse::{derive_syntax, tp};
+
+use crate::syn;
+
+/// A condition that is checked against the state when the script runs
+#[derive_syntax]
+#[derive(Debug)]
+pub enum Condition {
+    /// `ITEM OP X` - number of the item (or number of slots in the category)
+    Count(CondCount),
+    /// `mcount OP X` - number of items in the pouch (`mCount`)
+    MCount(CondMCount),
+    /// `gdt <FLAG>[properties]` - a gamedata flag has the value
+    Gdt(CondGdt),
+}
+
+/// `ITEM OP X`, e.g. `apple >= 3` or `weapon[slot=5] == 1`
+#[derive_syntax]
+#[derive(Debug)]
+pub struct CondCount {
+    pub item: syn::ItemOrCategory,
+    pub op: Comparison,
+    pub value: syn::Number,
+}
+
+/// `mcount OP X`, e.g. `mcount < 20`
+#[derive_syntax]
+#[derive(Debug)]
+pub struct CondMCount {
+    pub lit: syn::KwMCount,
+    pub op: Comparison,
+    pub value: syn::Number,
+}
+
+/// `gdt <FLAG>[properties]`, same as the properties in `!set-gdt`
+#[derive_syntax]
+#[derive(Debug)]
+pub struct CondGdt {
+    pub lit: syn::KwGdt,
+    pub flag_name: syn::AngledWord,
+    pub props: syn::Meta,
+}
+
+#[derive_syntax]
+#[derive(Debug)]
+pub enum Comparison {
+    Eq(syn::SymEqEq),
+    Ne(syn::SymNotEq),
+    Le(syn::SymLessEq),
+    Ge(syn::SymGreaterEq),
+    Lt(syn::SymLAngle),
+    Gt(syn::SymRAngle),
+}
@@ -0,0 +1,55 @@
+//! Syntax for integer expressions, e.g. `(count - 2)` or `let x = 100 * y`
+
+use teleparse::{derive_syntax, tp};
+
+use crate::syn;
+
+/// An expression in parentheses, which can be used in place of a number
+#[derive_syntax]
+#[teleparse(root)]
+#[derive(Debug)]
+pub struct ParenExpr {
+    pub open: syn::SymLParen,
+    pub expr: Expr,
+    pub close: syn::SymRParen,
+}
+
+/// Operands separated by operators, e.g. `a + 2 * b`
+///
+/// `*` and `/` are evaluated before `+` and `-`
+#[derive_syntax]
+#[derive(Debug)]
+pub struct Expr {
+    pub first: Operand,
+    pub rest: tp::Loop<ExprRest>,
+}
+
+#[derive_syntax]
+#[derive(Debug)]
+pub struct ExprRest {
+    pub op: Operator,
+    pub operand: Operand,
+}
+
+#[derive_syntax]
+#[derive(Debug)]
+pub enum Operator {
+    Add(syn::SymPlus),
+    Sub(syn::SymMinus),
+    Mul(syn::SymStar),
+    Div(syn::SymSlash),
+}
+
+#[derive_syntax]
+#[derive(Debug)]
+pub enum Operand {
+    /// A number literal
+    Number(tp::String<syn::SymNumber>),
+    /// A variable defined with `let`
+    #[teleparse(semantic(Variable))]
+    Variable(tp::String<syn::Word>),
+    /// A parameter inside a block, which is replaced
+    /// by the argument when the block is called
+    #[teleparse(semantic(Variable))]
+    Param(tp::String<syn::ParamWord>),
+}
@@ -86,6 +86,10 @@ pub enum ItemName {
     /// Use angle brackets to use the literal as the actor name
     /// e.g. `<Weapon_Sword_070>`
     Angle(syn::AngledWord),
+    /// A parameter inside a block, e.g. `$item`, which is replaced
+    /// by the argument when the block is called
+    #[teleparse(semantic(Variable))]
+    Param(tp::String<syn::ParamWord>),
 }
 
 /// A word that can be used as item name or property name
@@ -105,5 +109,9 @@ pub enum ItemWord {
     KwBreaking(syn::KwBreaking),
     KwDpad(syn::KwDpad),
     KwPerUse(syn::KwPerUse),
+    KwNoAutoEquip(syn::KwNoAutoEquip),
     KwDiscovered(syn::KwDiscovered),
+    KwFeatures(syn::KwFeatures),
+    KwGroundLimit(syn::KwGroundLimit),
+    KwDlc(syn::KwDlc),
 }
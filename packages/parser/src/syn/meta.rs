@@ -68,5 +68,5 @@ pub enum MetaValue {
 #[derive(Debug)]
 pub struct MetaValueNumber {
     pub int_part: syn::Number,
-    pub float_part: tp::Option<(syn::SymPeriod, tp::Option<syn::Number>)>,
+    pub float_part: tp::Option<(syn::SymPeriod, tp::Option<tp::String<syn::SymNumber>>)>,
 }
@@ -12,3 +12,7 @@ mod category;
 pub use category::*;
 mod meta;
 pub use meta::*;
+mod expr;
+pub use expr::*;
+mod condition;
+pub use condition::*;
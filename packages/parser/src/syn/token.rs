@@ -2,6 +2,8 @@ use derive_more::derive::{Deref, DerefMut};
 use serde::Serialize;
 use teleparse::{derive_lexicon, derive_syntax, tp};
 
+use crate::syn;
+
 /// Token type
 #[derive(Serialize)]
 #[derive_lexicon]
@@ -30,6 +32,14 @@ pub enum TT {
         SymSemi = ";",
         SymQuote = "\"",
         SymPeriod = ".",
+        SymPlus = "+",
+        SymMinus = "-",
+        SymStar = "*",
+        SymSlash = "/",
+        SymEqEq = "==",
+        SymNotEq = "!=",
+        SymLessEq = "<=",
+        SymGreaterEq = ">=",
     ))]
     Symbol,
 
@@ -43,12 +53,14 @@ pub enum TT {
     #[teleparse(terminal(
         /////////////////////////////
         // When updating syntax, Keep the order in sync with syn and cir Command
+        // Also update expected_after in recover.rs
         /////////////////////////////
 
         // @syntax-generator-hint commands
         KwGet = "get",
         KwGetPause = "get-pause",
         KwPickUp = "pick-up",
+        KwReceive = "receive",
 
         KwOpenInventory = "open-inventory",
         KwOpenInv = "open-inv",
@@ -77,12 +89,23 @@ pub enum TT {
         KwCloseDialog = "close-dialog",
         KwBuy = "buy",
         KwSell = "sell",
+        KwUpgrade = "upgrade",
+        KwExpand = "expand",
 
         KwSave = "save",
         KwSaveAs = "save-as",
         KwReload = "reload",
         KwCloseGame = "close-game",
         KwNewGame = "new-game",
+        KwWait = "wait",
+
+        KwBlock = "block",
+        KwCall = "call",
+        KwRepeat = "repeat",
+        KwInclude = "include",
+        KwLabel = "label",
+        KwLet = "let",
+        KwIf = "if",
 
 
         // == below are WIP ==
@@ -113,6 +136,7 @@ pub enum TT {
         /////////////////////////////
         // When updating syntax, Keep the order in sync with syn and cir Command
         // Also add the annotation to ItemWord
+        // Also update expected_after in recover.rs
         /////////////////////////////
 
         // @syntax-generator-hint annotations
@@ -126,7 +150,11 @@ pub enum TT {
         KwBreaking = "breaking",
         KwDpad = "dpad",
         KwPerUse = "per-use",
+        KwNoAutoEquip = "no-auto-equip",
         KwDiscovered = "discovered",
+        KwFeatures = "features",
+        KwGroundLimit = "ground-limit",
+        KwDlc = "dlc",
         // @syntax-generator-hint end
 
         /////////////////////////////
@@ -139,6 +167,7 @@ pub enum TT {
     #[teleparse(terminal(
         /////////////////////////////
         // When updating syntax, Keep the order in sync with syn and cir Command
+        // Also update expected_after in recover.rs
         /////////////////////////////
         KwSuBreak = "!break",
         KwSuInit = "!init",
@@ -168,7 +197,12 @@ pub enum TT {
         KwSlot = "slot",
         KwSlots = "slots",
         KwTo = "to",
+        KwFrom = "from",
         KwAnd = "and",
+        KwInGame = "in-game",
+        KwElse = "else",
+        KwMCount = "mcount",
+        KwGdt = "gdt",
         // @syntax-generator-hint end
 
         // @syntax-generator-hint types
@@ -222,6 +256,10 @@ pub enum TT {
     #[teleparse(regex(r#""[^"]*""#), terminal(QuotedWord))]
     QuotedWord,
 
+    /// A block parameter, e.g. `$item`
+    #[teleparse(regex(r"\$[_a-zA-Z][-0-9a-zA-Z_]*"), terminal(ParamWord))]
+    ParamWord,
+
     /// A variable name (for example, a meta key)
     Variable,
     /// item type/category
@@ -232,9 +270,20 @@ pub enum TT {
     /// item literal (for example <Weapon_Sword_502>)
     ItemLiteral,
 }
+/// A number, which is either a literal or an expression in parentheses,
+/// e.g. `5`, `0x10` or `(count - 2)`
+///
+/// Derefs to the source text. Expressions are evaluated during lowering
 #[derive_syntax]
 #[derive(Debug, Deref, DerefMut)]
-pub struct Number(pub tp::String<SymNumber>);
+pub struct Number(pub tp::String<NumberValue>);
+
+#[derive_syntax]
+#[derive(Debug)]
+pub enum NumberValue {
+    Literal(SymNumber),
+    Expr(syn::ParenExpr),
+}
 
 /// A word surrounded by angle brackets, e.g. `<word>`
 #[derive_syntax]
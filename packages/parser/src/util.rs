@@ -1,5 +1,23 @@
 use std::borrow::Cow;
 
+/// Get the number of stars (0-4) of an upgradable armor,
+/// or `None` if the actor is not upgradable armor
+pub fn get_armor_star(mut actor: &str) -> Option<i32> {
+    // special case for Snow Boots, see get_armor_with_star
+    if actor == "Armor_140_Lower" {
+        actor = "Armor_141_Lower";
+    }
+    let to_search = actor.strip_prefix("Armor_")?;
+    for armor_group in crate::generated::ARMOR_UPGRADE {
+        for (i, armor) in armor_group.iter().enumerate() {
+            if *armor == to_search {
+                return Some(i as i32);
+            }
+        }
+    }
+    None
+}
+
 /// Given any armor, get the armor actor with the number of stars
 ///
 /// Star is clamped between 0 and 4
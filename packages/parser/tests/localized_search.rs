@@ -0,0 +1,115 @@
+#![cfg(feature = "localized")]
+use skybook_parser::cir;
+use skybook_parser::search::{LocalizedItemResolver, ResolvedItem};
+
+const DATA: &str = r#"{
+    "de-DE": {
+        "actor.Item_Fruit_A.name": "Apfel",
+        "actor.Armor_001_Head.name": "Hylia-Kapuze",
+        "actor.Weapon_Lsword_036.name": "Königs-Zweihänder",
+        "actor.Item_Cook_A_03.name": "{{effect}}-Pilzspieß",
+        "cook.AllSpeed.name": "Spurt"
+    },
+    "en-US": {
+        "actor.Item_Fruit_A.name": "Apple",
+        "actor.Armor_001_Head.name": "Hylian Hood",
+        "actor.Weapon_Lsword_036.name": "Royal Claymore",
+        "actor.Weapon_Sword_071.name": "Master Sword",
+        "actor.Item_Cook_A_03.name": "{{effect}} Mushroom Skewer",
+        "cook.AllSpeed.name": "Hasty"
+    },
+    "zh-CN": {
+        "actor.Item_Fruit_A.name": "苹果",
+        "actor.Armor_001_Head.name": "海利亚兜帽",
+        "actor.Weapon_Lsword_036.name": "王家双手剑",
+        "actor.Item_Cook_A_03.name": "{{effect}}蘑菇烤串",
+        "cook.AllSpeed.name": "速速"
+    },
+    "zh-TW": {
+        "actor.Item_Fruit_A.name": "蘋果",
+        "actor.Armor_001_Head.name": "海利亞兜帽",
+        "actor.Weapon_Lsword_036.name": "王家雙手劍",
+        "actor.Item_Cook_A_03.name": "{{effect}}蘑菇烤串",
+        "cook.AllSpeed.name": "速速"
+    }
+}"#;
+
+fn resolve(query: &str) -> Option<String> {
+    let resolver = LocalizedItemResolver::from_json(DATA).unwrap();
+    resolver.search(query).map(|x| x.actor)
+}
+
+#[test]
+fn test_localized_search_exact() {
+    assert_eq!(resolve("Apple").as_deref(), Some("Item_Fruit_A"));
+    assert_eq!(
+        resolve("royal claymore").as_deref(),
+        Some("Weapon_Lsword_036")
+    );
+    assert_eq!(resolve("Apfel").as_deref(), Some("Item_Fruit_A"));
+    assert_eq!(resolve("苹果").as_deref(), Some("Item_Fruit_A"));
+    assert_eq!(resolve("王家雙手劍").as_deref(), Some("Weapon_Lsword_036"));
+}
+
+#[test]
+fn test_localized_search_fuzzy() {
+    assert_eq!(
+        resolve("royal claymor").as_deref(),
+        Some("Weapon_Lsword_036")
+    );
+    assert_eq!(resolve("claymore").as_deref(), Some("Weapon_Lsword_036"));
+    assert_eq!(
+        resolve("Konigs-Zweihander").as_deref(),
+        Some("Weapon_Lsword_036")
+    );
+    assert_eq!(resolve("xyzzy"), None);
+    assert_eq!(resolve(""), None);
+}
+
+#[test]
+fn test_localized_search_language_tag() {
+    assert_eq!(resolve("de:Apfel").as_deref(), Some("Item_Fruit_A"));
+    assert_eq!(resolve("en:Apfel"), None);
+    assert_eq!(resolve("xx:Apple"), None);
+}
+
+#[test]
+fn test_localized_search_unsearchable() {
+    assert_eq!(resolve("Master Sword"), None);
+}
+
+#[test]
+fn test_localized_search_cook_effect() {
+    let resolver = LocalizedItemResolver::from_json(DATA).unwrap();
+    let expected = Some(ResolvedItem::with_effect_id(
+        "Item_Cook_A_03".to_string(),
+        13,
+    ));
+    assert_eq!(resolver.search("Hasty Mushroom Skewer"), expected);
+    assert_eq!(resolver.search("Spurt-Pilzspieß"), expected);
+    assert_eq!(resolver.search("速速蘑菇烤串"), expected);
+}
+
+#[test]
+fn test_localized_search_armor_star() {
+    let resolver = LocalizedItemResolver::from_json(DATA).unwrap();
+    assert_eq!(
+        resolver.search("Hylian Hood ★★"),
+        Some(ResolvedItem::with_meta(
+            "Armor_001_Head".to_string(),
+            cir::ItemMeta {
+                star: Some(2),
+                ..Default::default()
+            }
+        ))
+    );
+    assert_eq!(
+        resolver.search("Hylian Hood"),
+        Some(ResolvedItem::with_effect_id(
+            "Armor_001_Head".to_string(),
+            0
+        ))
+    );
+    // stars are not removed for items that are not armor
+    assert_eq!(resolver.search("Apple ★"), resolver.search("Apple"));
+}
@@ -89,6 +89,724 @@ async fn parse_notes() -> anyhow::Result<()> {
     test_parser_snapshot("notes", script).await
 }
 
+#[tokio::test]
+async fn parse_blocks() -> anyhow::Result<()> {
+    let script = indoc! {r#"
+        get 5 apple
+        call hold-drop(2 apple)
+        repeat 2 { call hold-drop(apple) }
+        block hold-drop(item) {
+          hold $item
+          drop
+        }
+        repeat 0 { drop }
+    "#};
+    let output = skybook_parser::parse(&StubQuotedItemResolver, script).await;
+    assert!(output.errors.is_empty(), "{:#?}", output.errors);
+
+    let positions = output.steps.iter().map(|x| x.pos()).collect::<Vec<_>>();
+    let call_pos = script.find("call").unwrap();
+    let repeat_pos = script.find("repeat").unwrap();
+    assert_eq!(
+        positions,
+        vec![
+            0, call_pos, call_pos, repeat_pos, repeat_pos, repeat_pos, repeat_pos
+        ]
+    );
+    assert_eq!(output.steps[3].command(), output.steps[5].command());
+    assert_eq!(output.steps[4].command(), output.steps[2].command());
+
+    // the last step expanded from the statement is used
+    assert_eq!(output.step_idx_from_pos(call_pos), Some(2));
+    assert_eq!(output.step_idx_from_pos(repeat_pos + 3), Some(6));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn parse_blocks_errors() -> anyhow::Result<()> {
+    use skybook_parser::Error;
+
+    let script = indoc! {r#"
+        block hold-drop(item) { hold $item; drop }
+        call hold-drp(apple)
+        call hold-drop
+        block typo(item) { hold $iten }
+        call typo(apple)
+        hold $item
+        block recursive { call recursive }
+        call recursive
+    "#};
+    let output = skybook_parser::parse(&StubQuotedItemResolver, script).await;
+    let errors = &output.errors;
+    assert_eq!(errors.len(), 5, "{errors:#?}");
+
+    assert!(matches!(&errors[0].error, Error::UndefinedBlock(x) if x == "hold-drp"));
+    assert_eq!(errors[0].suggestions, vec!["hold-drop"]);
+    assert!(matches!(&errors[1].error, Error::BlockArgCount(_, 1, 0)));
+
+    // errors inside the block are reported at the call
+    let call_pos = script.find("call typo").unwrap();
+    assert!(matches!(&errors[2].error, Error::UnboundParam(x) if x == "$iten"));
+    assert_eq!(errors[2].span.0, call_pos);
+
+    assert!(matches!(&errors[3].error, Error::UnboundParam(x) if x == "$item"));
+    assert!(matches!(&errors[4].error, Error::BlockTooDeep(_)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn parse_pages() -> anyhow::Result<()> {
+    use skybook_parser::StepDisplay;
+
+    let script = indoc! {r#"
+        '''page
+        Setup
+        '''
+        get 5 apple
+        hold 2 apple; drop
+        '''page
+        Duplication
+        '''
+        '''note
+        save first
+        '''
+        save
+    "#};
+    let output = skybook_parser::parse(&StubQuotedItemResolver, script).await;
+    assert!(output.errors.is_empty(), "{:#?}", output.errors);
+
+    assert_eq!(output.pages, vec![0, 3]);
+    assert_eq!(
+        output.display,
+        vec![
+            (StepDisplay::Text("Setup".to_string()), 0),
+            (StepDisplay::Command, 0),
+            (StepDisplay::Command, 1),
+            (StepDisplay::Text("Duplication".to_string()), 3),
+            (StepDisplay::Text("save first".to_string()), 3),
+            (StepDisplay::Command, 3),
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn parse_note_sections() -> anyhow::Result<()> {
+    let script = indoc! {r#"
+        '''note
+        Get apples
+        '''
+        get 5 apple
+        hold 2 apple; drop
+
+        drop all weapons
+        '''note
+        Then save
+        '''
+        save
+    "#};
+    let output = skybook_parser::parse(&StubQuotedItemResolver, script).await;
+    assert!(output.errors.is_empty(), "{:#?}", output.errors);
+
+    let notes = output.step_notes();
+    let notes = notes.iter().map(|x| x.as_ref()).collect::<Vec<_>>();
+    assert_eq!(
+        notes,
+        vec!["Get apples", "Get apples", "Get apples", "", "Then save"]
+    );
+
+    let sections = output
+        .note_sections()
+        .into_iter()
+        .map(|x| (x.note.to_string(), x.steps))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sections,
+        vec![
+            ("Get apples".to_string(), 0..3),
+            ("Then save".to_string(), 4..5)
+        ]
+    );
+
+    let markdown = skybook_parser::notes::export_markdown(script, &output);
+    assert_eq!(
+        markdown,
+        indoc! {r#"
+            Get apples
+
+            ```skybook
+            get 5 apple
+            hold 2 apple;
+            drop
+            ```
+
+            ```skybook
+            drop all weapons
+            ```
+
+            Then save
+
+            ```skybook
+            save
+            ```
+
+        "#}
+    );
+
+    // steps from an included script are exported as the include
+    let script = indoc! {r#"
+        get 1 apple
+        include "a.txt"
+        drop
+    "#};
+    let loader = |path: &str| {
+        std::future::ready((path == "a.txt").then(|| "get 2 apple\nhold apple\n".to_string()))
+    };
+    let output = skybook_parser::parse_with_loader(&StubQuotedItemResolver, &loader, script).await;
+    assert!(output.errors.is_empty(), "{:#?}", output.errors);
+    assert_eq!(output.steps.len(), 4);
+    let markdown = skybook_parser::notes::export_markdown(script, &output);
+    assert_eq!(
+        markdown,
+        indoc! {r#"
+            ```skybook
+            get 1 apple
+            include "a.txt"
+            drop
+            ```
+
+        "#}
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn parse_labels() -> anyhow::Result<()> {
+    use skybook_parser::Error;
+
+    let script = indoc! {r#"
+        label start
+        get 5 apple
+        label after-get
+        hold 2 apple; drop
+        label end
+        label after-get
+        repeat 2 { label nested }
+    "#};
+    let output = skybook_parser::parse(&StubQuotedItemResolver, script).await;
+    assert_eq!(output.steps.len(), 3);
+
+    // no steps are before `start`, so it's the initial state
+    assert_eq!(output.labels.get("start"), Some(&0));
+    assert_eq!(output.step_idx_from_label("start"), None);
+    assert_eq!(output.step_idx_from_label("after-get"), Some(0));
+    assert_eq!(output.step_idx_from_label("end"), Some(2));
+    assert_eq!(output.step_idx_from_label("nested"), None);
+    assert_eq!(output.step_idx_from_label("missing"), None);
+
+    let errors = &output.errors;
+    assert_eq!(errors.len(), 2, "{errors:#?}");
+    assert!(matches!(&errors[0].error, Error::DuplicateLabel(x) if x == "after-get"));
+    assert_eq!(errors[0].span.0, script.rfind("after-get").unwrap());
+    assert!(matches!(&errors[1].error, Error::NestedLabel));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn parse_repeat_limit() -> anyhow::Result<()> {
+    use skybook_parser::Error;
+
+    // the expanded count doesn't fit in 32 bits
+    let script = indoc! {r#"
+        get 1 apple
+        repeat 2147483647 { get 1 apple; drop 1 apple; get 1 apple }
+    "#};
+    let output = skybook_parser::parse(&StubQuotedItemResolver, script).await;
+    let errors = &output.errors;
+    assert_eq!(errors.len(), 1, "{errors:#?}");
+    assert!(matches!(&errors[0].error, Error::TooManyExpandedSteps(_)));
+    assert_eq!(errors[0].span.0, script.find("repeat").unwrap());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn parse_variables() -> anyhow::Result<()> {
+    use skybook_parser::Error;
+
+    let script = indoc! {r#"
+        let count = 4
+        let count = count + 1
+        get (count * 2 - 1) apple
+        !break (count - 2) slots
+        get 1 royal-claymore[value=(100 * count)]
+        repeat (count / 2) { drop 1 apple }
+    "#};
+    let expected = indoc! {r#"
+        get 9 apple
+        !break 3 slots
+        get 1 royal-claymore[value=500]
+        repeat 2 { drop 1 apple }
+    "#};
+    let output = skybook_parser::parse(&StubQuotedItemResolver, script).await;
+    assert!(output.errors.is_empty(), "{:#?}", output.errors);
+    let expected = skybook_parser::parse(&StubQuotedItemResolver, expected).await;
+    let commands = output.steps.iter().map(|x| x.command()).collect::<Vec<_>>();
+    let expected = expected
+        .steps
+        .iter()
+        .map(|x| x.command())
+        .collect::<Vec<_>>();
+    assert_eq!(commands, expected);
+
+    let script = indoc! {r#"
+        let count = 1
+        get (coun + 1) apple
+        get (5 / 0) apple
+        get (0x7fffffffffffffff * 2) apple
+        drop (count - 2) apple
+        repeat 1 { let x = 1 }
+    "#};
+    let output = skybook_parser::parse(&StubQuotedItemResolver, script).await;
+    let errors = &output.errors;
+    assert_eq!(errors.len(), 5, "{errors:#?}");
+    assert!(matches!(&errors[0].error, Error::UndefinedVariable(x) if x == "coun"));
+    assert_eq!(errors[0].span.0, script.find("coun +").unwrap());
+    assert_eq!(errors[0].suggestions, vec!["count".to_string()]);
+    assert!(matches!(&errors[1].error, Error::DivideByZero));
+    assert_eq!(errors[1].span.0, script.find("0)").unwrap());
+    assert!(matches!(&errors[2].error, Error::IntRange(_)));
+    // errors after evaluating are reported at the expression
+    assert!(matches!(&errors[3].error, Error::InvalidItemAmount));
+    let pos = script.find("(count - 2)").unwrap();
+    assert_eq!(errors[3].span, (pos, pos + "(count - 2)".len()));
+    assert!(matches!(&errors[4].error, Error::NestedLet));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn parse_if() -> anyhow::Result<()> {
+    use skybook_parser::Error;
+    use skybook_parser::cir::{Command, Compare, Condition, StepBranch};
+
+    let script = indoc! {r#"
+        get 1 apple
+        if apple >= (2 + 1) {
+            get 1 banana
+            if mcount < 20 { drop 1 banana }
+        } else if weapon == 20 {
+            eat 1 apple
+        } else {
+            repeat 2 { get 1 apple }
+        }
+        repeat 2 { if mcount > 0 { get 1 apple } }
+    "#};
+    let output = skybook_parser::parse(&StubQuotedItemResolver, script).await;
+    assert!(output.errors.is_empty(), "{:#?}", output.errors);
+    let branch = |step, branch| Some(StepBranch { step, branch });
+    let branches = output.steps.iter().map(|x| x.branch()).collect::<Vec<_>>();
+    assert_eq!(
+        branches,
+        vec![
+            None,
+            None,
+            branch(1, 0),
+            branch(1, 0),
+            branch(3, 0),
+            branch(1, 1),
+            branch(1, 2),
+            branch(1, 2),
+            None,
+            branch(8, 0),
+            None,
+            branch(10, 0),
+        ]
+    );
+    let Command::If(conditions) = output.steps[1].command() else {
+        panic!("expected if, got {:?}", output.steps[1].command());
+    };
+    assert_eq!(conditions.len(), 2);
+    assert!(matches!(
+        &conditions[0],
+        Condition::Count(_, Compare::Ge, 3)
+    ));
+    assert!(matches!(
+        &conditions[1],
+        Condition::Count(_, Compare::Eq, 20)
+    ));
+    assert_eq!(
+        output.steps[3].command(),
+        &Command::If(vec![Condition::MCount(Compare::Lt, 20)])
+    );
+
+    let script = indoc! {r#"
+        if apple > 1 { } else { } else { }
+        if mcount < 1 { let x = 1 }
+    "#};
+    let output = skybook_parser::parse(&StubQuotedItemResolver, script).await;
+    let errors = &output.errors;
+    assert_eq!(errors.len(), 2, "{errors:#?}");
+    assert!(matches!(&errors[0].error, Error::ElseNotLast));
+    assert_eq!(errors[0].span.0, script.rfind("else").unwrap());
+    assert!(matches!(&errors[1].error, Error::NestedLet));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn parse_recovery() -> anyhow::Result<()> {
+    use skybook_parser::{Error, SyntaxExpectation};
+
+    let script = indoc! {r#"
+        get 1 apple
+        hold
+        get 2 banana
+        drop 1 apple; use; get 1 apple
+        repeat 2 {
+            get 1 apple
+            equip
+        }
+        wait
+    "#};
+    let output = skybook_parser::parse(&StubQuotedItemResolver, script).await;
+    // statements after the errors are still parsed
+    assert_eq!(output.steps.len(), 6, "{:#?}", output.steps);
+    assert_eq!(output.steps[1].pos(), script.find("get 2").unwrap());
+    assert_eq!(
+        output.steps[3].pos(),
+        script.rfind("get 1 apple\nrepeat").unwrap()
+    );
+
+    let errors = &output.errors;
+    assert_eq!(errors.len(), 4, "{errors:#?}");
+    assert!(matches!(
+        &errors[0].error,
+        Error::SyntaxExpected(SyntaxExpectation::ItemList, x) if x == "hold"
+    ));
+    assert_eq!(errors[0].span.0, script.find("get 2").unwrap());
+    assert_eq!(
+        errors[0].error.to_string(),
+        "expected item list after `hold`"
+    );
+    assert!(matches!(
+        &errors[1].error,
+        Error::SyntaxExpected(SyntaxExpectation::Item, x) if x == "use"
+    ));
+    assert_eq!(errors[1].span.0, script.find("; get").unwrap());
+    assert!(matches!(
+        &errors[2].error,
+        Error::SyntaxExpected(SyntaxExpectation::ItemList, x) if x == "equip"
+    ));
+    assert!(matches!(
+        &errors[3].error,
+        Error::SyntaxExpected(SyntaxExpectation::Number, x) if x == "wait"
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn parse_recovery_large() -> anyhow::Result<()> {
+    use skybook_parser::{Error, SyntaxExpectation};
+
+    // a large script with an error in every other statement
+    let line = "get 1 apple\nhold\n";
+    let script = line.repeat(2000);
+    let output = skybook_parser::parse(&StubQuotedItemResolver, &script).await;
+    assert!(output.errors.len() >= 1000);
+    assert!(output.steps.len() >= 1000);
+    // statements are still skipped until the recovery stops
+    for i in 0..1000 {
+        assert_eq!(output.steps[i].pos(), i * line.len());
+        let error = &output.errors[i];
+        assert!(matches!(
+            &error.error,
+            Error::SyntaxExpected(SyntaxExpectation::ItemList, x) if x == "hold"
+        ));
+        assert_eq!(error.span.0, (i + 1) * line.len());
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn parse_recovery_expected_after() -> anyhow::Result<()> {
+    use skybook_parser::Error;
+
+    // keywords of the commands, annotations and super commands
+    let mut keywords = Vec::new();
+    let mut prefix = None;
+    for line in include_str!("../src/syn/token.rs").lines() {
+        let line = line.trim();
+        match line {
+            "// @syntax-generator-hint commands" => prefix = Some(""),
+            "// @syntax-generator-hint annotations" => prefix = Some(":"),
+            "// @syntax-generator-hint end" => prefix = None,
+            _ => {}
+        }
+        let Some((name, value)) = line.split_once(" = \"") else {
+            continue;
+        };
+        if !name.starts_with("Kw") {
+            continue;
+        }
+        let keyword = value.trim_end_matches("\",");
+        if keyword.starts_with('!') {
+            keywords.push(keyword.to_string());
+        } else if let Some(prefix) = prefix {
+            keywords.push(format!("{prefix}{keyword}"));
+        }
+    }
+    assert!(keywords.iter().any(|x| x == "get"));
+    assert!(keywords.iter().any(|x| x == ":dlc"));
+    assert!(keywords.iter().any(|x| x == "!set-gdt"));
+
+    // keywords that need arguments should say what is expected
+    // (update expected_after in recover.rs if this fails)
+    for keyword in keywords {
+        let script = format!("{keyword}\n");
+        let output = skybook_parser::parse(&StubQuotedItemResolver, &script).await;
+        for error in &output.errors {
+            // keywords not used by any command are unexpected by themselves
+            if error.span.0 == 0 {
+                continue;
+            }
+            if matches!(
+                &error.error,
+                Error::SyntaxUnexpected | Error::SyntaxUnexpectedEof
+            ) {
+                bail!(
+                    "`{keyword}` needs an argument, but the error does not say what is expected: {error:?}"
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn parse_include() -> anyhow::Result<()> {
+    use skybook_parser::Error;
+
+    let script = indoc! {r#"
+        get 1 apple
+        include "a.txt"
+        include "missing.txt"
+        call from-b
+        include "a.txt"
+    "#};
+    let a = indoc! {r#"
+        include "b.txt"
+        hold apple
+    "#};
+    let b = indoc! {r#"
+        block from-b { drop }
+        include "a.txt"
+    "#};
+    let loader = |path: &str| {
+        std::future::ready(match path {
+            "a.txt" => Some(a.to_string()),
+            "b.txt" => Some(b.to_string()),
+            _ => None,
+        })
+    };
+    let output = skybook_parser::parse_with_loader(&StubQuotedItemResolver, &loader, script).await;
+    assert_eq!(output.files, vec!["a.txt", "b.txt"]);
+
+    // steps from the included script have the position of the include
+    let include_pos = script.find("include").unwrap();
+    let last_include_pos = script.rfind("include").unwrap();
+    let call_pos = script.find("call").unwrap();
+    let steps = output
+        .steps
+        .iter()
+        .map(|x| (x.file(), x.pos()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        steps,
+        vec![
+            (0, 0),
+            (1, include_pos),
+            (0, call_pos),
+            (1, last_include_pos)
+        ]
+    );
+    assert_eq!(output.steps[1].span().lo, a.find("hold").unwrap());
+
+    let errors = &output.errors;
+    assert_eq!(errors.len(), 5, "{errors:#?}");
+    assert!(matches!(&errors[0].error, Error::IncludeCycle(x) if x == "a.txt"));
+    assert_eq!(errors[0].file, 2);
+    assert!(matches!(&errors[1].error, Error::IncludeNotFound(x) if x == "missing.txt"));
+    assert_eq!(errors[1].file, 0);
+    assert_eq!(errors[1].span.0, script.find("include \"missing").unwrap());
+
+    // errors in included scripts are also reported at the include
+    assert!(matches!(&errors[2].error, Error::IncludeHasErrors(x) if x == "a.txt"));
+    assert_eq!((errors[2].file, errors[2].span.0), (0, include_pos));
+    assert!(matches!(&errors[3].error, Error::IncludeHasErrors(x) if x == "a.txt"));
+    assert_eq!((errors[3].file, errors[3].span.0), (0, last_include_pos));
+    assert!(matches!(&errors[4].error, Error::IncludeHasErrors(x) if x == "b.txt"));
+    assert_eq!((errors[4].file, errors[4].span.0), (1, 0));
+
+    // errors from executing an included step are also located at the include
+    let step = &output.steps[1];
+    let error = skybook_parser::ErrorReport::error(step.span(), Error::NestedLet);
+    let located = step
+        .locate_error(error)
+        .map(|x| (x.file, x.span.0))
+        .collect::<Vec<_>>();
+    assert_eq!(located, vec![(1, step.span().lo), (0, include_pos)]);
+    let error = skybook_parser::ErrorReport::error(output.steps[0].span(), Error::NestedLet);
+    assert_eq!(output.steps[0].locate_error(error).count(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn parse_dlc() -> anyhow::Result<()> {
+    use skybook_parser::Error;
+    use skybook_parser::cir::{Command, Trial};
+
+    let script = indoc! {r#"
+        :dlc 0
+        enter tots
+        :dlc 4
+        :dlc 3
+        enter thunderblight-refight
+    "#};
+    let output = skybook_parser::parse(&StubQuotedItemResolver, script).await;
+    let commands = output.steps.iter().map(|x| x.command()).collect::<Vec<_>>();
+    assert_eq!(
+        commands,
+        vec![
+            &Command::CoDlc(0),
+            &Command::Enter(Trial::SwordNext),
+            &Command::CoDlc(3),
+            &Command::Enter(Trial::RefightThunder),
+        ]
+    );
+
+    let errors = &output.errors;
+    assert_eq!(errors.len(), 1, "{errors:#?}");
+    assert!(matches!(&errors[0].error, Error::InvalidDlcVersion(4)));
+    assert_eq!(errors[0].span.0, script.find('4').unwrap());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn parse_wait() -> anyhow::Result<()> {
+    use skybook_parser::cir::Command;
+
+    let script = indoc! {r#"
+        wait 2 minutes
+        wait 2 in-game hours
+        wait 30 in-game seconds
+        wait 61 in-game minutes
+        wait 0 in-game seconds
+    "#};
+    let output = skybook_parser::parse(&StubQuotedItemResolver, script).await;
+    assert!(output.errors.is_empty(), "{:#?}", output.errors);
+    let commands = output.steps.iter().map(|x| x.command()).collect::<Vec<_>>();
+    assert_eq!(
+        commands,
+        vec![
+            &Command::Wait(120),
+            &Command::Wait(120),
+            &Command::Wait(1),
+            &Command::Wait(61),
+            &Command::Wait(0),
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn parse_receive() -> anyhow::Result<()> {
+    use skybook_parser::Error;
+    use skybook_parser::cir::{Command, ReceiveSource as S, ReceiveTiming as T};
+
+    let script = indoc! {r#"
+        receive apple from chest
+        receive apple from npc
+        receive apple from chest-after
+        receive apple from npc-before
+        receive apple from Chest-Before
+        receive apple from npc-after
+    "#};
+    let output = skybook_parser::parse(&StubQuotedItemResolver, script).await;
+    assert!(output.errors.is_empty(), "{:#?}", output.errors);
+    let sources = output
+        .steps
+        .iter()
+        .map(|x| match x.command() {
+            Command::Receive(_, source, timing) => (*source, *timing),
+            other => panic!("unexpected command: {other:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sources,
+        vec![
+            (S::Chest, T::BeforeClose),
+            (S::Npc, T::AfterClose),
+            (S::Chest, T::AfterClose),
+            (S::Npc, T::BeforeClose),
+            (S::Chest, T::BeforeClose),
+            (S::Npc, T::AfterClose),
+        ]
+    );
+
+    let script = "receive apple from npc-during";
+    let output = skybook_parser::parse(&StubQuotedItemResolver, script).await;
+    let errors = &output.errors;
+    assert_eq!(errors.len(), 1, "{errors:#?}");
+    assert!(matches!(&errors[0].error, Error::InvalidReceiveSource(x) if x == "npc-during"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn parse_ground_limit() -> anyhow::Result<()> {
+    use skybook_parser::cir::{Command, GroundLimitMeta};
+
+    let script = indoc! {r#"
+        :ground-limit [arrows=5]
+        :ground-limit [materials=3, weapons=0, arrow=0]
+    "#};
+    let output = skybook_parser::parse(&StubQuotedItemResolver, script).await;
+    assert!(output.errors.is_empty(), "{:#?}", output.errors);
+    let commands = output.steps.iter().map(|x| x.command()).collect::<Vec<_>>();
+    assert_eq!(
+        commands,
+        vec![
+            &Command::CoGroundLimit(Box::new(GroundLimitMeta {
+                materials: None,
+                weapons: None,
+                arrows: Some(5),
+            })),
+            &Command::CoGroundLimit(Box::new(GroundLimitMeta {
+                materials: Some(3),
+                weapons: Some(0),
+                arrows: Some(0),
+            })),
+        ]
+    );
+
+    let output = skybook_parser::parse(&StubQuotedItemResolver, ":ground-limit [arrows=-1]").await;
+    assert_eq!(output.errors.len(), 1, "{:#?}", output.errors);
+
+    Ok(())
+}
+
 struct StubQuotedItemResolver;
 impl QuotedItemResolver for StubQuotedItemResolver {
     type Future = std::future::Ready<Option<ResolvedItem>>;
@@ -0,0 +1,41 @@
+use skybook_parser::search::{suggest_from, suggest_item_by_ident};
+
+#[test]
+fn test_suggest_item() {
+    assert_eq!(suggest_item_by_ident("royalclaymre"), vec!["royalclaymore"]);
+    assert_eq!(
+        suggest_item_by_ident("royal-claymre"),
+        vec!["royalclaymore"]
+    );
+    assert_eq!(
+        suggest_item_by_ident("travelersword"),
+        vec!["travelerssword", "travelersbow"]
+    );
+    assert_eq!(
+        suggest_item_by_ident("hylianshrom"),
+        vec!["hylianshroom", "hylianhood"]
+    );
+    assert!(suggest_item_by_ident("smug").is_empty());
+    assert!(suggest_item_by_ident("").is_empty());
+}
+
+#[test]
+fn test_suggest_item_effect() {
+    assert_eq!(
+        suggest_item_by_ident("hasty-mushroomskewr"),
+        vec!["hasty-mushroomskewer"]
+    );
+}
+
+#[test]
+fn test_suggest_from() {
+    let candidates = ["eventide", "final-trial", "middle-trial"];
+    assert_eq!(suggest_from("evntide", candidates), vec!["eventide"]);
+    assert_eq!(suggest_from("Final_Trail", candidates), vec!["final-trial"]);
+    assert_eq!(
+        suggest_from("midle-trial", candidates),
+        vec!["middle-trial"]
+    );
+    assert!(suggest_from("xyz", candidates).is_empty());
+    assert!(suggest_from("", candidates).is_empty());
+}
@@ -6,6 +6,8 @@ use std::sync::atomic::AtomicBool;
 
 use anyhow::Context;
 use skybook_parser::ParseOutput;
+use skybook_parser::include::FsScriptLoader;
+use skybook_parser::search::LocalizedItemResolver;
 use skybook_parser::search::QuotedItemResolver;
 use skybook_parser::search::ResolvedItem;
 use skybook_runtime::MaybeAborted;
@@ -71,11 +73,13 @@ async fn run_tests(
     test_names: Vec<String>,
     refresh: bool,
 ) -> anyhow::Result<usize> {
+    let resolver = TestQuotedItemResolver::new();
+    let loader = FsScriptLoader::new("src/script_tests");
     let mut handles = vec![];
     for test in test_names {
         let test_file = std::fs::read_to_string(format!("src/script_tests/{test}.txt"))
             .context("cannot read test file")?;
-        let parsed = skybook_parser::parse(&StubQuotedItemResolver, &test_file).await;
+        let parsed = skybook_parser::parse_with_loader(&resolver, &loader, &test_file).await;
         if ENCOUNTERED_QUOTED_SEARCH.load(std::sync::atomic::Ordering::SeqCst) {
             ENCOUNTERED_QUOTED_SEARCH.store(false, std::sync::atomic::Ordering::SeqCst);
             log::error!("FAIL {test} - quoted item search not supported");
@@ -113,11 +117,33 @@ async fn run_tests(
 
 static ENCOUNTERED_QUOTED_SEARCH: AtomicBool = AtomicBool::new(false);
 
-struct StubQuotedItemResolver;
-impl QuotedItemResolver for StubQuotedItemResolver {
+/// Path to the localized search data generated by the localization package
+const LOCALIZED_SEARCH_DATA: &str = "../localization/src/generated/item-search.json";
+
+/// Resolver for quoted items in tests
+///
+/// Uses the localized search data if it's generated, otherwise
+/// quoted items are not supported and the test fails
+struct TestQuotedItemResolver(Option<LocalizedItemResolver>);
+impl TestQuotedItemResolver {
+    fn new() -> Self {
+        match LocalizedItemResolver::load(LOCALIZED_SEARCH_DATA) {
+            Ok(resolver) => Self(Some(resolver)),
+            Err(e) => {
+                log::warn!("localized search is not available: {e}");
+                Self(None)
+            }
+        }
+    }
+}
+impl QuotedItemResolver for TestQuotedItemResolver {
     type Future = Pin<Box<dyn Future<Output = Option<ResolvedItem>>>>;
 
     fn resolve_quoted(&self, word: &str) -> Self::Future {
+        if let Some(resolver) = &self.0 {
+            let result = resolver.search(word);
+            return Box::pin(async { result });
+        }
         log::error!("quoted item search is not supported in tests, searching: {word}");
         ENCOUNTERED_QUOTED_SEARCH.store(true, std::sync::atomic::Ordering::Relaxed);
         Box::pin(async { None })
@@ -144,6 +170,13 @@ async fn run_test(
         return Ok(false);
     };
 
+    // spans in included scripts are in those scripts, see ParseOutput::files
+    let mut sources = vec![test_script.to_string()];
+    for file in &parsed_output.files {
+        sources
+            .push(std::fs::read_to_string(format!("src/script_tests/{file}")).unwrap_or_default());
+    }
+
     // also write diagnostics into output
     for error in &parsed_output.errors {
         let prefix = if error.is_warning {
@@ -154,7 +187,7 @@ async fn run_test(
         new_snapshot += &format!("{}: {}\n", prefix, error.error);
         new_snapshot += &format!("  span: {}..{}\n", error.span.0, error.span.1);
         new_snapshot += "-----\n";
-        new_snapshot += &test_script[error.span.0..error.span.1];
+        new_snapshot += &sources[error.file as usize][error.span.0..error.span.1];
         new_snapshot += "\n-----\n";
     }
     for error in output.errors {
@@ -166,7 +199,7 @@ async fn run_test(
         new_snapshot += &format!("{}: {}\n", prefix, error.error);
         new_snapshot += &format!("  span: {}..{}\n", error.span.0, error.span.1);
         new_snapshot += "-----\n";
-        new_snapshot += &test_script[error.span.0..error.span.1];
+        new_snapshot += &sources[error.file as usize][error.span.0..error.span.1];
         new_snapshot += "\n-----\n";
     }
 
@@ -177,7 +210,7 @@ async fn run_test(
     for (i, step) in parsed_output.steps.iter().enumerate() {
         let span = step.span();
         new_snapshot += "\n";
-        let script = &test_script[span.lo..span.hi];
+        let script = &sources[step.file() as usize][span.lo..span.hi];
         new_snapshot += &format!("----- Step[{i}]: {script}");
         new_snapshot += "\n\n";
 
@@ -81,6 +81,9 @@ pub fn setup_test_process() -> anyhow::Result<Arc<sim::Runtime>> {
                 stack_size: 0,
                 heap_free_size: 0,
                 pmdm_addr: "0x0000002222200000".to_string(),
+                features: vec![],
+                symbol_map: String::new(),
+                trace: Default::default(),
             }),
         )
         .context("failed to initialize runtime")?;
@@ -0,0 +1,41 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use js_sys::{Function, Promise};
+use skybook_parser::include::ScriptLoader;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// ScriptLoader implementation that delegates to JS
+#[derive(Clone)]
+pub struct JsScriptLoader {
+    delegate: Function,
+}
+
+impl JsScriptLoader {
+    pub fn new(delegate: Function) -> Self {
+        Self { delegate }
+    }
+
+    pub async fn call(self, path: String) -> Option<String> {
+        self.call_internal(&path).await.ok().flatten()
+    }
+    pub async fn call_internal(&self, path: &str) -> Result<Option<String>, JsValue> {
+        let result = self
+            .delegate
+            .call1(&JsValue::NULL, &JsValue::from_str(path))?;
+        // delegate must return a promise
+        let promise = result.dyn_into::<Promise>()?;
+        let result = JsFuture::from(promise).await?;
+        // null, undefined or non-string means the script cannot be loaded
+        Ok(result.as_string())
+    }
+}
+
+impl ScriptLoader for JsScriptLoader {
+    type Future = Pin<Box<dyn Future<Output = Option<String>>>>;
+
+    fn load_script(&self, path: &str) -> Self::Future {
+        Box::pin(self.clone().call(path.to_string()))
+    }
+}
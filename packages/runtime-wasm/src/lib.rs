@@ -6,10 +6,10 @@ use std::sync::Arc;
 use blueflame::env::GameVer;
 use js_sys::{Function, Promise, Uint8Array};
 use serde::{Deserialize, Serialize};
-use skybook_parser::{ParseOutput, search};
+use skybook_parser::{ParseOutput, StepDisplay, search};
 use skybook_runtime::exec::Spawner;
 use skybook_runtime::iv;
-use skybook_runtime::sim::{self, RuntimeInitParams};
+use skybook_runtime::sim::{self, RuntimeInitParams, RuntimeTraceParams, RuntimeWatchpoint};
 use skybook_runtime::{MaybeAborted, RuntimeInitError, RuntimeViewError};
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
@@ -18,6 +18,8 @@ mod interop;
 
 mod js_item_resolve;
 use js_item_resolve::JsQuotedItemResolver;
+mod js_script_load;
+use js_script_load::JsScriptLoader;
 use wasm_bindgen_futures::JsFuture;
 
 thread_local! {
@@ -107,6 +109,71 @@ pub fn init_runtime(
     })
 }
 
+/// Start recording a structured execution trace for the steps executed
+/// from now on, or stop recording if nothing to trace is specified
+///
+/// The state cache is cleared, so the next run executes all the steps
+#[wasm_bindgen]
+pub fn set_trace(params: RuntimeTraceParams) -> interop::Result<(), RuntimeInitError> {
+    let config = match sim::Tracing::config_from_params(&params) {
+        Ok(x) => x,
+        Err(range) => return interop::Result::Err(RuntimeInitError::InvalidTraceRange(range)),
+    };
+    RUNTIME.with(|runtime| {
+        runtime
+            .get()
+            .expect("set_trace called before module_init")
+            .set_tracing(config);
+    });
+    interop::Result::Ok(())
+}
+
+/// Replace the memory watchpoints. The hits are reported
+/// for each step with [`get_watch_hits`]
+///
+/// The state cache is cleared, so the next run executes all the steps
+#[wasm_bindgen]
+pub fn set_watchpoints(
+    watchpoints: Vec<RuntimeWatchpoint>,
+) -> interop::Result<(), RuntimeInitError> {
+    RUNTIME
+        .with(|runtime| {
+            runtime
+                .get()
+                .expect("set_watchpoints called before module_init")
+                .set_watchpoints_from_params(&watchpoints)
+        })
+        .into()
+}
+
+/// Trace recorded when executing a step, see [`take_traces`]
+#[derive(Debug, Clone, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub struct StepTraceEntry {
+    /// Byte position of the step in the script
+    pos: usize,
+    /// The recorded trace, readable with `blueflame-trace`
+    data: Vec<u8>,
+}
+
+/// Take out the traces recorded since the last call
+#[wasm_bindgen]
+pub fn take_traces() -> Vec<StepTraceEntry> {
+    RUNTIME.with(|runtime| {
+        runtime
+            .get()
+            .expect("take_traces called before module_init")
+            .take_traces()
+            .into_iter()
+            .map(|x| StepTraceEntry {
+                pos: x.pos,
+                data: x.data,
+            })
+            .collect()
+    })
+}
+
 //////////// Item Resolver //////////
 
 #[derive(Debug, Clone, Serialize, Deserialize, Tsify)]
@@ -133,12 +200,19 @@ pub fn resolve_item_ident(query: String) -> Vec<ItemSearchResult> {
 
 /// Parse the script
 ///
+/// `load_script` is called with the path to load scripts included with `include`
+///
 /// ## Pointer Ownership
 /// The ParseOutput object is leaked into the caller. call [`free_parse_output`] to reclaim
 #[wasm_bindgen]
-pub async fn parse_script(script: String, resolve_quoted_item: Function) -> *const ParseOutput {
+pub async fn parse_script(
+    script: String,
+    resolve_quoted_item: Function,
+    load_script: Function,
+) -> *const ParseOutput {
     let resolver = JsQuotedItemResolver::new(resolve_quoted_item);
-    let parse_output = skybook_parser::parse(&resolver, &script).await;
+    let loader = JsScriptLoader::new(load_script);
+    let parse_output = skybook_parser::parse_with_loader(&resolver, &loader, &script).await;
     ParseOutput::leak(Arc::new(parse_output))
 }
 
@@ -197,6 +271,15 @@ pub fn get_step_from_pos(parse_output_ref: *const ParseOutput, pos: usize) -> us
         .unwrap_or_default()
 }
 
+/// Get index of the step from the name of a label in the script
+///
+/// undefined is returned if the label is not defined, or there are no steps
+/// before the label (i.e. the state at the label is the initial state)
+#[wasm_bindgen]
+pub fn get_step_from_label(parse_output_ref: *const ParseOutput, label: String) -> Option<usize> {
+    unsafe_deref_parse_output!(parse_output_ref).step_idx_from_label(&label)
+}
+
 /// Get the starting index for each step
 ///
 /// ## Pointer Ownership
@@ -210,6 +293,93 @@ pub fn get_step_byte_positions(parse_output_ref: *const ParseOutput) -> Vec<u32>
         .collect()
 }
 
+/// Get the index of the first step in each page
+///
+/// ## Pointer Ownership
+/// Borrows the ParseOutput pointer.
+#[wasm_bindgen]
+pub fn get_step_pages(parse_output_ref: *const ParseOutput) -> Vec<u32> {
+    unsafe_deref_parse_output!(parse_output_ref)
+        .pages
+        .iter()
+        .map(|x| *x as u32)
+        .collect()
+}
+
+/// One entry in the step list, see [`get_step_display`]
+#[derive(Debug, Clone, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum StepDisplayEntry {
+    /// Text from notes or page titles, displayed before the step
+    Text { text: String, step: usize },
+    /// Commands starting at the step, until the step of the next entry
+    Command { step: usize },
+}
+
+/// Get the list of steps to display in the step list
+///
+/// ## Pointer Ownership
+/// Borrows the ParseOutput pointer.
+#[wasm_bindgen]
+pub fn get_step_display(parse_output_ref: *const ParseOutput) -> Vec<StepDisplayEntry> {
+    unsafe_deref_parse_output!(parse_output_ref)
+        .display
+        .iter()
+        .map(|(display, step)| match display {
+            StepDisplay::Text(text) => StepDisplayEntry::Text {
+                text: text.clone(),
+                step: *step,
+            },
+            StepDisplay::Command => StepDisplayEntry::Command { step: *step },
+        })
+        .collect()
+}
+
+/// Get the note in effect for each step, in markdown.
+/// The note is empty if the step does not have a note
+///
+/// ## Pointer Ownership
+/// Borrows the ParseOutput pointer.
+#[wasm_bindgen]
+pub fn get_step_notes(parse_output_ref: *const ParseOutput) -> Vec<String> {
+    unsafe_deref_parse_output!(parse_output_ref)
+        .steps
+        .iter()
+        .map(|x| x.notes.to_string())
+        .collect()
+}
+
+/// Consecutive steps that share the same note, see [`get_note_sections`]
+#[derive(Debug, Clone, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteSection {
+    /// The note in markdown
+    note: String,
+    /// Index of the first step in the section
+    start: usize,
+    /// Index after the last step in the section
+    end: usize,
+}
+
+/// Get the sections of steps that have notes
+///
+/// ## Pointer Ownership
+/// Borrows the ParseOutput pointer.
+#[wasm_bindgen]
+pub fn get_note_sections(parse_output_ref: *const ParseOutput) -> Vec<NoteSection> {
+    unsafe_deref_parse_output!(parse_output_ref)
+        .note_sections()
+        .into_iter()
+        .map(|x| NoteSection {
+            note: x.note.to_string(),
+            start: x.steps.start,
+            end: x.steps.end,
+        })
+        .collect()
+}
+
 ////////// Runtime //////////
 
 /// Make a run handle that you can pass back into run_parsed
@@ -312,6 +482,41 @@ pub fn get_run_errors(run_output_ref: *const sim::RunOutput) -> Vec<skybook_runt
     run_output.errors.clone()
 }
 
+/// How a step was run, see [`get_step_runs`]
+#[derive(Debug, Clone, Serialize, Tsify)]
+#[tsify(into_wasm_abi)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum StepRunEntry {
+    /// The step was executed
+    Executed,
+    /// The step is in a branch of an `if` that was not taken
+    Skipped,
+    /// The step is an `if`, with the index of the branch taken
+    Branch { branch: Option<u32> },
+}
+
+/// Get how each step was run, which shows the branches taken
+/// in `if` statements
+///
+/// ## Pointer Ownership
+/// Borrows the RunOutput pointer.
+#[wasm_bindgen]
+pub fn get_step_runs(run_output_ref: *const sim::RunOutput) -> Vec<StepRunEntry> {
+    if run_output_ref.is_null() {
+        return Vec::new();
+    }
+    let run_output = unsafe { &*run_output_ref };
+    run_output
+        .runs
+        .iter()
+        .map(|run| match run {
+            sim::StepRun::Executed => StepRunEntry::Executed,
+            sim::StepRun::Skipped => StepRunEntry::Skipped,
+            sim::StepRun::Branch(branch) => StepRunEntry::Branch { branch: *branch },
+        })
+        .collect()
+}
+
 macro_rules! deref_with_step {
     ($run:ident, $parse:ident, $pos:ident) => {{
         if $parse.is_null() || $run.is_null() {
@@ -324,6 +529,19 @@ macro_rules! deref_with_step {
         // so the reference will always be valid in the function
         (run_output, step)
     }};
+    ($run:ident, $parse:ident, label $label:ident) => {{
+        if $parse.is_null() || $run.is_null() {
+            return Default::default();
+        }
+        let parse_output = unsafe { &*$parse };
+        // the default (empty) view is returned if the label is not defined,
+        // or if there are no steps before it, since nothing is in the initial state
+        let Some(step) = parse_output.step_idx_from_label(&$label) else {
+            return Default::default();
+        };
+        let run_output = unsafe { &*$run };
+        (run_output, step)
+    }};
 }
 
 /// Get the Pouch inventory view for the given byte position in the script
@@ -368,6 +586,48 @@ pub fn get_overworld_items(
     run_output.get_overworld_items(step).into()
 }
 
+/// Get the Pouch inventory view at the label in the script
+///
+/// ## Pointer Ownership
+/// Borrows both the RunOutput and ParseOutput pointers.
+#[wasm_bindgen]
+pub fn get_pouch_list_at_label(
+    run_output_ref: *const sim::RunOutput,
+    parse_output_ref: *const ParseOutput,
+    label: String,
+) -> interop::Result<iv::PouchList, RuntimeViewError> {
+    let (run_output, step) = deref_with_step!(run_output_ref, parse_output_ref, label label);
+    run_output.get_pouch_list(step).into()
+}
+
+/// Get the GDT inventory view at the label in the script
+///
+/// ## Pointer Ownership
+/// Borrows both the RunOutput and ParseOutput pointers.
+#[wasm_bindgen]
+pub fn get_gdt_inventory_at_label(
+    run_output_ref: *const sim::RunOutput,
+    parse_output_ref: *const ParseOutput,
+    label: String,
+) -> interop::Result<iv::Gdt, RuntimeViewError> {
+    let (run_output, step) = deref_with_step!(run_output_ref, parse_output_ref, label label);
+    run_output.get_gdt_inventory(step).into()
+}
+
+/// Get the overworld items at the label in the script
+///
+/// ## Pointer Ownership
+/// Borrows both the RunOutput and ParseOutput pointers.
+#[wasm_bindgen]
+pub fn get_overworld_items_at_label(
+    run_output_ref: *const sim::RunOutput,
+    parse_output_ref: *const ParseOutput,
+    label: String,
+) -> interop::Result<iv::Overworld, RuntimeViewError> {
+    let (run_output, step) = deref_with_step!(run_output_ref, parse_output_ref, label label);
+    run_output.get_overworld_items(step).into()
+}
+
 /// Get the crash info at the given byte position, empty if no crash
 ///
 /// ## Pointer Ownership
@@ -385,6 +645,36 @@ pub fn get_crash_info(
         .unwrap_or_default()
 }
 
+/// Get the memory accesses that hit watchpoints in the step at the given byte position,
+/// formatted with the stack traces
+///
+/// ## Pointer Ownership
+/// Borrows both the RunOutput and ParseOutput pointers.
+#[wasm_bindgen]
+pub fn get_watch_hits(
+    run_output_ref: *const sim::RunOutput,
+    parse_output_ref: *const ParseOutput,
+    byte_pos: usize,
+) -> Vec<String> {
+    let (run_output, step) = deref_with_step!(run_output_ref, parse_output_ref, byte_pos);
+    run_output.get_watch_hits_formatted(step)
+}
+
+/// Export the game process at the given byte position as an ELF core file,
+/// empty if the game is not running
+///
+/// ## Pointer Ownership
+/// Borrows both the RunOutput and ParseOutput pointers.
+#[wasm_bindgen]
+pub fn get_core_dump(
+    run_output_ref: *const sim::RunOutput,
+    parse_output_ref: *const ParseOutput,
+    byte_pos: usize,
+) -> Vec<u8> {
+    let (run_output, step) = deref_with_step!(run_output_ref, parse_output_ref, byte_pos);
+    run_output.get_core_dump(step).unwrap_or_default()
+}
+
 /// Get the list of save names for the given byte position in the script.
 /// Does not include the manual save in the output
 ///
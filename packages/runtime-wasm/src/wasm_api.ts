@@ -11,8 +11,13 @@ import type {
     MaybeAborted,
     RuntimeInitParams,
     RuntimeInitError,
+    RuntimeTraceParams,
     RuntimeViewError,
+    RuntimeWatchpoint,
     RuntimeError,
+    StepDisplayEntry,
+    NoteSection,
+    StepRunEntry,
 } from "@pistonite/skybook-api";
 import {
     crashApplication,
@@ -20,6 +25,8 @@ import {
     type Pwr,
     type NativeApi,
     type QuotedItemResolverFn,
+    type ScriptLoaderFn,
+    type StepTraceEntry,
     type NativeEmpFactory,
 } from "skybook-runtime-worker";
 
@@ -81,6 +88,28 @@ export class WasmApi implements NativeApi<number> {
         return result;
     }
 
+    public setTrace(
+        params: RuntimeTraceParams,
+    ): Pwr<Result<void, RuntimeInitError>> {
+        return this.exec(() => {
+            return wasm_bindgen.set_trace(params);
+        });
+    }
+
+    public setWatchpoints(
+        watchpoints: RuntimeWatchpoint[],
+    ): Pwr<Result<void, RuntimeInitError>> {
+        return this.exec(() => {
+            return wasm_bindgen.set_watchpoints(watchpoints);
+        });
+    }
+
+    public takeTraces(): Pwr<StepTraceEntry[]> {
+        return this.exec(() => {
+            return wasm_bindgen.take_traces();
+        });
+    }
+
     // Note that due to type issues with wasm-bindgen
     // TypeScript may not report errors here! be careful!
 
@@ -93,9 +122,14 @@ export class WasmApi implements NativeApi<number> {
     public parseScript(
         script: string,
         resolveQuotedItem: QuotedItemResolverFn,
+        loadScript: ScriptLoaderFn,
     ): Pwr<number> {
         return this.exec(() => {
-            return wasm_bindgen.parse_script(script, resolveQuotedItem);
+            return wasm_bindgen.parse_script(
+                script,
+                resolveQuotedItem,
+                loadScript,
+            );
         });
     }
 
@@ -127,12 +161,45 @@ export class WasmApi implements NativeApi<number> {
         });
     }
 
+    public getStepFromLabel(
+        ptr: number,
+        label: string,
+    ): Pwr<number | undefined> {
+        return this.exec(() => {
+            return wasm_bindgen.get_step_from_label(ptr, label);
+        });
+    }
+
     public getStepBytePositions(ptr: number): Pwr<Uint32Array> {
         return this.exec(() => {
             return wasm_bindgen.get_step_byte_positions(ptr);
         });
     }
 
+    public getStepPages(ptr: number): Pwr<Uint32Array> {
+        return this.exec(() => {
+            return wasm_bindgen.get_step_pages(ptr);
+        });
+    }
+
+    public getStepDisplay(ptr: number): Pwr<StepDisplayEntry[]> {
+        return this.exec(() => {
+            return wasm_bindgen.get_step_display(ptr);
+        });
+    }
+
+    public getStepNotes(ptr: number): Pwr<string[]> {
+        return this.exec(() => {
+            return wasm_bindgen.get_step_notes(ptr);
+        });
+    }
+
+    public getNoteSections(ptr: number): Pwr<NoteSection[]> {
+        return this.exec(() => {
+            return wasm_bindgen.get_note_sections(ptr);
+        });
+    }
+
     public makeTaskHandle(): Pwr<number> {
         return this.exec(() => {
             return wasm_bindgen.make_task_handle();
@@ -165,6 +232,12 @@ export class WasmApi implements NativeApi<number> {
         });
     }
 
+    public getStepRuns(ptr: number): Pwr<StepRunEntry[]> {
+        return this.exec(() => {
+            return wasm_bindgen.get_step_runs(ptr);
+        });
+    }
+
     public getPouchList(
         runOutputPtr: number,
         parseOutputPtr: number,
@@ -207,6 +280,48 @@ export class WasmApi implements NativeApi<number> {
         });
     }
 
+    public getPouchListAtLabel(
+        runOutputPtr: number,
+        parseOutputPtr: number,
+        label: string,
+    ): Pwr<Result<InvView_PouchList, RuntimeViewError>> {
+        return this.exec(() => {
+            return wasm_bindgen.get_pouch_list_at_label(
+                runOutputPtr,
+                parseOutputPtr,
+                label,
+            );
+        });
+    }
+
+    public getGdtInventoryAtLabel(
+        runOutputPtr: number,
+        parseOutputPtr: number,
+        label: string,
+    ): Pwr<Result<InvView_Gdt, RuntimeViewError>> {
+        return this.exec(() => {
+            return wasm_bindgen.get_gdt_inventory_at_label(
+                runOutputPtr,
+                parseOutputPtr,
+                label,
+            );
+        });
+    }
+
+    public getOverworldItemsAtLabel(
+        runOutputPtr: number,
+        parseOutputPtr: number,
+        label: string,
+    ): Pwr<Result<InvView_Overworld, RuntimeViewError>> {
+        return this.exec(() => {
+            return wasm_bindgen.get_overworld_items_at_label(
+                runOutputPtr,
+                parseOutputPtr,
+                label,
+            );
+        });
+    }
+
     public getCrashInfo(
         runOutputPtr: number,
         parseOutputPtr: number,
@@ -221,6 +336,34 @@ export class WasmApi implements NativeApi<number> {
         });
     }
 
+    public getWatchHits(
+        runOutputPtr: number,
+        parseOutputPtr: number,
+        bytePos: number,
+    ): Pwr<string[]> {
+        return this.exec(() => {
+            return wasm_bindgen.get_watch_hits(
+                runOutputPtr,
+                parseOutputPtr,
+                bytePos,
+            );
+        });
+    }
+
+    public getCoreDump(
+        runOutputPtr: number,
+        parseOutputPtr: number,
+        bytePos: number,
+    ): Pwr<Uint8Array> {
+        return this.exec(() => {
+            return wasm_bindgen.get_core_dump(
+                runOutputPtr,
+                parseOutputPtr,
+                bytePos,
+            );
+        });
+    }
+
     public getSaveNames(
         runOutputPtr: number,
         parseOutputPtr: number,
@@ -17,6 +17,7 @@ import type { RunMgr } from "./run_mgr.ts";
 import type { ImageMgr } from "./image_mgr.ts";
 import { unwrap, type Pwr, unwrapMaybeAborted } from "./error.ts";
 import { getCustomBlueFlameImage } from "./app_call.ts";
+import { setIncludedScripts } from "./script_fs.ts";
 
 export const createRuntimeWorker = <TPtr>(
     napi: NativeApi<TPtr>,
@@ -36,6 +37,18 @@ export const createRuntimeWorker = <TPtr>(
         resolveItemIdent: async (query) => {
             return { val: unwrap(await napi.resolveItemIdent(query)) };
         },
+        setWatchpoints: async (watchpoints) => {
+            const result = unwrap(await napi.setWatchpoints(watchpoints));
+            if (!result.err) {
+                runMgr.invalidateCache();
+            }
+            return { val: result };
+        },
+        setIncludedScripts: wxWrapHandler((scripts) => {
+            setIncludedScripts(scripts);
+            parseMgr.invalidateCache();
+            runMgr.invalidateCache();
+        }),
         getParserDiagnostics: async (script) => {
             return { val: unwrap(await parseMgr.getParserDiagnostics(script)) };
         },
@@ -63,6 +76,13 @@ export const createRuntimeWorker = <TPtr>(
                 ),
             };
         },
+        getStepRuns: async (script, taskId, bytePos) => {
+            return {
+                val: unwrapMaybeAborted(
+                    await runMgr.getStepRuns(script, taskId, bytePos),
+                ),
+            };
+        },
         getPouchList: async (script, taskId, pos) => {
             return {
                 val: unwrapMaybeAborted(
@@ -91,6 +111,13 @@ export const createRuntimeWorker = <TPtr>(
                 ),
             };
         },
+        getWatchHits: async (script, taskId, pos) => {
+            return {
+                val: unwrapMaybeAborted(
+                    await runMgr.getWatchHits(script, taskId, pos),
+                ),
+            };
+        },
         getSaveNames: async (script, taskId, pos) => {
             return {
                 val: unwrapMaybeAborted(
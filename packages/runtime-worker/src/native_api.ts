@@ -11,8 +11,13 @@ import type {
     ParserError,
     RuntimeInitError,
     RuntimeInitParams,
+    RuntimeTraceParams,
     RuntimeViewError,
+    RuntimeWatchpoint,
     RuntimeError,
+    StepDisplayEntry,
+    NoteSection,
+    StepRunEntry,
 } from "@pistonite/skybook-api";
 
 import type { Pwr } from "./error.ts";
@@ -21,6 +26,19 @@ export type QuotedItemResolverFn = (
     query: string,
 ) => Promise<ItemSearchResult | undefined | null>;
 
+/** Load the script included with `include`, undefined if it cannot be loaded */
+export type ScriptLoaderFn = (
+    path: string,
+) => Promise<string | undefined | null>;
+
+/** Execution trace recorded for a step */
+export type StepTraceEntry = {
+    /** Byte position of the step in the script */
+    pos: number;
+    /** The recorded trace, readable with blueflame-trace */
+    data: number[];
+};
+
 export type RuntimeInitOutput = {
     /** Custom image version initialized, should be in the form of "X.X.X" */
     gameVersion: string;
@@ -39,6 +57,22 @@ export interface NativeApiFunctions<TPtr> {
         customImageParams: RuntimeInitParams | undefined,
     ): Pwr<Result<RuntimeInitOutput, RuntimeInitError>>;
 
+    /**
+     * Start recording execution traces for the steps executed from now on,
+     * or stop if nothing to trace is specified
+     */
+    setTrace(
+        params: RuntimeTraceParams,
+    ): Pwr<Result<void, RuntimeInitError>>;
+
+    /** Replace the memory watchpoints. Hits are reported for each step with getWatchHits */
+    setWatchpoints(
+        watchpoints: RuntimeWatchpoint[],
+    ): Pwr<Result<void, RuntimeInitError>>;
+
+    /** Take out the execution traces recorded since the last call */
+    takeTraces(): Pwr<StepTraceEntry[]>;
+
     // === item api ===
     resolveItemIdent: (query: string) => Pwr<ItemSearchResult[]>;
 
@@ -48,6 +82,7 @@ export interface NativeApiFunctions<TPtr> {
     parseScript(
         script: string,
         resolveQuotedItem: QuotedItemResolverFn,
+        loadScript: ScriptLoaderFn,
     ): Pwr<TPtr>;
     /**
      * Parse the semantics of the script in the given range
@@ -70,9 +105,30 @@ export interface NativeApiFunctions<TPtr> {
      */
     getStepFromPos(ptr: TPtr, bytePos: number): Pwr<number>;
 
+    /**
+     * Get the step index from the name of a label in the script in the parse output.
+     *
+     * Returns undefined if the label is not defined, or there are no steps
+     * before the label (the state at the label is the initial state).
+     * Does not consume the ptr.
+     */
+    getStepFromLabel(ptr: TPtr, label: string): Pwr<number | undefined>;
+
     /** Get the start byte positions for each step, does not consume the ptr */
     getStepBytePositions(ptr: TPtr): Pwr<Uint32Array>;
 
+    /** Get the index of the first step in each page, does not consume the ptr */
+    getStepPages(ptr: TPtr): Pwr<Uint32Array>;
+
+    /** Get the list of steps to display in the step list, does not consume the ptr */
+    getStepDisplay(ptr: TPtr): Pwr<StepDisplayEntry[]>;
+
+    /** Get the note in markdown for each step (empty if none), does not consume the ptr */
+    getStepNotes(ptr: TPtr): Pwr<string[]>;
+
+    /** Get the sections of steps that have notes, does not consume the ptr */
+    getNoteSections(ptr: TPtr): Pwr<NoteSection[]>;
+
     // === run/task api ===
 
     /** Make a new task handle and returns the ptr to it (that must be freed) */
@@ -101,6 +157,9 @@ export interface NativeApiFunctions<TPtr> {
     /** Get the errors from the run output. Does not consume the ptr */
     getRunErrors(ptr: TPtr): Pwr<ErrorReport<RuntimeError>[]>;
 
+    /** Get how each step was run, does not consume the ptr */
+    getStepRuns(ptr: TPtr): Pwr<StepRunEntry[]>;
+
     /**
      * Get the Pouch inventory view for the given byte position in the script.
      * Does not consume either ptr.
@@ -131,6 +190,36 @@ export interface NativeApiFunctions<TPtr> {
         bytePos: number,
     ): Pwr<Result<InvView_Overworld, RuntimeViewError>>;
 
+    /**
+     * Get the Pouch inventory view at the label in the script.
+     * Does not consume either ptr.
+     */
+    getPouchListAtLabel(
+        runOutputPtr: TPtr,
+        parseOutputPtr: TPtr,
+        label: string,
+    ): Pwr<Result<InvView_PouchList, RuntimeViewError>>;
+
+    /**
+     * Get the GDT inventory view at the label in the script.
+     * Does not consume either ptr.
+     */
+    getGdtInventoryAtLabel(
+        runOutputPtr: TPtr,
+        parseOutputPtr: TPtr,
+        label: string,
+    ): Pwr<Result<InvView_Gdt, RuntimeViewError>>;
+
+    /**
+     * Get the overworld items at the label in the script.
+     * Does not consume either ptr.
+     */
+    getOverworldItemsAtLabel(
+        runOutputPtr: TPtr,
+        parseOutputPtr: TPtr,
+        label: string,
+    ): Pwr<Result<InvView_Overworld, RuntimeViewError>>;
+
     /**
      * Get crash info for the given byte position in the script.
      * Does not consume either ptr. Returns empty string if no crash
@@ -141,6 +230,13 @@ export interface NativeApiFunctions<TPtr> {
         bytePos: number,
     ): Pwr<string>;
 
+    /** Get the watchpoint hits in the step. Does not consume either ptr. */
+    getWatchHits(
+        runOutputPtr: TPtr,
+        parseOutputPtr: TPtr,
+        bytePos: number,
+    ): Pwr<string[]>;
+
     /** Get list of saves. Does not consume either ptr. */
     getSaveNames(
         runOutputPtr: TPtr,
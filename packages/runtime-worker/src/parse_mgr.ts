@@ -2,9 +2,15 @@ import type { Result } from "@pistonite/pure/result";
 import type { Emp } from "@pistonite/pure/memory";
 import { scopedCapture } from "@pistonite/pure/sync";
 
-import type { ErrorReport, ParserError } from "@pistonite/skybook-api";
+import type {
+    ErrorReport,
+    ParserError,
+    StepDisplayEntry,
+    NoteSection,
+} from "@pistonite/skybook-api";
 
 import { resolveQuotedItem } from "./app_call.ts";
+import { loadScript } from "./script_fs.ts";
 import type { NativeApi, ParseOutput } from "./native_api.ts";
 import { type Pwr, type WorkerError, nullptrError } from "./error.ts";
 import { log } from "./util.ts";
@@ -30,6 +36,14 @@ export class ParseMgr<TPtr> {
         this.cachedOutput = undefined;
     }
 
+    /**
+     * Make the next call parse the script again even if it didn't change,
+     * for when the included scripts changed
+     */
+    public invalidateCache() {
+        this.lastScript = "";
+    }
+
     /** Wrapper to call parseScript, null check and use the result pointer */
     private async withParseOutput<T>(
         script: string,
@@ -87,6 +101,7 @@ export class ParseMgr<TPtr> {
         const outputRaw = await this.napi.parseScript(
             script,
             resolveQuotedItem,
+            loadScript,
         );
 
         if (outputRaw.err) {
@@ -139,4 +154,28 @@ export class ParseMgr<TPtr> {
             return this.napi.getStepBytePositions(ptr);
         });
     }
+
+    public getStepPages(script: string): Pwr<Uint32Array> {
+        return this.withParseOutput(script, (ptr) => {
+            return this.napi.getStepPages(ptr);
+        });
+    }
+
+    public getStepDisplay(script: string): Pwr<StepDisplayEntry[]> {
+        return this.withParseOutput(script, (ptr) => {
+            return this.napi.getStepDisplay(ptr);
+        });
+    }
+
+    public getStepNotes(script: string): Pwr<string[]> {
+        return this.withParseOutput(script, (ptr) => {
+            return this.napi.getStepNotes(ptr);
+        });
+    }
+
+    public getNoteSections(script: string): Pwr<NoteSection[]> {
+        return this.withParseOutput(script, (ptr) => {
+            return this.napi.getNoteSections(ptr);
+        });
+    }
 }
@@ -9,6 +9,7 @@ import type {
     InvView_PouchList,
     RuntimeError,
     RuntimeViewError,
+    StepRunEntry,
 } from "@pistonite/skybook-api";
 
 import {
@@ -121,6 +122,14 @@ export class RunMgr<TPtr> {
         this.cachedEmp = undefined;
     }
 
+    /**
+     * Make the next call run the script again even if it didn't change,
+     * for when the runtime changed (for example, when the watchpoints are set)
+     */
+    public invalidateCache() {
+        this.lastScript = "";
+    }
+
     /**
      * Wrapper to parse and run the script. If successful, run the function
      * and ensure the references are alive
@@ -502,6 +511,25 @@ export class RunMgr<TPtr> {
         );
     }
 
+    public getWatchHits(
+        script: string,
+        taskId: string,
+        bytePos: number,
+    ): Pwr<string[]> {
+        return this.withParseAndRunOutput(
+            script,
+            taskId,
+            bytePos,
+            (parseOutputBorrowed, runOutputBorrowed) => {
+                return this.napi.getWatchHits(
+                    runOutputBorrowed,
+                    parseOutputBorrowed,
+                    bytePos,
+                );
+            },
+        );
+    }
+
     public getRuntimeDiagnostics(
         script: string,
         taskId: string,
@@ -517,6 +545,21 @@ export class RunMgr<TPtr> {
         );
     }
 
+    public getStepRuns(
+        script: string,
+        taskId: string,
+        bytePos: number,
+    ): Pwr<StepRunEntry[]> {
+        return this.withParseAndRunOutput(
+            script,
+            taskId,
+            bytePos,
+            (_, runOutputBorrowed) => {
+                return this.napi.getStepRuns(runOutputBorrowed);
+            },
+        );
+    }
+
     public getSaveNames(
         script: string,
         taskId: string,
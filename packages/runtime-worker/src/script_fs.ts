@@ -0,0 +1,19 @@
+/** Virtual file system for the scripts included with `include` */
+
+import type { ScriptLoaderFn } from "./native_api.ts";
+
+// the scripts are provided by the app, and the path in
+// `include` must match the path exactly
+let includedScripts: Record<string, string> = {};
+
+/** Replace the scripts that can be included */
+export const setIncludedScripts = (scripts: Record<string, string>) => {
+    includedScripts = scripts;
+};
+
+export const loadScript: ScriptLoaderFn = async (path) => {
+    if (!Object.hasOwn(includedScripts, path)) {
+        return undefined;
+    }
+    return includedScripts[path];
+};
@@ -0,0 +1,129 @@
+use blueflame::game::gdt;
+use blueflame::linker;
+use blueflame::memory::{self, proxy};
+use blueflame::processor::{self, Cpu2};
+use skybook_parser::cir;
+
+use crate::error::{ErrorReport, sim_error};
+use crate::sim;
+
+/// A pouch Hestu can expand
+struct Pouch {
+    /// GDT flag for the number of slots
+    flag_name: &'static str,
+    /// Number of slots before any expansion
+    initial_slots: i32,
+    /// Number of Korok Seeds for each expansion of this pouch, indexed by
+    /// the number of expansions already done for this pouch. The pouch
+    /// is fully expanded after the last entry
+    costs: &'static [i32],
+}
+
+// TODO: the costs are not verified against Hestu's event flow yet
+const WEAPON_POUCH: Pouch = Pouch {
+    flag_name: "WeaponPorchStockNum",
+    initial_slots: 8,
+    costs: &[1, 2, 3, 5, 7, 10, 15, 20, 25, 30, 35, 40],
+};
+const BOW_POUCH: Pouch = Pouch {
+    flag_name: "BowPorchStockNum",
+    initial_slots: 5,
+    costs: &[1, 2, 3, 5, 7, 10, 15, 20, 25],
+};
+const SHIELD_POUCH: Pouch = Pouch {
+    flag_name: "ShieldPorchStockNum",
+    initial_slots: 4,
+    costs: &[1, 2, 3, 5, 7, 10, 15, 20, 25, 30, 35, 40, 50, 60, 70, 80],
+};
+
+const KOROK_SEED: &str = "Obj_KorokNuts";
+
+/// Expand the weapon, bow or shield pouch by 1 slot with Korok Seeds
+///
+/// Like `:slots`, this does not change the current screen, so the
+/// inventory can stay open to see the new capacity
+pub fn expand_pouch(
+    ctx: &mut sim::Context<&mut Cpu2>,
+    sys: &mut sim::GameSystems,
+    errors: &mut Vec<ErrorReport>,
+    category: cir::Category,
+) -> Result<(), processor::Error> {
+    let pouch = match category {
+        cir::Category::Weapon => WEAPON_POUCH,
+        cir::Category::Bow => BOW_POUCH,
+        cir::Category::Shield => SHIELD_POUCH,
+        other => {
+            errors.push(sim_error!(ctx.span, PouchNotExpandable(other)));
+            return Ok(());
+        }
+    };
+    let flag_name = pouch.flag_name;
+
+    let slots = get_slots(ctx, flag_name)?;
+    let expansions = (slots - pouch.initial_slots).max(0) as usize;
+    let Some(&cost) = pouch.costs.get(expansions) else {
+        errors.push(sim_error!(ctx.span, PouchFullyExpanded));
+        return Ok(());
+    };
+
+    let have = {
+        let proc = &ctx.cpu().proc;
+        let inventory = sim::PouchScreen::open_no_exec(proc, true)?;
+        inventory.get_amount(
+            &cir::ItemNameSpec::Actor(KOROK_SEED.to_string()),
+            None,
+            sim::CountingMethod::CanStack,
+            proc.memory(),
+        )?
+    };
+    if have < cost as usize {
+        errors.push(sim_error!(
+            ctx.span,
+            NotEnoughMaterials(KOROK_SEED.to_string(), cost as usize, have)
+        ));
+        return Ok(());
+    }
+
+    log::debug!(
+        "expanding {flag_name} to {} for {cost} korok seeds",
+        slots + 1
+    );
+    for _ in 0..cost {
+        linker::remove_item_by_name(ctx.cpu(), KOROK_SEED)?;
+    }
+    set_slots(ctx, flag_name, slots + 1)?;
+    super::low_level::fix_inventory_state_and_gamedata(ctx)?;
+
+    // refresh the tabs in case the inventory is open, so the new
+    // capacity is used right away
+    if let Some(inventory) = sys.screen.current_screen_mut().as_inventory_mut() {
+        inventory.update_all_items(ctx.cpu(), false)?;
+    }
+    Ok(())
+}
+
+fn get_slots(ctx: &mut sim::Context<&mut Cpu2>, flag_name: &str) -> Result<i32, memory::Error> {
+    let m = ctx.cpu().proc.memory();
+    let gdt_ptr = gdt::trigger_param_ptr(m)?;
+    let proc = &ctx.cpu().proc;
+    proxy! { let gdt = *gdt_ptr as trigger_param in proc };
+    Ok(gdt
+        .by_name::<gdt::fd!(s32)>(flag_name)
+        .map(|flag| *flag.get())
+        .unwrap_or_default())
+}
+
+fn set_slots(
+    ctx: &mut sim::Context<&mut Cpu2>,
+    flag_name: &str,
+    value: i32,
+) -> Result<(), memory::Error> {
+    let m = ctx.cpu().proc.memory();
+    let gdt_ptr = gdt::trigger_param_ptr(m)?;
+    let proc = &mut ctx.cpu().proc;
+    proxy! { let mut gdt = *gdt_ptr as trigger_param in proc };
+    if let Some(flag) = gdt.by_name_mut::<gdt::fd!(s32)>(flag_name) {
+        flag.set(value);
+    }
+    Ok(())
+}
@@ -3,7 +3,7 @@ use blueflame::linker;
 use blueflame::processor::{self, Cpu2};
 use skybook_parser::cir;
 
-use crate::error::{ErrorReport, sim_error};
+use crate::error::{ErrorReport, sim_error, sim_warning};
 use crate::sim;
 
 /// Add items to pouch by eventually calling itemGet or cookItemGet
@@ -31,6 +31,87 @@ pub fn get_items(
     Ok(())
 }
 
+/// Receiving items from a chest or an NPC
+///
+/// This is similar to `get`, but the items come with a dialog, which
+/// cannot be started while holding items (unless they are in the attached
+/// state, in which case they are removed after the dialog).
+///
+/// The dialog of the source is the item text box for a chest, and the talking
+/// dialog for an NPC. Depending on `timing`, the items are added while it is
+/// still open, or after it closes. Either way, the item text box is shown
+/// after the items are added:
+/// - Chest (default before close): the items are added when the chest opens,
+///   before the item text box
+/// - NPC (default after close): the items are added after the talking dialog
+///   closes, then the item text box is shown
+///
+/// The item text box stays open after the command, so the next command has to close
+/// it or open the pause menu on top of it, which is what `:pause-during` does
+#[allow(clippy::too_many_arguments)]
+pub fn receive_items(
+    ctx: &mut sim::Context<&mut Cpu2>,
+    sys: &mut sim::GameSystems,
+    errors: &mut Vec<ErrorReport>,
+    items: &[cir::ItemSpec],
+    source: cir::ReceiveSource,
+    timing: cir::ReceiveTiming,
+    pause_during: bool,
+    accurate: bool,
+) -> Result<(), processor::Error> {
+    super::switch_to_overworld_or_stop!(ctx, sys, errors, "RECEIVE");
+    let dialog = match source {
+        cir::ReceiveSource::Chest => sim::DialogScreen::ItemTextBox,
+        cir::ReceiveSource::Npc => sim::DialogScreen::Talking,
+    };
+    if !sys
+        .screen
+        .transition_to_dialog(ctx, &mut sys.overworld, dialog, errors)?
+    {
+        log::warn!("failed to open dialog for RECEIVE");
+        return Ok(());
+    }
+    if timing == cir::ReceiveTiming::AfterClose {
+        // held items are removed when the dialog closes
+        super::switch_to_overworld_or_stop!(ctx, sys, errors, "RECEIVE");
+    }
+
+    for item in items {
+        get_item_internal(ctx, sys, item, errors, accurate)?;
+        if ctx.is_aborted() {
+            break;
+        }
+    }
+    sys.overworld.despawn_items();
+
+    // show the item text box, which directly follows the dialog if it's
+    // still open (no-op if it's already the item text box)
+    if !sys.screen.transition_to_dialog(
+        ctx,
+        &mut sys.overworld,
+        sim::DialogScreen::ItemTextBox,
+        errors,
+    )? {
+        log::warn!("failed to open item text box for RECEIVE");
+        return Ok(());
+    }
+
+    if pause_during {
+        // the item text box stays open behind the pause menu,
+        // and closes along with it
+        if !sys
+            .screen
+            .transition_to_inventory(ctx, &mut sys.overworld, false, errors)?
+        {
+            log::warn!("failed to open inventory during item text box for RECEIVE");
+            return Ok(());
+        }
+        log::debug!("inventory screen opened during item text box");
+    }
+
+    Ok(())
+}
+
 /// Buying items from shop
 ///
 /// This is very similar to `get`, the only difference being
@@ -73,8 +154,36 @@ pub fn buy_items(
         super::predrop_items!(ctx, sys, errors, "BUY")
     };
 
+    // special vendors have their own prices and currency
+    let vendor = sys.screen.current_screen().vendor();
+    let currency = vendor
+        .map(|v| v.currency.as_str())
+        .unwrap_or("CurrentRupee");
+
     for item in items {
-        get_item_internal(ctx, sys, item, errors, accurate)?;
+        let price = match vendor.map(|v| v.buying_price(&item.name)) {
+            Some(sim::VendorPrice::NotTraded) => {
+                errors.push(sim_error!(item.span, NotSoldByVendor(item.name.clone())));
+                continue;
+            }
+            Some(sim::VendorPrice::Fixed(price)) => price.max(0),
+            _ => get_buying_price(item),
+        };
+        let cost = price.saturating_mul(item.amount as i32);
+        let have = super::low_level::get_currency(ctx, currency)?;
+        if cost > have {
+            match vendor {
+                Some(v) if !v.uses_rupees() => errors.push(sim_error!(
+                    item.span,
+                    NotEnoughCurrency(currency.to_string(), cost, have)
+                )),
+                _ => errors.push(sim_error!(item.span, NotEnoughRupees(cost, have))),
+            }
+            continue;
+        }
+        let count = get_item_internal(ctx, sys, item, errors, accurate)?;
+        // only pay for what was actually obtained
+        super::low_level::add_currency(ctx, currency, -price.saturating_mul(count as i32))?;
         if ctx.is_aborted() {
             break;
         }
@@ -99,26 +208,47 @@ pub fn buy_items(
     Ok(())
 }
 
+/// Get the price of one of the item in shops, 0 if unknown
+fn get_buying_price(item: &cir::ItemSpec) -> i32 {
+    let price = if item.name.starts_with("Item_Cook_") {
+        item.meta.as_ref().and_then(|m| m.sell_price)
+    } else {
+        game::get_buying_price(&item.name)
+    };
+    price.unwrap_or_default().max(0)
+}
+
+/// Get the item, returning the number of items actually obtained
 fn get_item_internal(
     ctx: &mut sim::Context<&mut Cpu2>,
     sys: &mut sim::GameSystems,
     item: &cir::ItemSpec,
     errors: &mut Vec<ErrorReport>,
     accurate: bool,
-) -> Result<(), processor::Error> {
+) -> Result<usize, processor::Error> {
     let amount = item.amount;
     let name = &item.name;
+    // DLC items don't exist in the game without the DLC, so nothing is
+    // obtained, and the rest of the command continues like in the game
+    let required_dlc = sim::item_required_dlc(name);
+    if ctx.dlc < required_dlc {
+        errors.push(sim_warning!(
+            item.span,
+            RequiresDlc(sim::dlc_to_num(required_dlc), sim::dlc_to_num(ctx.dlc))
+        ));
+        return Ok(0);
+    }
     let is_cook_item = name.starts_with("Item_Cook_");
     let meta = item.meta.as_ref();
     if is_cook_item {
         // cannot optimize cook items
-        for _ in 0..amount {
+        for i in 0..amount {
             if ctx.is_aborted() {
-                return Ok(());
+                return Ok(i);
             }
             if linker::cannot_get_item(ctx.cpu(), name, 1)? {
                 errors.push(sim_error!(item.span, CannotGetMore));
-                return Ok(());
+                return Ok(i);
             }
             linker::get_cook_item(
                 ctx.cpu(),
@@ -131,7 +261,7 @@ fn get_item_internal(
                 meta.and_then(|m| m.effect_level),
             )?;
         }
-        return Ok(());
+        return Ok(amount);
     }
 
     // getting non-cook item
@@ -149,21 +279,21 @@ fn get_item_internal(
     if can_optimize {
         if linker::cannot_get_item(ctx.cpu(), name, amount as i32)? {
             errors.push(sim_error!(item.span, CannotGetMore));
-        } else {
-            // optimize into one call with a value
-            linker::get_item(ctx.cpu(), name, Some(amount as i32), modifier)?;
+            return Ok(0);
         }
-        return Ok(());
+        // optimize into one call with a value
+        linker::get_item(ctx.cpu(), name, Some(amount as i32), modifier)?;
+        return Ok(amount);
     }
-    for _ in 0..amount {
+    for i in 0..amount {
         if ctx.is_aborted() {
-            return Ok(());
+            return Ok(i);
         }
         if linker::cannot_get_item(ctx.cpu(), name, 1)? {
             errors.push(sim_error!(item.span, CannotGetMore));
-            return Ok(());
+            return Ok(i);
         }
         super::get_item_with_auto_equip(ctx.cpu(), sys, is_weapon, name, meta_value, modifier)?;
     }
-    Ok(())
+    Ok(amount)
 }
@@ -6,7 +6,7 @@ use blueflame::game::{
 use blueflame::linker;
 use blueflame::memory::{self, Ptr, mem, proxy};
 use blueflame::processor::{self, Cpu2};
-use skybook_parser::cir;
+use skybook_parser::{cir, search};
 
 use crate::error::{ErrorReport, sim_error, sim_warning};
 use crate::sim;
@@ -250,7 +250,7 @@ pub fn swap_items(
 }
 
 /// Use a "forced pouch screen" to find item in the pouch
-fn find_single_item_target(
+pub fn find_single_item_target(
     ctx: &mut sim::Context<&mut Cpu2>,
     errors: &mut Vec<ErrorReport>,
     spec: &cir::ItemSelectSpec,
@@ -354,10 +354,11 @@ pub fn set_gdt(
 
     macro_rules! cannot_find {
         ($desc:literal) => {{
-            errors.push(sim_error!(
-                span,
-                CannotFindGdtFlag(name.to_string(), $desc.to_string())
-            ));
+            let suggestions = search::suggest_from(name, gdt::flag_names().iter().copied());
+            errors.push(
+                sim_error!(span, CannotFindGdtFlag(name.to_string(), $desc.to_string()))
+                    .with_suggestions(suggestions),
+            );
             return Ok(());
         }};
     }
@@ -530,3 +531,41 @@ pub fn set_gdt(
 
     Ok(())
 }
+
+/// Get the current amount of a currency stored in a s32 flag
+pub fn get_currency(
+    ctx: &mut sim::Context<&mut Cpu2>,
+    flag_name: &str,
+) -> Result<i32, memory::Error> {
+    let proc = &ctx.cpu().proc;
+    let gdt_ptr = gdt::trigger_param_ptr(proc.memory())?;
+    proxy! { let gdt = *gdt_ptr as trigger_param in proc };
+    match gdt.by_name::<gdt::fd!(s32)>(flag_name) {
+        Some(flag) => Ok(*flag.get()),
+        None => {
+            log::warn!("could not find {flag_name} flag");
+            Ok(0)
+        }
+    }
+}
+
+/// Add (or subtract, if negative) to a currency stored in a s32 flag,
+/// clamping the result to `0..=999999`
+pub fn add_currency(
+    ctx: &mut sim::Context<&mut Cpu2>,
+    flag_name: &str,
+    amount: i32,
+) -> Result<(), memory::Error> {
+    let m = ctx.cpu().proc.memory();
+    let gdt_ptr = gdt::trigger_param_ptr(m)?;
+    let proc = &mut ctx.cpu().proc;
+    proxy! { let mut gdt = *gdt_ptr as trigger_param in proc };
+    match gdt.by_name_mut::<gdt::fd!(s32)>(flag_name) {
+        Some(flag) => {
+            let value = flag.get().saturating_add(amount).clamp(0, 999999);
+            flag.set(value);
+        }
+        None => log::warn!("could not find {flag_name} flag"),
+    }
+    Ok(())
+}
@@ -17,10 +17,16 @@ mod sell_items;
 pub use sell_items::*;
 mod entangle;
 pub use entangle::*;
+mod expand_pouch;
+pub use expand_pouch::*;
 mod save_reload;
 pub use save_reload::*;
+mod upgrade_armor;
+pub use upgrade_armor::*;
 mod use_items;
 pub use use_items::*;
+mod wait;
+pub use wait::*;
 
 mod force_remove;
 pub use force_remove::*;
@@ -23,6 +23,12 @@ pub fn sell_items(
         return Ok(());
     }
 
+    // special vendors have their own prices and currency
+    let vendor = sys.screen.current_screen().vendor();
+    let currency = vendor
+        .map(|v| v.currency.as_str())
+        .unwrap_or("CurrentRupee");
+
     let shop = sys.screen.current_screen_mut().as_selling_mut().unwrap();
     'outer: for item in items {
         let name = &item.name;
@@ -94,6 +100,18 @@ pub fn sell_items(
                 break;
             }
 
+            let vendor_price = vendor.map(|v| v.selling_price(&item_name));
+            if vendor_price == Some(sim::VendorPrice::NotTraded) {
+                errors.push(sim_error!(item.span, NotSellable(item_name)));
+                check_for_extra_error = false;
+                break;
+            }
+            if vendor_price == Some(sim::VendorPrice::Unknown) {
+                errors.push(sim_error!(item.span, UnknownVendorPrice(item_name)));
+                check_for_extra_error = false;
+                break;
+            }
+
             // calculate the amount to sell
             let sell_amount = if can_stack {
                 // if the item somehow has negative value, make it 0
@@ -106,8 +124,24 @@ pub fn sell_items(
                 1
             };
 
+            // vendors can set their own price, cook items have their own price,
+            // other items use the price from actor data
+            let price = if let Some(sim::VendorPrice::Fixed(price)) = vendor_price {
+                price
+            } else if item_name.starts_with("Item_Cook_") {
+                mem! { m: let sell_price = *(&item_ptr->mSellPrice); }
+                sell_price
+            } else {
+                game::get_selling_price(&item_name).unwrap_or_default()
+            };
+
             remaining.sub(sell_amount);
             linker::sell_item(ctx.cpu(), item_ptr, sell_amount as i32)?;
+            super::low_level::add_currency(
+                ctx,
+                currency,
+                price.max(0).saturating_mul(sell_amount as i32),
+            )?;
             shop.update(tab, slot, None, ctx.cpu().proc.memory())?;
         }
         if check_for_extra_error {
@@ -0,0 +1,83 @@
+use blueflame::game;
+use blueflame::linker;
+use blueflame::memory::{Ptr, mem};
+use blueflame::processor::{self, Cpu2};
+use skybook_parser::cir;
+
+use crate::error::{ErrorReport, sim_error};
+use crate::sim;
+
+/// Upgrade an armor at a Great Fairy Fountain
+///
+/// The required materials are removed from the inventory, and the armor
+/// is renamed to the actor with one more star. The equipped state is kept
+pub fn upgrade_armor(
+    ctx: &mut sim::Context<&mut Cpu2>,
+    sys: &mut sim::GameSystems,
+    errors: &mut Vec<ErrorReport>,
+    item: &cir::ItemSelectSpec,
+) -> Result<(), processor::Error> {
+    super::switch_to_overworld_or_stop!(ctx, sys, errors, "UPGRADE");
+
+    let Some(item_ptr) = super::low_level::find_single_item_target(ctx, errors, item)? else {
+        errors.push(sim_error!(item.span, CannotFindItem));
+        return Ok(());
+    };
+    let m = ctx.cpu().proc.memory();
+    let name = Ptr!(&item_ptr->mName).cstr(m)?.load_utf8_lossy(m)?;
+
+    let Some(star) = skybook_parser::get_armor_star(&name) else {
+        errors.push(sim_error!(item.span, NotUpgradable(name)));
+        return Ok(());
+    };
+    if star >= 4 {
+        errors.push(sim_error!(item.span, ArmorMaxStar));
+        return Ok(());
+    }
+    let materials = game::get_armor_upgrade_materials(&name);
+    if materials.is_empty() {
+        errors.push(sim_error!(item.span, NotUpgradable(name)));
+        return Ok(());
+    }
+
+    // make sure all materials are there before taking any of them
+    let inventory = sim::PouchScreen::open_no_exec(ctx.cpu().proc, true)?;
+    for (material, need) in materials {
+        let need = *need as usize;
+        let have = inventory.get_amount(
+            &cir::ItemNameSpec::Actor(material.to_string()),
+            None,
+            sim::CountingMethod::CanStack,
+            ctx.cpu().proc.memory(),
+        )?;
+        if have < need {
+            errors.push(sim_error!(
+                item.span,
+                NotEnoughMaterials(material.to_string(), need, have)
+            ));
+            return Ok(());
+        }
+    }
+
+    let new_name = skybook_parser::get_armor_with_star(&name, star + 1);
+    log::debug!("upgrading {name} to {new_name}");
+    let m = ctx.cpu().proc.memory_mut();
+    Ptr!(&item_ptr->mName).safe_store(&new_name, m)?;
+    let item_type = game::get_pouch_item_type(&new_name);
+    let item_use = game::get_pouch_item_use(&new_name);
+    mem! { m:
+        *(&item_ptr->mType) = item_type;
+        *(&item_ptr->mItemUse) = item_use;
+    }
+
+    for (material, need) in materials {
+        for _ in 0..*need {
+            if ctx.is_aborted() {
+                break;
+            }
+            linker::remove_item_by_name(ctx.cpu(), material)?;
+        }
+    }
+
+    super::low_level::fix_inventory_state_and_gamedata(ctx)
+}
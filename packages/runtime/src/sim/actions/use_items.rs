@@ -16,7 +16,11 @@ use crate::sim;
 /// - Arrow/Armor/Food/KeyItem: will call removeItem(), but the behavior
 ///   doesn't correspond to anything in game
 ///
-/// Non-equipment must specify the name of the item to remove
+/// Non-equipment must specify the name of the item to remove.
+///
+/// When the equipment breaks, the next equipment of the same type is
+/// auto-equipped (unless `auto_equip` is false), and the remaining uses
+/// continue with it if it still matches the spec
 pub fn use_items(
     ctx: &mut sim::Context<&mut Cpu2>,
     sys: &mut sim::GameSystems,
@@ -24,6 +28,7 @@ pub fn use_items(
     item: &cir::ItemNameSpec,
     times: usize,
     per_use: Option<i32>,
+    auto_equip: bool,
 ) -> Result<(), processor::Error> {
     // must be in the overworld to use anything
     super::switch_to_overworld_or_stop!(ctx, sys, errors, "USE");
@@ -44,8 +49,9 @@ pub fn use_items(
             if ctx.is_aborted() {
                 break;
             }
-            let can_continue =
-                use_overworld_equipment_once_internal(ctx, sys, errors, item, item_type, step_by)?;
+            let can_continue = use_overworld_equipment_once_internal(
+                ctx, sys, errors, item, item_type, step_by, auto_equip,
+            )?;
             if !can_continue {
                 if i != times - 1 {
                     errors.push(sim_warning!(ctx.span, CannotUseMore(times - 1 - i)));
@@ -79,6 +85,57 @@ pub fn use_items(
     Ok(())
 }
 
+/// Shoot the equipped bow, optionally switching to another arrow type first
+///
+/// The arrow is equipped with the same rules as the `equip` command,
+/// using the DPad quick menu if `is_dpad` is true
+#[allow(clippy::too_many_arguments)]
+pub fn shoot(
+    ctx: &mut sim::Context<&mut Cpu2>,
+    sys: &mut sim::GameSystems,
+    errors: &mut Vec<ErrorReport>,
+    arrow: Option<&cir::ItemSelectSpec>,
+    times: usize,
+    per_use: Option<i32>,
+    auto_equip: bool,
+    pe_target: Option<&cir::ItemSelectSpec>,
+    is_dpad: bool,
+) -> Result<(), processor::Error> {
+    if let Some(arrow) = arrow {
+        if sim::util::name_spec_to_item_type(&arrow.name) != PouchItemType::Arrow as i32 {
+            errors.push(sim_error!(arrow.span, NotArrow));
+            return Ok(());
+        }
+        if !is_arrow_equipped(ctx, &arrow.name)? {
+            let arrows = std::slice::from_ref(arrow);
+            super::change_equip(ctx, sys, errors, arrows, pe_target, true, is_dpad)?;
+            if !is_arrow_equipped(ctx, &arrow.name)? {
+                // error is already reported when equipping
+                return Ok(());
+            }
+        }
+    }
+    let bow = cir::ItemNameSpec::Category(cir::Category::Bow);
+    use_items(ctx, sys, errors, &bow, times, per_use, auto_equip)
+}
+
+/// Check if the currently equipped arrow matches the name
+fn is_arrow_equipped(
+    ctx: &mut sim::Context<&mut Cpu2>,
+    name: &cir::ItemNameSpec,
+) -> Result<bool, processor::Error> {
+    let cir::ItemNameSpec::Actor(name) = name else {
+        return Ok(false);
+    };
+    let equipped_arrow = linker::get_equipped_item(ctx.cpu(), PouchItemType::Arrow as i32)?;
+    if equipped_arrow.is_nullptr() {
+        return Ok(false);
+    }
+    let m = ctx.cpu().proc.memory();
+    let equipped_name = Ptr!(&equipped_arrow->mName).cstr(m)?.load_utf8_lossy(m)?;
+    Ok(&equipped_name == name)
+}
+
 // returns the equipment can continue to be used
 fn use_overworld_equipment_once_internal(
     ctx: &mut sim::Context<&mut Cpu2>,
@@ -87,6 +144,7 @@ fn use_overworld_equipment_once_internal(
     item: &cir::ItemNameSpec,
     item_type: i32,
     step_by: i32,
+    auto_equip: bool,
 ) -> Result<bool, processor::Error> {
     // cannot use anymore
     if !sys.overworld.equipped_item_matches(item_type, item) {
@@ -102,17 +160,18 @@ fn use_overworld_equipment_once_internal(
         "Weapon_Bow_071" | "Weapon_Sword_502" | "Weapon_Sword_503"
     );
 
-    // TODO: to confirm, does hold smuggle items get unheld? (probably not)
-    if item_type == PouchItemType::Bow as i32 {
-        // to use bow, first drop/unhold items
-        if sys.overworld.is_holding_attached() {
-            log::debug!("automatically dropping for USE command");
-            super::drop_held_items(ctx, sys, "USE")?;
-        } else if sys.overworld.is_holding() {
-            log::debug!("automatically unholding for USE command");
-            super::unhold_internal(ctx, sys)?;
-        }
+    // to use any equipment, first drop/unhold items. hold smuggled items
+    // are not unheld, they fall to the ground like with any other action
+    // in the overworld
+    if sys.overworld.is_holding_attached() {
+        log::debug!("automatically dropping for USE command");
+        super::drop_held_items(ctx, sys, "USE")?;
+    } else if sys.overworld.is_holding() {
+        log::debug!("automatically unholding for USE command");
+        super::unhold_internal(ctx, sys)?;
+    }
 
+    if item_type == PouchItemType::Bow as i32 {
         // get the arrow to spawn
         let arrow_name = match item_name.as_str() {
             "Weapon_Bow_072" | "Weapon_Bow_071" => None,
@@ -148,21 +207,84 @@ fn use_overworld_equipment_once_internal(
         if let Some(arrow) = arrow_name {
             log::debug!("removing arrow: {arrow}");
             linker::remove_arrow(ctx.cpu(), &arrow, 1)?;
+            // bomb and ancient arrows are destroyed on impact,
+            // other arrows can be picked up again
+            if !matches!(arrow.as_str(), "BombArrow_A" | "AncientArrow") {
+                sys.overworld.spawn_ground_arrow(arrow);
+            }
         }
 
-        return Ok(!broken);
+        if broken {
+            return handle_broken_equipment(ctx, sys, errors, &item_name, item_type, auto_equip);
+        }
+        return Ok(true);
     }
 
     // using weapon/shield
-    if sys.overworld.is_holding() {
-        log::debug!("automatically unholding for USE command");
-        super::unhold_internal(ctx, sys)?;
-    }
     // damage the equipment
     let broken = is_damagable
         && sys
             .overworld
             .damage_equipment(ctx.cpu(), item_type, step_by)?;
 
-    Ok(!broken)
+    if broken {
+        return handle_broken_equipment(ctx, sys, errors, &item_name, item_type, auto_equip);
+    }
+    Ok(true)
+}
+
+/// Report the broken equipment and auto-equip the next one if `auto_equip` is true
+///
+/// The game equips the next equipment of the same type by itself when the
+/// equipment breaks. This is simulated in the same way as `:dpad equip <category>`.
+///
+/// Returns if a new equipment is equipped
+fn handle_broken_equipment(
+    ctx: &mut sim::Context<&mut Cpu2>,
+    sys: &mut sim::GameSystems,
+    errors: &mut Vec<ErrorReport>,
+    item_name: &str,
+    item_type: i32,
+    auto_equip: bool,
+) -> Result<bool, processor::Error> {
+    log::debug!("equipment broke: {item_name}");
+    errors.push(sim_warning!(
+        ctx.span,
+        EquipmentBroke(item_name.to_string())
+    ));
+
+    // the Master Sword stays in the inventory to recharge
+    // and the player is left empty-handed
+    if item_name == "Weapon_Sword_070" || !auto_equip {
+        return Ok(false);
+    }
+    let Some(category) = sim::util::item_type_to_category(item_type) else {
+        return Ok(false);
+    };
+    let next_item = cir::ItemSelectSpec {
+        amount: cir::AmountSpec::Num(1),
+        name: cir::ItemNameSpec::Category(category),
+        meta: None,
+        span: ctx.span,
+    };
+    // errors from equipping are not reported, since not having anything
+    // else to equip is reported as not being able to use more
+    let mut equip_errors = Vec::new();
+    super::change_equip_dpad(
+        ctx,
+        sys,
+        &mut equip_errors,
+        std::slice::from_ref(&next_item),
+        true,
+    )?;
+    let Some(next_name) = sys
+        .overworld
+        .get_equiped_item(item_type)
+        .map(|x| x.name.to_string())
+    else {
+        return Ok(false);
+    };
+    log::debug!("auto-equipped after break: {next_name}");
+    errors.push(sim_warning!(ctx.span, EquipmentAutoEquipped(next_name)));
+    Ok(true)
 }
@@ -0,0 +1,107 @@
+use blueflame::game::{self, PauseMenuDataMgr, PouchItem, PouchItemType, gdt, singleton_instance};
+use blueflame::memory::{self, Memory, Ptr, mem, proxy};
+use blueflame::processor::{self, Cpu2};
+
+use crate::error::ErrorReport;
+use crate::sim;
+
+/// Real-time minutes it takes for a broken Master Sword to recharge
+const MASTER_SWORD_RECOVER_MINUTES: f32 = 10.0;
+
+/// Let time pass in the overworld
+///
+/// The duration is in real-time seconds
+pub fn wait(
+    ctx: &mut sim::Context<&mut Cpu2>,
+    sys: &mut sim::GameSystems,
+    errors: &mut Vec<ErrorReport>,
+    seconds: u32,
+) -> Result<(), processor::Error> {
+    super::switch_to_overworld_or_stop!(ctx, sys, errors, "WAIT");
+    if seconds == 0 {
+        return Ok(());
+    }
+
+    // materials over the ground limit despawn, and dropped weapons spawn,
+    // once the game runs in the overworld. This is the same as other actions
+    // that return to the overworld (like get and closing the inventory),
+    // which don't take any time in the simulator
+    sys.overworld.despawn_items();
+    sys.overworld.spawn_ground_weapons();
+
+    recharge_master_sword(ctx, seconds as f32 / 60.0)
+}
+
+/// Advance the `MasterSwordRecoverTime` timer by `minutes` if the
+/// Master Sword is broken, and restore the sword once the timer runs out
+fn recharge_master_sword(
+    ctx: &mut sim::Context<&mut Cpu2>,
+    minutes: f32,
+) -> Result<(), processor::Error> {
+    let m = ctx.cpu().proc.memory();
+    let pmdm = singleton_instance!(pmdm(m))?;
+    let Some(item_ptr) = find_broken_master_sword(pmdm, m)? else {
+        return Ok(());
+    };
+
+    let recovered = {
+        let gdt_ptr = gdt::trigger_param_ptr(m)?;
+        let proc = &mut ctx.cpu().proc;
+        proxy! { let mut gdt = *gdt_ptr as trigger_param in proc };
+        let Some(flag) = gdt.by_name_mut::<gdt::fd!(f32)>("MasterSwordRecoverTime") else {
+            log::warn!("could not find MasterSwordRecoverTime flag");
+            return Ok(());
+        };
+        // the timer is not running if the sword was broken
+        // without going through the game's normal flow (e.g. with !write)
+        let remaining = match *flag.get() {
+            x if x > 0.0 => x,
+            _ => MASTER_SWORD_RECOVER_MINUTES,
+        } - minutes;
+        if remaining > 0.0 {
+            log::debug!("master sword recovers in {remaining} minutes");
+            flag.set(remaining);
+            false
+        } else {
+            flag.set(0.0);
+            true
+        }
+    };
+    if !recovered {
+        return Ok(());
+    }
+
+    log::debug!("master sword recovered");
+    let value = game::get_weapon_general_life("Weapon_Sword_070").unwrap_or(40) * 100;
+    mem! { (ctx.cpu().proc.memory_mut()): *(&item_ptr->mValue) = value; }
+    super::low_level::fix_inventory_state_and_gamedata(ctx)
+}
+
+/// Find the Master Sword in the inventory that has 0 durability
+fn find_broken_master_sword(
+    pmdm: Ptr![PauseMenuDataMgr],
+    memory: &Memory,
+) -> Result<Option<Ptr![PouchItem]>, memory::Error> {
+    let mut list_iter = Ptr!(&pmdm->mList1).begin(memory)?;
+    let iter_end = Ptr!(&pmdm->mList1).end(memory)?;
+    while list_iter != iter_end {
+        let item_ptr: Ptr![PouchItem] = list_iter.get_tptr().into();
+        if item_ptr.is_nullptr() {
+            break;
+        }
+        mem! { memory:
+            let item_type = *(&item_ptr->mType);
+            let value = *(&item_ptr->mValue);
+        }
+        if item_type == PouchItemType::Sword as i32 && value <= 0 {
+            let name = Ptr!(&item_ptr->mName)
+                .cstr(memory)?
+                .load_utf8_lossy(memory)?;
+            if name == "Weapon_Sword_070" {
+                return Ok(Some(item_ptr));
+            }
+        }
+        list_iter.next(memory)?;
+    }
+    Ok(None)
+}
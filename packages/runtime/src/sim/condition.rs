@@ -0,0 +1,146 @@
+use blueflame::game::{gdt, singleton_instance};
+use blueflame::memory::{self, Ptr, proxy};
+use blueflame::processor::Process;
+use skybook_parser::{cir, search};
+use teleparse::Span;
+
+use crate::error::{ErrorReport, Report, sim_error};
+use crate::sim;
+
+impl sim::State {
+    /// Check the conditions of an `if` and get the index of the branch to take,
+    /// which is the first condition that is true, or the number of conditions
+    /// (the `else` branch) if none are
+    ///
+    /// `None` is returned if the conditions cannot be checked,
+    /// in which case none of the branches should run
+    pub fn check_conditions(
+        &self,
+        conditions: &[cir::Condition],
+        span: Span,
+    ) -> Report<Option<u32>> {
+        let sim::Game::Running(game) = &self.game else {
+            return Report::error(None, sim_error!(span, CannotCheckCondition));
+        };
+        let mut errors = Vec::new();
+        for (i, condition) in conditions.iter().enumerate() {
+            match check_condition(&game.process, condition, span, &mut errors) {
+                Ok(Some(true)) => return Report::with_errors(Some(i as u32), errors),
+                Ok(Some(false)) => {}
+                Ok(None) => return Report::with_errors(None, errors),
+                Err(e) => {
+                    log::error!("failed to read memory when checking condition: {e}");
+                    errors.push(sim_error!(span, CannotCheckCondition));
+                    return Report::with_errors(None, errors);
+                }
+            }
+        }
+        Report::with_errors(Some(conditions.len() as u32), errors)
+    }
+}
+
+/// Check one condition. `None` is returned if there are errors
+fn check_condition(
+    proc: &Process,
+    condition: &cir::Condition,
+    span: Span,
+    errors: &mut Vec<ErrorReport>,
+) -> Result<Option<bool>, memory::Error> {
+    match condition {
+        cir::Condition::Count(item, compare, expected) => {
+            let inventory = sim::PouchScreen::open_no_exec(proc, true)?;
+            // items are counted by value if they stack, categories by slots
+            let method = match &item.name {
+                cir::ItemNameSpec::Actor(_) => sim::CountingMethod::CanStack,
+                cir::ItemNameSpec::Category(_) => sim::CountingMethod::Slot,
+            };
+            let count =
+                inventory.get_amount(&item.name, item.meta.as_ref(), method, proc.memory())?;
+            Ok(Some(compare.check(count as i64, *expected)))
+        }
+        cir::Condition::MCount(compare, expected) => {
+            let memory = proc.memory();
+            let pmdm = singleton_instance!(pmdm(memory))?;
+            let count = Ptr!(&pmdm->mList1.mCount).load(memory)?;
+            Ok(Some(compare.check(count as i64, *expected)))
+        }
+        cir::Condition::Gdt(name, meta) => check_gdt(proc, name, meta, span, errors),
+    }
+}
+
+/// Check if the gamedata flag has the value. Vector components
+/// that are not specified are not checked
+fn check_gdt(
+    proc: &Process,
+    name: &str,
+    meta: &cir::GdtMeta,
+    span: Span,
+    errors: &mut Vec<ErrorReport>,
+) -> Result<Option<bool>, memory::Error> {
+    macro_rules! cannot_find {
+        ($desc:expr) => {{
+            let suggestions = search::suggest_from(name, gdt::flag_names().iter().copied());
+            errors.push(
+                sim_error!(span, CannotFindGdtFlag(name.to_string(), $desc.to_string()))
+                    .with_suggestions(suggestions),
+            );
+            return Ok(None);
+        }};
+    }
+    macro_rules! flag_value {
+        (($($fd:tt)*), ($($fd_array:tt)*), $desc:literal) => {
+            match meta.array_idx {
+                Some(i) => match gdt.by_name::<gdt::fd!($($fd_array)*)>(name) {
+                    None => cannot_find!(concat!($desc, "[]")),
+                    Some(flag) => match flag.get_at(i) {
+                        Some(v) => v,
+                        None => {
+                            errors.push(sim_error!(
+                                span,
+                                InvalidGdtArrayIndex(name.to_string(), concat!($desc, "[]").to_string(), i)
+                            ));
+                            return Ok(None);
+                        }
+                    },
+                },
+                None => match gdt.by_name::<gdt::fd!($($fd)*)>(name) {
+                    None => cannot_find!($desc),
+                    Some(flag) => flag.get(),
+                },
+            }
+        };
+    }
+    let gdt_ptr = gdt::trigger_param_ptr(proc.memory())?;
+    proxy! { let gdt = *gdt_ptr as trigger_param in proc };
+
+    let matches = match &meta.value {
+        cir::GdtValueSpec::Bool(v) => flag_value!((bool), (bool[]), "bool") == v,
+        cir::GdtValueSpec::S32(v) => flag_value!((s32), (s32[]), "s32") == v,
+        cir::GdtValueSpec::F32(v) => flag_value!((f32), (f32[]), "f32") == v,
+        cir::GdtValueSpec::String32(v) => {
+            // there are no str32[] flags in the game
+            if meta.array_idx.is_some() {
+                cannot_find!("str32[]");
+            }
+            match gdt.by_name::<gdt::fd!(str32)>(name) {
+                None => cannot_find!("str32"),
+                Some(flag) => &**flag.get() == v.as_str(),
+            }
+        }
+        cir::GdtValueSpec::String64(v) => &**flag_value!((str64), (str64[]), "str64") == v.as_str(),
+        cir::GdtValueSpec::String256(v) => {
+            &**flag_value!((str256), (str256[]), "str256") == v.as_str()
+        }
+        cir::GdtValueSpec::Vec2f(x, y) => {
+            let value = flag_value!((vec2f), (vec2f[]), "vec2f");
+            x.is_none_or(|x| x == value.0) && y.is_none_or(|y| y == value.1)
+        }
+        cir::GdtValueSpec::Vec3f(x, y, z) => {
+            let value = flag_value!((vec3f), (vec3f[]), "vec3f");
+            x.is_none_or(|x| x == value.0)
+                && y.is_none_or(|y| y == value.1)
+                && z.is_none_or(|z| z == value.2)
+        }
+    };
+    Ok(Some(matches))
+}
@@ -0,0 +1,93 @@
+use blueflame::env::DlcVer;
+use skybook_parser::cir;
+
+/// Actor name prefixes of items that only exist with the DLC installed,
+/// and the DLC version they require
+const DLC_ITEMS: &[(&str, DlcVer)] = &[
+    // Day 1 bonus items
+    ("Armor_170_", DlcVer::V100),
+    ("Armor_175_", DlcVer::V100),
+    // The Master Trials
+    ("Armor_171_", DlcVer::V200),
+    ("Armor_172_", DlcVer::V200),
+    ("Armor_173_", DlcVer::V200),
+    ("Armor_174_", DlcVer::V200),
+    ("Armor_176_", DlcVer::V200),
+    ("Armor_177_", DlcVer::V200),
+    ("Armor_178_", DlcVer::V200),
+    ("Armor_185_", DlcVer::V200),
+    ("Obj_WarpDLC", DlcVer::V200),
+    // The Champions' Ballad
+    ("Armor_168_", DlcVer::V300),
+    ("Armor_179_", DlcVer::V300),
+    ("Armor_180_", DlcVer::V300),
+    ("Armor_181_", DlcVer::V300),
+    ("Armor_182_", DlcVer::V300),
+    ("Armor_183_", DlcVer::V300),
+    ("Weapon_Sword_502", DlcVer::V300),
+    ("Obj_DLC_", DlcVer::V300),
+    ("Obj_Motorcycle", DlcVer::V300),
+    ("Get_TwnObj_DLC_", DlcVer::V300),
+];
+
+/// Get the DLC version required for the item to exist in the game
+pub fn item_required_dlc(actor: &str) -> DlcVer {
+    DLC_ITEMS
+        .iter()
+        .find(|(prefix, _)| actor.starts_with(prefix))
+        .map(|(_, dlc)| *dlc)
+        .unwrap_or(DlcVer::None)
+}
+
+/// Get the DLC version required to enter the trial
+pub fn trial_required_dlc(trial: cir::Trial) -> DlcVer {
+    match trial {
+        cir::Trial::Eventide => DlcVer::None,
+        cir::Trial::SwordNext | cir::Trial::Sword1 | cir::Trial::Sword2 | cir::Trial::Sword3 => {
+            DlcVer::V200
+        }
+        cir::Trial::RefightThunder
+        | cir::Trial::RefightWater
+        | cir::Trial::RefightFire
+        | cir::Trial::RefightWind => DlcVer::V300,
+    }
+}
+
+/// Convert the DLC version to the number used in scripts and init params
+pub fn dlc_to_num(dlc: DlcVer) -> u32 {
+    match dlc {
+        DlcVer::None => 0,
+        DlcVer::V100 => 1,
+        DlcVer::V200 => 2,
+        DlcVer::V300 => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_required_dlc() {
+        assert_eq!(item_required_dlc("Item_Fruit_A"), DlcVer::None);
+        assert_eq!(item_required_dlc("Armor_170_Upper"), DlcVer::V100);
+        assert_eq!(item_required_dlc("Armor_172_Head"), DlcVer::V200);
+        assert_eq!(item_required_dlc("Weapon_Sword_502"), DlcVer::V300);
+        assert_eq!(item_required_dlc("Obj_Motorcycle"), DlcVer::V300);
+    }
+
+    #[test]
+    fn test_trial_required_dlc() {
+        assert_eq!(trial_required_dlc(cir::Trial::Eventide), DlcVer::None);
+        assert_eq!(trial_required_dlc(cir::Trial::Sword1), DlcVer::V200);
+        assert_eq!(trial_required_dlc(cir::Trial::RefightFire), DlcVer::V300);
+    }
+
+    #[test]
+    fn test_dlc_to_num() {
+        for num in 0..=3 {
+            assert_eq!(dlc_to_num(DlcVer::from_num(num).unwrap()), num);
+        }
+        assert!(DlcVer::from_num(4).is_none());
+    }
+}
@@ -4,6 +4,9 @@
 /// by multiple commands
 pub mod actions;
 
+mod condition;
+mod dlc;
+pub use dlc::*;
 mod output;
 pub use output::*;
 mod overworld;
@@ -20,6 +23,8 @@ mod state_context;
 pub use state_context::*;
 mod snapshot;
 pub use snapshot::*;
+mod trace;
+pub use trace::*;
 mod util;
 pub use util::*;
 
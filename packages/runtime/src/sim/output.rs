@@ -1,4 +1,4 @@
-use blueflame::processor::CrashReport;
+use blueflame::processor::{CoreDump, Cpu0, CrashReport, WatchHit};
 
 use crate::error::{ErrorReport, RuntimeViewError};
 use crate::{iv, sim};
@@ -7,9 +7,25 @@ use crate::{iv, sim};
 pub struct RunOutput {
     /// State at each simulation step
     pub states: Vec<sim::State>,
+    /// How each simulation step was run
+    pub runs: Vec<StepRun>,
     pub errors: Vec<ErrorReport>,
 }
 
+/// How a simulation step was run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StepRun {
+    /// The step was executed
+    Executed,
+    /// The step was not executed, because it's in a branch of
+    /// an `if` that was not taken
+    Skipped,
+    /// The step is an `if`, with the index of the branch taken.
+    /// `None` if the conditions cannot be checked, in which case
+    /// no branch is taken
+    Branch(Option<u32>),
+}
+
 impl RunOutput {
     /// Leak self into a raw pointer to pass to external code
     ///
@@ -103,6 +119,48 @@ impl RunOutput {
         None
     }
 
+    /// Get the memory accesses that hit watchpoints during a step
+    ///
+    /// If execution breaks on a watchpoint, the game crashes and the
+    /// crash report has the stack trace of the access instead
+    pub fn get_watch_hits(&self, step: usize) -> &[WatchHit] {
+        match self.states.get(step).map(|x| &x.game) {
+            Some(sim::Game::Running(game)) => game.process.watch_hits(),
+            _ => &[],
+        }
+    }
+
+    /// Export the process at the given step as an ELF core file,
+    /// see [`CoreDump`] for the format
+    ///
+    /// Returns `None` if the game is not running at that step
+    pub fn get_core_dump(&self, step: usize) -> Option<Vec<u8>> {
+        let state = self.get_state_by_step(step)?;
+        let sim::Game::Running(game) = &state.game else {
+            return None;
+        };
+        let cpu = match &game.last_cpu {
+            Some(cpu) => cpu.as_ref(),
+            None => &Cpu0::default(),
+        };
+        Some(CoreDump::new(&game.process, cpu).to_elf())
+    }
+
+    /// Get the watchpoint hits during a step, formatted with the stack traces,
+    /// see [`get_watch_hits`](Self::get_watch_hits)
+    pub fn get_watch_hits_formatted(&self, step: usize) -> Vec<String> {
+        let Some(sim::Game::Running(game)) = self.states.get(step).map(|x| &x.game) else {
+            return Vec::new();
+        };
+        let main_start = game.process.main_start();
+        let symbols = blueflame::env::symbol_map(game.process.env().game_ver);
+        game.process
+            .watch_hits()
+            .iter()
+            .map(|x| x.format_with_symbols(main_start, symbols.as_deref()))
+            .collect()
+    }
+
     /// Get the save names at the given step. Does not include the manual save (which doesn't have
     /// a name)
     pub fn get_save_names(&self, step: usize) -> Vec<String> {
@@ -24,11 +24,46 @@ pub struct OverworldSystem {
     ground_materials: VecDeque<OverworldActor>,
     /// Materials on the ground that are despawning
     ground_materials_despawning: Vec<OverworldActor>,
+    /// Arrows that are shot and can be picked up from the ground
+    ground_arrows: Vec<OverworldActor>,
     /// Items held by player in the overworld
     holding: Vec<OverworldActor>,
     /// If currently in the "hold attached" state
     /// used for arrowless offset
     is_hold_attached: bool,
+
+    /// Limits for items on the ground
+    ground_limits: GroundLimits,
+    /// Names of actors despawned because of the ground limits,
+    /// since they were last taken
+    evicted: Vec<String>,
+}
+
+/// Max number of items on the ground before the oldest ones despawn
+#[derive(Debug, Clone, Copy)]
+pub struct GroundLimits {
+    /// Max number of materials, 0 means no limit
+    pub materials: usize,
+    /// Max number of weapons, 0 means no limit
+    pub weapons: usize,
+    /// Max number of shot arrows, 0 means no limit. None means
+    /// shot arrows are not left on the ground
+    pub arrows: Option<usize>,
+}
+
+impl Default for GroundLimits {
+    fn default() -> Self {
+        Self {
+            materials: 10,
+            // the limit for weapons is not known,
+            // so it's not limited unless configured
+            weapons: 0,
+            // the limit for arrows is not known either, and leaving
+            // arrows on the ground changes the overworld of every script
+            // that shoots, so it's only done when configured
+            arrows: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -63,6 +98,7 @@ impl OverworldSystem {
         self.ground_weapons.clear();
         self.ground_materials.clear();
         self.ground_materials_despawning.clear();
+        self.ground_arrows.clear();
         self.holding.clear();
         self.is_hold_attached = false;
     }
@@ -91,6 +127,9 @@ impl OverworldSystem {
         for item in &self.ground_materials_despawning {
             items.push(item.to_ground_item_iv(true));
         }
+        for item in &self.ground_arrows {
+            items.push(item.to_ground_item_iv(false));
+        }
 
         iv::Overworld { items }
     }
@@ -109,7 +148,7 @@ impl OverworldSystem {
     /// Add an actor to the queue to spawn when inventory is closed
     pub fn spawn_weapon_later(&mut self, actor: OverworldActor) {
         log::debug!("adding ground equipments to spawn: {}", actor.name);
-        self.spawning_ground_weapons.push(actor)
+        self.spawning_ground_weapons.push(actor);
     }
 
     /// Spawn items that are previous dropped
@@ -126,6 +165,94 @@ impl OverworldSystem {
                 log::debug!("weapon broke on spawn: {}", weapon.name);
             }
         }
+        self.evict_ground_weapons();
+    }
+
+    /// Change the limits for items on the ground. Items over the new limits
+    /// despawn immediately
+    pub fn set_ground_limits(
+        &mut self,
+        materials: Option<usize>,
+        weapons: Option<usize>,
+        arrows: Option<usize>,
+    ) {
+        if let Some(x) = materials {
+            self.ground_limits.materials = x;
+        }
+        if let Some(x) = weapons {
+            self.ground_limits.weapons = x;
+        }
+        if let Some(x) = arrows {
+            self.ground_limits.arrows = Some(x);
+        }
+        self.evict_ground_materials();
+        self.evict_ground_weapons();
+        self.evict_ground_arrows();
+    }
+
+    /// Take the names of the actors despawned because of the ground limits
+    pub fn take_evicted(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.evicted)
+    }
+
+    /// Mark the oldest materials over the limit as despawning
+    fn evict_ground_materials(&mut self) {
+        let limit = self.ground_limits.materials;
+        if limit == 0 {
+            return;
+        }
+        while self.ground_materials.len() > limit {
+            // unwrap: length is > limit
+            let item = self.ground_materials.pop_front().unwrap();
+            log::debug!("ground material over limit: {}", item.name);
+            self.evicted.push(item.name.clone());
+            self.ground_materials_despawning.push(item);
+        }
+    }
+
+    /// Despawn the oldest weapons over the limit, counting the weapons
+    /// that are scheduled to spawn
+    fn evict_ground_weapons(&mut self) {
+        let limit = self.ground_limits.weapons;
+        if limit == 0 {
+            return;
+        }
+        while !self.ground_weapons.is_empty()
+            && self.ground_weapons.len() + self.spawning_ground_weapons.len() > limit
+        {
+            let item = self.ground_weapons.remove(0);
+            log::debug!("ground weapon over limit: {}", item.name);
+            self.evicted.push(item.name);
+        }
+    }
+
+    /// Spawn an arrow that was shot on the ground, so it can be picked up
+    ///
+    /// Does nothing unless an arrow limit is configured
+    pub fn spawn_ground_arrow(&mut self, name: String) {
+        if self.ground_limits.arrows.is_none() {
+            return;
+        }
+        log::debug!("spawning shot arrow on the ground: {name}");
+        self.ground_arrows.push(OverworldActor {
+            name,
+            value: 1,
+            modifier: None,
+        });
+        self.evict_ground_arrows();
+    }
+
+    /// Despawn the oldest shot arrows over the limit
+    fn evict_ground_arrows(&mut self) {
+        let limit = match self.ground_limits.arrows {
+            None | Some(0) => return,
+            Some(x) => x,
+        };
+        while self.ground_arrows.len() > limit {
+            let arrow = self.ground_arrows.remove(0);
+            log::debug!("ground arrow over limit: {}", arrow.name);
+            self.evicted.push(arrow.name);
+        }
     }
 
     /// Clear the weapons that are about to spawn (as if spawning failed)
@@ -168,11 +295,7 @@ impl OverworldSystem {
         self.is_hold_attached = false;
         self.ground_materials
             .extend(std::mem::take(&mut self.holding));
-        while self.ground_materials.len() > 10 {
-            // unwrap: length is > 10
-            let item = self.ground_materials.pop_front().unwrap();
-            self.ground_materials_despawning.push(item);
-        }
+        self.evict_ground_materials();
     }
 
     pub fn is_holding(&self) -> bool {
@@ -421,6 +544,7 @@ impl OverworldSystem {
     pub fn drop_player_equipment(&mut self, item_type: i32) {
         if let Some(actor) = self.delete_player_equipment(item_type) {
             self.spawn_weapon_later(actor);
+            self.evict_ground_weapons();
         }
     }
 
@@ -570,6 +694,12 @@ impl OverworldSystem {
                     .enumerate()
                     .map(|(i, item)| (GroundItemHandle::Weapon((), i), item)),
             )
+            .chain(
+                self.ground_arrows
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| (GroundItemHandle::Arrow((), i), item)),
+            )
     }
 
     /// Select an item from equipped items
@@ -725,6 +855,7 @@ pub enum GroundItemHandle<TSys> {
     Weapon(TSys, usize),
     Material(TSys, usize),
     MaterialDespawning(TSys, usize),
+    Arrow(TSys, usize),
 }
 
 impl GroundItemHandle<()> {
@@ -733,6 +864,7 @@ impl GroundItemHandle<()> {
             Self::Weapon(_, i) => GroundItemHandle::Weapon(sys, i),
             Self::Material(_, i) => GroundItemHandle::Material(sys, i),
             Self::MaterialDespawning(_, i) => GroundItemHandle::MaterialDespawning(sys, i),
+            Self::Arrow(_, i) => GroundItemHandle::Arrow(sys, i),
         }
     }
 }
@@ -744,6 +876,7 @@ impl GroundItemHandle<&mut OverworldSystem> {
             Self::Weapon(o, i) => &o.ground_weapons[*i],
             Self::Material(o, i) => &o.ground_materials[*i],
             Self::MaterialDespawning(o, i) => &o.ground_materials_despawning[*i],
+            Self::Arrow(o, i) => &o.ground_arrows[*i],
         }
     }
 
@@ -753,6 +886,7 @@ impl GroundItemHandle<&mut OverworldSystem> {
             Self::Weapon(o, i) => o.ground_weapons.remove(i),
             Self::Material(o, i) => o.ground_materials.remove(i).unwrap(),
             Self::MaterialDespawning(o, i) => o.ground_materials_despawning.remove(i),
+            Self::Arrow(o, i) => o.ground_arrows.remove(i),
         }
     }
 }
@@ -764,6 +898,7 @@ impl GroundItemHandle<&OverworldSystem> {
             Self::Weapon(o, i) => &o.ground_weapons[*i],
             Self::Material(o, i) => &o.ground_materials[*i],
             Self::MaterialDespawning(o, i) => &o.ground_materials_despawning[*i],
+            Self::Arrow(o, i) => &o.ground_arrows[*i],
         }
     }
 }
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
-use skybook_parser::ParseOutput;
+use skybook_parser::{ParseOutput, cir};
 
 use crate::error::MaybeAborted;
 use crate::sim;
@@ -52,10 +52,18 @@ impl Run {
         TFuture: std::future::Future,
     {
         self.output.states.reserve(parsed.steps.len());
+        self.output.runs.reserve(parsed.steps.len());
 
         let mut state = sim::State::default();
-        let mut commands = Vec::with_capacity(parsed.steps.len());
+        let base_features = runtime.features();
+        let base_dlc = runtime.dlc();
+        let mut cache_key = (
+            base_features,
+            base_dlc,
+            Vec::with_capacity(parsed.steps.len()),
+        );
         let mut ctx = sim::Context::new(self.handle, runtime);
+        ctx.tracing = runtime.tracing();
 
         for i in 0..parsed.steps.len() {
             if ctx.is_aborted() {
@@ -74,17 +82,50 @@ impl Run {
             // notify only if it's not the first step
             // this is because the first step may not be byte pos 0,
             // and may cause the "initial" state to be sent in notification
-            // while actually what we need to send is the state after the first step
-            if i > 0 {
+            // while actually what we need to send is the state after the first step.
+            // Steps expanded from the same statement have the same pos,
+            // so notify only after the last one
+            if i > 0 && parsed.steps[i - 1].pos() != pos {
                 notify_fn(pos, &self.output).await;
             }
 
-            commands.push(step.command.clone());
+            // steps in a branch that is not taken are skipped
+            let skipped = step.branch().is_some_and(|b| {
+                self.output.runs.get(b.step) != Some(&sim::StepRun::Branch(Some(b.branch)))
+            });
+            if skipped {
+                cache_key
+                    .2
+                    .push((step.command.clone(), sim::StepRun::Skipped));
+                self.output.states.push(state.clone());
+                self.output.runs.push(sim::StepRun::Skipped);
+                continue;
+            }
+            // conditions are checked against the current state, which is not changed.
+            // The branch taken is in the cache key, so the steps after it
+            // are cached separately for each branch
+            if let cir::Command::If(conditions) = step.command() {
+                let report = state.check_conditions(conditions, step.span());
+                let run = sim::StepRun::Branch(report.value);
+                cache_key.2.push((step.command.clone(), run));
+                self.output.states.push(state.clone());
+                self.output.runs.push(run);
+                let errors = report.errors.into_iter().flat_map(|e| step.locate_error(e));
+                self.output.errors.extend(errors);
+                continue;
+            }
+
+            cache_key
+                .2
+                .push((step.command.clone(), sim::StepRun::Executed));
 
-            let report = match runtime.find_cached(&commands) {
+            let report = match runtime.find_cached(&cache_key) {
                 Some(report) => report,
                 None => {
                     ctx.span = step.span();
+                    ctx.features = state.features.unwrap_or(base_features);
+                    ctx.dlc = state.dlc.unwrap_or(base_dlc);
+                    state.clear_watch_hits();
 
                     let report = match state.execute_step(ctx.clone(), step).await {
                         Err(e) => {
@@ -102,14 +143,16 @@ impl Run {
                     // note we must only update the cache if the run
                     // is not aborted, since it could abort
                     // in the middle of a step (i.e. partially executed)
-                    runtime.set_cache(&commands, &report);
+                    runtime.set_cache(&cache_key, &report);
 
                     report
                 }
             };
 
             self.output.states.push(report.value.clone());
-            self.output.errors.extend(report.errors);
+            self.output.runs.push(sim::StepRun::Executed);
+            let errors = report.errors.into_iter().flat_map(|e| step.locate_error(e));
+            self.output.errors.extend(errors);
             state = report.value;
         }
 
@@ -1,7 +1,9 @@
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
-use blueflame::env::{DlcVer, Environment, GameVer};
+use blueflame::env::{DlcVer, Environment, Feature, FeatureSet, GameVer, SymbolMap};
+use blueflame::memory::{WatchKind, Watchpoint};
 use blueflame::processor::{Cpu1, Process};
+use blueflame::trace::TraceConfig;
 use blueflame::{linker, program};
 use hashlink::LruCache;
 use skybook_parser::cir;
@@ -11,14 +13,31 @@ use crate::exec::{self, Executor, Spawner};
 use crate::sim;
 
 #[doc(inline)]
-pub use skybook_api::runtime::sim::RuntimeInitParams;
+pub use skybook_api::runtime::sim::{
+    RuntimeInitParams, RuntimeTraceParams, RuntimeWatchKind, RuntimeWatchpoint,
+};
+
+/// Key for caching the state after a step, which are the features and DLC version
+/// the run starts with, and the commands up to the step with how they were run
+///
+/// Note it's important to include the command spans in the keys,
+/// since we cache the diagnostics as well
+pub type StateCacheKey = (
+    FeatureSet,
+    DlcVer,
+    Vec<(cir::CommandWithSpan, sim::StepRun)>,
+);
 
 pub struct Runtime {
     executor: Executor,
     initial_process: Mutex<Option<Process>>,
-    // note it's important to include the command spans in the keys,
-    // since we cache the diagnostics as well
-    state_cache: Mutex<LruCache<Vec<cir::CommandWithSpan>, Report<sim::State>>>,
+    /// Features the runs start with, set from the init params
+    features: Mutex<FeatureSet>,
+    /// DLC version the runs start with, set from the init params
+    dlc: Mutex<DlcVer>,
+    /// Structured execution traces to record, if any
+    tracing: Mutex<Option<Arc<sim::Tracing>>>,
+    state_cache: Mutex<LruCache<StateCacheKey, Report<sim::State>>>,
 }
 
 impl Runtime {
@@ -28,6 +47,9 @@ impl Runtime {
         Self {
             executor,
             initial_process: Mutex::new(None),
+            features: Mutex::new(Feature::default_const()),
+            dlc: Mutex::new(DlcVer::V300),
+            tracing: Mutex::new(None),
             state_cache: Mutex::new(LruCache::new(256)),
         }
     }
@@ -41,6 +63,95 @@ impl Runtime {
             .ok_or(crate::error::Error::Uninitialized)
     }
 
+    /// Replace the memory watchpoints of the initial process
+    ///
+    /// The state cache is cleared, since the cached steps do not
+    /// have the hits from the new watchpoints
+    pub fn set_watchpoints(&self, watchpoints: Vec<Watchpoint>) -> Result<(), crate::error::Error> {
+        {
+            let mut p = self
+                .initial_process
+                .lock()
+                .expect("failed to acquire lock for initial process");
+            let Some(process) = p.as_mut() else {
+                return Err(crate::error::Error::Uninitialized);
+            };
+            process.set_watchpoints(watchpoints);
+        }
+        self.state_cache
+            .lock()
+            .expect("failed to acquire lock for state cache")
+            .clear();
+        Ok(())
+    }
+
+    /// Replace the memory watchpoints from the API params, see [`set_watchpoints`](Self::set_watchpoints)
+    pub fn set_watchpoints_from_params(
+        &self,
+        params: &[RuntimeWatchpoint],
+    ) -> Result<(), RuntimeInitError> {
+        let mut watchpoints = Vec::with_capacity(params.len());
+        for param in params {
+            let Some(range) = sim::parse_mem_range(&param.range) else {
+                return Err(RuntimeInitError::InvalidWatchRange(param.range.clone()));
+            };
+            let kind = match param.kind {
+                RuntimeWatchKind::Read => WatchKind::Read,
+                RuntimeWatchKind::Write => WatchKind::Write,
+                RuntimeWatchKind::ReadWrite => WatchKind::ReadWrite,
+            };
+            watchpoints.push(Watchpoint {
+                range,
+                kind,
+                break_on_hit: param.break_on_hit,
+            });
+        }
+        self.set_watchpoints(watchpoints)
+            .map_err(|_| RuntimeInitError::InitializeProcess)
+    }
+
+    /// Start recording a structured execution trace for each step executed
+    /// from now on, or stop recording if `config` is `None`
+    ///
+    /// The state cache is cleared, since the cached steps are not traced.
+    /// Traces recorded previously are discarded
+    pub fn set_tracing(&self, config: Option<TraceConfig>) {
+        {
+            let mut t = self
+                .tracing
+                .lock()
+                .expect("failed to acquire lock for tracing");
+            *t = config.map(|x| Arc::new(sim::Tracing::new(x)));
+        }
+        self.state_cache
+            .lock()
+            .expect("failed to acquire lock for state cache")
+            .clear();
+    }
+
+    /// Get the traces to record, if tracing is on
+    pub fn tracing(&self) -> Option<Arc<sim::Tracing>> {
+        self.tracing.lock().expect("cannot acquire").clone()
+    }
+
+    /// Take out the traces recorded so far
+    pub fn take_traces(&self) -> Vec<sim::StepTrace> {
+        match self.tracing() {
+            Some(tracing) => tracing.take(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Get the BlueFlame features that the runs start with
+    pub fn features(&self) -> FeatureSet {
+        *self.features.lock().expect("cannot acquire")
+    }
+
+    /// Get the DLC version that the runs start with
+    pub fn dlc(&self) -> DlcVer {
+        *self.dlc.lock().expect("cannot acquire")
+    }
+
     /// Initialize the runtime
     pub fn init(
         &self,
@@ -139,6 +250,53 @@ impl Runtime {
             .take_if(|x| *x != 0)
             .unwrap_or(0x4000);
 
+        let features = match params.map(|x| x.features.as_slice()) {
+            None => Feature::default_const(),
+            Some(toggles) => {
+                match blueflame::env::apply_feature_toggles(Feature::default_const(), toggles) {
+                    Ok(x) => x,
+                    Err(name) => {
+                        log::error!("unknown feature in params: {name}");
+                        return Err(RuntimeInitError::InvalidFeature(name));
+                    }
+                }
+            }
+        };
+        log::info!("features: {features:?}");
+
+        let trace_config = match params.map(|x| sim::Tracing::config_from_params(&x.trace)) {
+            None | Some(Ok(None)) => None,
+            Some(Ok(Some(x))) => Some(x),
+            Some(Err(range)) => {
+                log::error!("invalid memory range to trace in params: {range}");
+                return Err(RuntimeInitError::InvalidTraceRange(range));
+            }
+        };
+
+        let symbol_map = match params.map(|x| &x.symbol_map).take_if(|x| !x.is_empty()) {
+            Some(text) => match SymbolMap::parse(text) {
+                Ok(x) => Some(x),
+                Err(e) => {
+                    log::error!("failed to parse symbol map from the params: {e}");
+                    return Err(RuntimeInitError::InvalidSymbolMap(e.to_string()));
+                }
+            },
+            None => match program.symbol_map() {
+                None => None,
+                Some(Ok(x)) => Some(x),
+                Some(Err(e)) => {
+                    // the image is still usable without symbols
+                    log::error!("failed to parse symbol map packed in the image: {e}");
+                    None
+                }
+            },
+        };
+        match &symbol_map {
+            Some(x) => log::info!("loaded symbol map with {} symbols", x.len()),
+            None => log::info!("no symbol map loaded"),
+        }
+        blueflame::env::set_symbol_map(env.game_ver, symbol_map);
+
         let process = match linker::init_process(
             program,
             env.dlc_ver,
@@ -161,6 +319,18 @@ impl Runtime {
                 .expect("failed to acquire lock for initial process");
             *p = Some(process);
         }
+        {
+            let mut f = self
+                .features
+                .lock()
+                .expect("failed to acquire lock for features");
+            *f = features;
+        }
+        {
+            let mut d = self.dlc.lock().expect("failed to acquire lock for dlc");
+            *d = env.dlc_ver;
+        }
+        self.set_tracing(trace_config);
 
         Ok(env)
     }
@@ -173,19 +343,19 @@ impl Runtime {
         self.executor.execute(f).await
     }
 
-    pub fn find_cached(&self, commands: &[cir::CommandWithSpan]) -> Option<Report<sim::State>> {
+    pub fn find_cached(&self, key: &StateCacheKey) -> Option<Report<sim::State>> {
         self.state_cache
             .lock()
             .expect("failed to acquire lock for find_cached")
-            .get(commands)
+            .get(key)
             .cloned()
     }
 
-    pub fn set_cache(&self, commands: &[cir::CommandWithSpan], report: &Report<sim::State>) {
+    pub fn set_cache(&self, key: &StateCacheKey, report: &Report<sim::State>) {
         self.state_cache
             .lock()
             .expect("failed to acquire lock for set_cache")
-            .insert(commands.to_vec(), report.clone());
+            .insert(key.clone(), report.clone());
     }
 }
 
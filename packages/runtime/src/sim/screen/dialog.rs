@@ -0,0 +1,10 @@
+/// Simulation of dialogs that are not part of a shop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DialogScreen {
+    /// Talking to an NPC. The pause menu cannot be opened, so the dialog
+    /// is closed first when pausing
+    Talking,
+    /// The text box shown after receiving an item. The pause menu can
+    /// be opened while it's showing
+    ItemTextBox,
+}
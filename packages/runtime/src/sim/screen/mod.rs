@@ -1,3 +1,5 @@
+mod dialog;
+pub use dialog::*;
 mod item;
 pub use item::*;
 mod pouch;
@@ -6,3 +8,5 @@ mod shop;
 pub use shop::*;
 mod system;
 pub use system::*;
+mod vendor;
+pub use vendor::*;
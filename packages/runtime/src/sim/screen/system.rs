@@ -57,6 +57,10 @@ pub enum Screen {
     Inventory(sim::PouchScreen),
     /// In shop dialog (selling sellable items)
     Shop(sim::ShopScreen),
+    /// In shop dialog of a special vendor, with its own buying and selling tables
+    Vendor(sim::VendorScreen),
+    /// In a dialog that is not a shop, like talking to an NPC
+    Dialog(sim::DialogScreen),
 }
 
 impl ScreenSystem {
@@ -95,9 +99,13 @@ impl ScreenSystem {
                 return Ok(true);
             }
             Screen::Overworld => {}
+            // the pause menu can be opened during the item text box, which
+            // stays open behind it and closes along with it. Held items
+            // are still removed after the dialog, when the inventory closes
+            Screen::Dialog(sim::DialogScreen::ItemTextBox) => {}
             // if the screen cannot be transition directly to inventory
             // screen, close it first to go back to overworld
-            Screen::Shop(_) => {
+            Screen::Shop(_) | Screen::Vendor(_) | Screen::Dialog(_) => {
                 if !self.transition_to_overworld(ctx, overworld, false, errors)? {
                     return Ok(false);
                 }
@@ -122,31 +130,31 @@ impl ScreenSystem {
         errors: &mut Vec<ErrorReport>,
     ) -> Result<bool, processor::Error> {
         match self.screen.as_ref() {
-            // close inventory first to return to overworld
-            Screen::Inventory(_) => {
+            // close inventory or dialog first to return to overworld
+            Screen::Inventory(_) | Screen::Dialog(_) => {
                 if !self.transition_to_overworld(ctx, overworld, false, errors)? {
                     return Ok(false);
                 }
             }
             Screen::Overworld => {}
-            Screen::Shop(sim::ShopScreen::Buy) => {
+            Screen::Shop(sim::ShopScreen::Buy)
+            | Screen::Vendor(sim::VendorScreen {
+                shop: sim::ShopScreen::Buy,
+                ..
+            }) => {
                 if manual {
                     errors.push(sim_warning!(ctx.span, UselessScreenTransition));
                 }
                 return Ok(true);
             }
             // shop screen can be switched without going back to overworld
-            Screen::Shop(sim::ShopScreen::Sell(_)) => {
+            Screen::Shop(sim::ShopScreen::Sell(_)) | Screen::Vendor(_) => {
                 if manual {
                     self.is_manually_switched = true;
                 }
-                match self.current_screen_mut() {
-                    Screen::Shop(shop) => {
-                        shop.transition_to_buy(ctx.cpu())?;
-                    }
-                    // unreachable: outer match
-                    _ => unreachable!(),
-                }
+                // unwrap: outer match
+                let shop = self.current_screen_mut().as_shop_mut().unwrap();
+                shop.transition_to_buy(ctx.cpu())?;
                 return Ok(true);
             }
         }
@@ -179,25 +187,31 @@ impl ScreenSystem {
         errors: &mut Vec<ErrorReport>,
     ) -> Result<bool, processor::Error> {
         match self.screen.as_ref() {
-            // close inventory first to return to overworld
-            Screen::Inventory(_) => {
+            // close inventory or dialog first to return to overworld
+            Screen::Inventory(_) | Screen::Dialog(_) => {
                 if !self.transition_to_overworld(ctx, overworld, false, errors)? {
                     return Ok(false);
                 }
             }
             Screen::Overworld => {}
-            Screen::Shop(sim::ShopScreen::Sell(_)) => {
+            Screen::Shop(sim::ShopScreen::Sell(_))
+            | Screen::Vendor(sim::VendorScreen {
+                shop: sim::ShopScreen::Sell(_),
+                ..
+            }) => {
                 if manual {
                     errors.push(sim_warning!(ctx.span, UselessScreenTransition));
                 }
                 return Ok(true);
             }
             // shop screen can be switched without going back to overworld
-            Screen::Shop(sim::ShopScreen::Buy) => {
+            Screen::Shop(sim::ShopScreen::Buy) | Screen::Vendor(_) => {
                 if manual {
                     self.is_manually_switched = true;
                 }
-                *self.current_screen_mut() = Screen::Shop(sim::ShopScreen::open_sell(ctx.cpu())?);
+                let sell = sim::ShopScreen::open_sell(ctx.cpu())?;
+                // unwrap: outer match
+                *self.current_screen_mut().as_shop_mut().unwrap() = sell;
                 return Ok(true);
             }
         }
@@ -222,6 +236,78 @@ impl ScreenSystem {
         Ok(true)
     }
 
+    /// Open the shop screen of a special vendor by talking to them
+    pub fn transition_to_vendor(
+        &mut self,
+        ctx: &mut sim::Context<&mut Cpu2>,
+        overworld: &mut sim::OverworldSystem,
+        vendor: &'static sim::Vendor,
+        manual: bool,
+        errors: &mut Vec<ErrorReport>,
+    ) -> Result<bool, processor::Error> {
+        match self.screen.as_ref() {
+            // already talking to the vendor, same as switching to buying
+            Screen::Vendor(current) if std::ptr::eq(current.vendor, vendor) => {
+                return self.transition_to_shop_buying(ctx, overworld, manual, errors);
+            }
+            Screen::Overworld => {}
+            // close other screens first to return to overworld
+            _ => {
+                if !self.transition_to_overworld(ctx, overworld, false, errors)? {
+                    return Ok(false);
+                }
+            }
+        }
+        // talking to the vendor is the same as talking to a shop NPC
+        if !self.transition_to_shop_buying(ctx, overworld, manual, errors)? {
+            return Ok(false);
+        }
+        *self.current_screen_mut() = Screen::Vendor(sim::VendorScreen {
+            vendor,
+            shop: sim::ShopScreen::Buy,
+        });
+
+        Ok(true)
+    }
+
+    /// Open a dialog that is not a shop. This is never manual, since
+    /// dialogs are opened as part of a command
+    pub fn transition_to_dialog(
+        &mut self,
+        ctx: &mut sim::Context<&mut Cpu2>,
+        overworld: &mut sim::OverworldSystem,
+        dialog: sim::DialogScreen,
+        errors: &mut Vec<ErrorReport>,
+    ) -> Result<bool, processor::Error> {
+        match self.screen.as_ref() {
+            // close other screens first to return to overworld
+            Screen::Inventory(_) | Screen::Shop(_) | Screen::Vendor(_) => {
+                if !self.transition_to_overworld(ctx, overworld, false, errors)? {
+                    return Ok(false);
+                }
+            }
+            Screen::Overworld => {}
+            // dialogs can follow each other without going back to overworld
+            Screen::Dialog(_) => {
+                *self.current_screen_mut() = Screen::Dialog(dialog);
+                return Ok(true);
+            }
+        }
+
+        // cannot start a dialog while holding items
+        let should_drop = match overworld.predrop_for_action(ctx.span, errors) {
+            sim::OverworldPreDropResult::Holding => {
+                return Ok(false);
+            }
+            sim::OverworldPreDropResult::AutoDrop => true,
+            sim::OverworldPreDropResult::Ok => false,
+        };
+        self.remove_held_item_after_dialog = should_drop;
+        *self.current_screen_mut() = Screen::Dialog(dialog);
+
+        Ok(true)
+    }
+
     pub fn transition_to_overworld(
         &mut self,
         ctx: &mut sim::Context<&mut Cpu2>,
@@ -276,7 +362,8 @@ impl Screen {
         match self {
             Screen::Overworld => iv::Screen::Overworld,
             Screen::Inventory(_) => iv::Screen::Inventory,
-            Screen::Shop(_) => iv::Screen::Shop,
+            Screen::Shop(_) | Screen::Vendor(_) => iv::Screen::Shop,
+            Screen::Dialog(_) => iv::Screen::Dialog,
         }
     }
 
@@ -293,15 +380,19 @@ impl Screen {
     }
 
     pub fn is_shop(&self) -> bool {
-        matches!(self, Screen::Shop(_))
+        self.as_shop().is_some()
     }
 
     pub fn is_shop_buying(&self) -> bool {
-        matches!(self, Screen::Shop(sim::ShopScreen::Buy))
+        matches!(self.as_shop(), Some(sim::ShopScreen::Buy))
     }
 
     pub fn is_shop_selling(&self) -> bool {
-        matches!(self, Screen::Shop(sim::ShopScreen::Sell(_)))
+        matches!(self.as_shop(), Some(sim::ShopScreen::Sell(_)))
+    }
+
+    pub fn is_dialog(&self) -> bool {
+        matches!(self, Screen::Dialog(_))
     }
 
     pub fn as_inventory(&self) -> Option<&sim::PouchScreen> {
@@ -318,16 +409,42 @@ impl Screen {
         }
     }
 
-    pub fn as_selling(&self) -> Option<&sim::ScreenItems> {
+    /// Get the shop screen, including the shop screen of a vendor
+    pub fn as_shop(&self) -> Option<&sim::ShopScreen> {
         match self {
-            Screen::Shop(sim::ShopScreen::Sell(inv)) => Some(inv),
+            Screen::Shop(shop) => Some(shop),
+            Screen::Vendor(vendor) => Some(&vendor.shop),
             _ => None,
         }
     }
 
-    pub fn as_selling_mut(&mut self) -> Option<&mut sim::ScreenItems> {
+    /// Get the shop screen, including the shop screen of a vendor
+    pub fn as_shop_mut(&mut self) -> Option<&mut sim::ShopScreen> {
         match self {
-            Screen::Shop(sim::ShopScreen::Sell(inv)) => Some(inv),
+            Screen::Shop(shop) => Some(shop),
+            Screen::Vendor(vendor) => Some(&mut vendor.shop),
+            _ => None,
+        }
+    }
+
+    /// Get the vendor if in the shop screen of a special vendor
+    pub fn vendor(&self) -> Option<&'static sim::Vendor> {
+        match self {
+            Screen::Vendor(vendor) => Some(vendor.vendor),
+            _ => None,
+        }
+    }
+
+    pub fn as_selling(&self) -> Option<&sim::ScreenItems> {
+        match self.as_shop() {
+            Some(sim::ShopScreen::Sell(inv)) => Some(inv),
+            _ => None,
+        }
+    }
+
+    pub fn as_selling_mut(&mut self) -> Option<&mut sim::ScreenItems> {
+        match self.as_shop_mut() {
+            Some(sim::ShopScreen::Sell(inv)) => Some(inv),
             _ => None,
         }
     }
@@ -404,7 +521,7 @@ impl Screen {
                 // Could also be possible this check is done the next time inventory is opened,
                 // but there's no way to know right now
             }
-            Self::Shop(_) => {}
+            Self::Shop(_) | Self::Vendor(_) | Self::Dialog(_) => {}
         }
         for x in remove_equipments {
             log::debug!("removing {x} on returning to overworld");
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use crate::sim;
+
+/// Vendor data, loaded from `data/vendors.json`
+static VENDORS: LazyLock<BTreeMap<String, Vendor>> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("../../../data/vendors.json"))
+        .expect("vendor data should be valid")
+});
+
+/// Flag used as the currency when the vendor doesn't specify one
+const DEFAULT_CURRENCY: &str = "CurrentRupee";
+
+/// Buying and selling tables for a special shop NPC
+///
+/// When talking to a vendor with `talk-to`, `buy` and `sell` in the
+/// [`VendorScreen`] use the tables here instead of the actor data.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct Vendor {
+    /// The s32 GDT flag used as the currency
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    /// Items that can be bought from the vendor and their prices.
+    /// If empty, anything can be bought with the price from actor data
+    #[serde(default)]
+    pub buy: BTreeMap<String, i32>,
+    /// Items that can be sold to the vendor and their prices.
+    /// `None` means to use the price from actor data for vendors that pay
+    /// in rupees. For other currencies, it means the price is not known,
+    /// and selling the item is not supported.
+    /// If empty, anything sellable can be sold
+    #[serde(default)]
+    pub sell: BTreeMap<String, Option<i32>>,
+}
+
+fn default_currency() -> String {
+    DEFAULT_CURRENCY.to_string()
+}
+
+/// Price of an item when trading with a vendor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VendorPrice {
+    /// The vendor doesn't trade this item
+    NotTraded,
+    /// Use the price from actor data
+    Default,
+    /// Price set by the vendor
+    Fixed(i32),
+    /// The vendor trades this item, but the price is not known
+    Unknown,
+}
+
+impl Vendor {
+    /// Find a vendor by the NPC name used in `talk-to`
+    ///
+    /// The name is case-insensitive and `-` and `_` are treated the same
+    pub fn find(name: &str) -> Option<&'static Self> {
+        let name = name.to_ascii_lowercase().replace('_', "-");
+        VENDORS.get(&name)
+    }
+
+    /// If the currency is rupees
+    pub fn uses_rupees(&self) -> bool {
+        self.currency == DEFAULT_CURRENCY
+    }
+
+    /// Get the price of buying one of the item from the vendor
+    pub fn buying_price(&self, actor: &str) -> VendorPrice {
+        if self.buy.is_empty() {
+            return VendorPrice::Default;
+        }
+        match self.buy.get(actor) {
+            Some(price) => VendorPrice::Fixed(*price),
+            None => VendorPrice::NotTraded,
+        }
+    }
+
+    /// Get the price of selling one of the item to the vendor
+    pub fn selling_price(&self, actor: &str) -> VendorPrice {
+        if self.sell.is_empty() {
+            return VendorPrice::Default;
+        }
+        match self.sell.get(actor) {
+            Some(Some(price)) => VendorPrice::Fixed(*price),
+            // actor data only has prices in rupees
+            Some(None) if !self.uses_rupees() => VendorPrice::Unknown,
+            Some(None) => VendorPrice::Default,
+            None => VendorPrice::NotTraded,
+        }
+    }
+}
+
+/// Simulation of the shop screen of a special vendor
+///
+/// This works the same as [`ShopScreen`](sim::ShopScreen), but the buying and
+/// selling use the tables from the vendor
+#[derive(Debug, Clone)]
+pub struct VendorScreen {
+    pub vendor: &'static Vendor,
+    pub shop: sim::ShopScreen,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vendor_data() {
+        assert!(!VENDORS.is_empty());
+        for (name, vendor) in VENDORS.iter() {
+            // names are looked up in lower case with `-`
+            assert_eq!(name, &name.to_ascii_lowercase().replace('_', "-"));
+            // actor data only has rupee prices, so the tables
+            // must list every item for other currencies
+            if !vendor.uses_rupees() {
+                assert!(!vendor.buy.is_empty(), "{name} needs a buying table");
+                assert!(!vendor.sell.is_empty(), "{name} needs a selling table");
+                for item in vendor.sell.keys() {
+                    assert_ne!(vendor.selling_price(item), VendorPrice::Default);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_kilton() {
+        let kilton = Vendor::find("Kilton").unwrap();
+        assert_eq!(kilton.currency, "CurrentMamo");
+        assert!(!kilton.uses_rupees());
+        assert_eq!(
+            kilton.buying_price("Armor_022_Head"),
+            VendorPrice::Fixed(99)
+        );
+        assert_eq!(kilton.buying_price("Item_Fruit_A"), VendorPrice::NotTraded);
+        // the Mon prices for selling are not known yet
+        assert_eq!(kilton.selling_price("Item_Enemy_14"), VendorPrice::Unknown);
+        assert_eq!(kilton.selling_price("Item_Fruit_A"), VendorPrice::NotTraded);
+    }
+}
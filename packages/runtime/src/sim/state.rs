@@ -1,10 +1,11 @@
 use std::sync::Arc;
 
+use blueflame::env::{DlcVer, Feature, FeatureSet};
 use blueflame::game::gdt;
-use blueflame::processor::{CrashReport, Process};
+use blueflame::processor::{Cpu0, CrashReport, Process};
 use skybook_parser::cir;
 
-use crate::error::{Report, sim_error};
+use crate::error::{Report, sim_error, sim_warning};
 use crate::{exec, sim};
 
 /// The state of one step in the simulation.
@@ -21,6 +22,16 @@ pub struct State {
     saves: Arc<Vec<(String, Arc<gdt::TriggerParam>)>>,
     /// The "manual" or "default" save (what is used if a name is not specified when saving)
     pub manual_save: Option<Arc<gdt::TriggerParam>>,
+    /// BlueFlame features changed with the `:features` annotation.
+    ///
+    /// `None` means the features from the runtime init params are used
+    pub features: Option<FeatureSet>,
+    /// DLC version changed with the `:dlc` annotation. This is only used
+    /// for the checks in the simulator, the DLC version of the game process
+    /// is not changed.
+    ///
+    /// `None` means the DLC version from the runtime init params is used
+    pub dlc: Option<DlcVer>,
 }
 
 impl State {
@@ -47,6 +58,14 @@ impl State {
         }
     }
 
+    /// Clear the watchpoint hits from the previous step, so the state
+    /// after the step only has the hits from that step
+    pub fn clear_watch_hits(&mut self) {
+        if let Game::Running(game) = &mut self.game {
+            game.process.take_watch_hits();
+        }
+    }
+
     /// Set a manual save (if name is `None`) or a named save
     pub fn set_save_by_name(&mut self, name: Option<&str>, data: Arc<gdt::TriggerParam>) {
         match name {
@@ -90,6 +109,8 @@ pub struct StateArgs {
     pub breaking: bool,
     /// Specify how much value is decreases per use
     pub per_use: Option<i32>,
+    /// Specify another equipment should not be equipped when one breaks
+    pub no_auto_equip: bool,
 }
 
 #[derive(Clone, Default)]
@@ -119,6 +140,9 @@ pub struct GameState {
     pub process: Process,
     /// Simulated systems in the game
     pub systems: GameSystems,
+    /// CPU state at the end of the last execution on the process,
+    /// used for exporting core dumps
+    pub last_cpu: Option<Box<Cpu0>>,
 }
 
 #[derive(Default, Clone)]
@@ -202,6 +226,34 @@ impl State {
             X::CoNonBreaking => set_arg!(self, args, non_breaking, true),
             X::CoBreaking => set_arg!(self, args, breaking, true),
             X::CoPerUse(x) => set_arg!(self, args, per_use, Some(*x)),
+            X::CoNoAutoEquip => set_arg!(self, args, no_auto_equip, true),
+            X::CoFeatures(meta) => {
+                // features are not one-time args, so keep the args for the next step
+                self.args = args;
+                Ok(self.handle_features(ctx, meta))
+            }
+            X::CoDlc(version) => {
+                // DLC version is not one-time arg, so keep the args for the next step
+                self.args = args;
+                log::debug!("handling DLC");
+                // this only changes the version used for checks in the simulator,
+                // the game process keeps the version it's initialized with
+                match DlcVer::from_num(*version) {
+                    Some(dlc) => {
+                        self.dlc = Some(dlc);
+                        Ok(Report::new(self))
+                    }
+                    None => {
+                        let error = sim_error!(ctx.span, InvalidDlcVersion(*version));
+                        Ok(Report::with_errors(self, vec![error]))
+                    }
+                }
+            }
+            X::CoGroundLimit(meta) => {
+                // ground limits are not one-time args, so keep the args for the next step
+                self.args = args;
+                self.handle_ground_limit(ctx, meta).await
+            }
 
             command => self.handle_command(ctx, args, command).await,
         }
@@ -216,6 +268,10 @@ impl State {
         match command {
             X::Get(items) => self.handle_get(ctx, items, args.as_deref()).await,
             X::PickUp(items) => self.handle_pick_up(ctx, items, args.as_deref()).await,
+            X::Receive(items, source, timing) => {
+                self.handle_receive(ctx, items, *source, *timing, args.as_deref())
+                    .await
+            }
             X::OpenInv => self.handle_pause(ctx).await,
             X::CloseInv => self.handle_unpause(ctx).await,
             X::Hold(items) => self.handle_hold(ctx, items, args.as_deref()).await,
@@ -246,11 +302,17 @@ impl State {
                     .await
             }
             X::Use(item, times) => self.handle_use(ctx, item, *times, args.as_deref()).await,
+            X::Shoot(arrow, times) => {
+                self.handle_shoot(ctx, arrow.as_deref(), *times, args.as_deref())
+                    .await
+            }
 
-            X::OpenShop => self.handle_open_shop(ctx).await,
+            X::OpenShop(name) => self.handle_open_shop(ctx, name.as_deref()).await,
             X::CloseShop => self.handle_close_shop(ctx).await,
             X::Sell(items) => self.handle_sell(ctx, items).await,
             X::Buy(items) => self.handle_buy(ctx, items, args.as_deref()).await,
+            X::Upgrade(item) => self.handle_upgrade(ctx, item).await,
+            X::Expand(category) => self.handle_expand(ctx, *category).await,
 
             X::Save(name) => self.handle_save(ctx, name.as_deref()).await,
             X::Reload(name) => self.handle_reload(ctx, name.as_deref(), false).await,
@@ -260,6 +322,8 @@ impl State {
                 Ok(Report::new(self))
             }
             X::NewGame => self.handle_reload(ctx, None, true).await,
+            X::Wait(seconds) => self.handle_wait(ctx, *seconds).await,
+            X::Enter(trial) => self.handle_enter(ctx, *trial),
 
             X::SuBreak(count) => self.handle_su_break(ctx, *count).await,
             X::SuInit(items) => self.handle_su_add_slot(ctx, items, true).await,
@@ -273,6 +337,54 @@ impl State {
         }
     }
 
+    fn handle_features(
+        mut self,
+        ctx: sim::Context<&sim::Runtime>,
+        meta: &cir::FeatureMeta,
+    ) -> Report<Self> {
+        log::debug!("handling FEATURES");
+        let mut features = self.features.unwrap_or_else(|| ctx.runtime().features());
+        let mut errors = vec![];
+        for (name, enabled) in &meta.features {
+            let Some(feature) = Feature::parse(name) else {
+                errors.push(sim_error!(ctx.span, InvalidFeature(name.clone())));
+                continue;
+            };
+            if *enabled {
+                features.insert(feature);
+            } else {
+                features.remove(feature);
+            }
+        }
+        self.features = Some(features);
+        Report::with_errors(self, errors)
+    }
+
+    /// Enter a trial. Only the DLC version is checked.
+    ///
+    /// Divine Beast refights keep the inventory, so nothing else happens.
+    /// Eventide and Trial of the Sword take away the inventory,
+    /// which is not simulated yet
+    fn handle_enter(self, ctx: sim::Context<&sim::Runtime>, trial: cir::Trial) -> Report<Self> {
+        log::debug!("handling ENTER");
+        let required = sim::trial_required_dlc(trial);
+        if ctx.dlc < required {
+            let error = sim_error!(
+                ctx.span,
+                RequiresDlc(sim::dlc_to_num(required), sim::dlc_to_num(ctx.dlc))
+            );
+            return Report::error(self, error);
+        }
+        match trial {
+            cir::Trial::RefightThunder
+            | cir::Trial::RefightWater
+            | cir::Trial::RefightFire
+            | cir::Trial::RefightWind => Report::new(self),
+            // TODO: simulate the inventory being taken away
+            _ => Report::error(self, sim_warning!(ctx.span, Unimplemented)),
+        }
+    }
+
     async fn handle_get(
         self,
         rt: sim::Context<&sim::Runtime>,
@@ -289,6 +401,24 @@ impl State {
         })
     }
 
+    async fn handle_receive(
+        self,
+        rt: sim::Context<&sim::Runtime>,
+        items: &[cir::ItemSpec],
+        source: cir::ReceiveSource,
+        timing: cir::ReceiveTiming,
+        args: Option<&StateArgs>,
+    ) -> Result<Report<Self>, exec::Error> {
+        log::debug!("handling RECEIVE");
+        let items = items.to_vec();
+        let (pause, accurate) = args
+            .map(|args| (args.pause_during, args.accurately_simulate))
+            .unwrap_or_default();
+        in_game!(self, rt, cpu, sys, errors => {
+            sim::actions::receive_items(&mut cpu, sys, errors, &items, source, timing, pause, accurate)
+        })
+    }
+
     async fn handle_pick_up(
         self,
         rt: sim::Context<&sim::Runtime>,
@@ -442,19 +572,50 @@ impl State {
     ) -> Result<Report<Self>, exec::Error> {
         log::debug!("handling USE");
         let per_use = args.and_then(|x| x.per_use);
+        let auto_equip = !args.map(|x| x.no_auto_equip).unwrap_or_default();
         let item = item.clone();
         in_game!(self, rt, cpu, sys, errors => {
-            sim::actions::use_items(&mut cpu, sys, errors, &item, times, per_use)
+            sim::actions::use_items(&mut cpu, sys, errors, &item, times, per_use, auto_equip)
+        })
+    }
+
+    async fn handle_shoot(
+        self,
+        rt: sim::Context<&sim::Runtime>,
+        arrow: Option<&cir::ItemSelectSpec>,
+        times: usize,
+        args: Option<&StateArgs>,
+    ) -> Result<Report<Self>, exec::Error> {
+        log::debug!("handling SHOOT");
+        let per_use = args.and_then(|x| x.per_use);
+        let auto_equip = !args.map(|x| x.no_auto_equip).unwrap_or_default();
+        let (pe_target, is_dpad) = args
+            .map(|x| (x.entangle_target.as_ref().cloned(), x.dpad))
+            .unwrap_or_default();
+        let arrow = arrow.cloned();
+        in_game!(self, rt, cpu, sys, errors => {
+            sim::actions::shoot(&mut cpu, sys, errors, arrow.as_ref(),
+                times, per_use, auto_equip, pe_target.as_ref(), is_dpad)
         })
     }
 
     async fn handle_open_shop(
         self,
         rt: sim::Context<&sim::Runtime>,
+        name: Option<&str>,
     ) -> Result<Report<Self>, exec::Error> {
         log::debug!("handling OPEN-SHOP");
+        // NPCs that are not special vendors are treated as a normal shop
+        let vendor = name.and_then(sim::Vendor::find);
         in_game!(self, rt, cpu, sys, errors => {
-            sys.screen.transition_to_shop_buying(&mut cpu, &mut sys.overworld, true, errors)?;
+            match vendor {
+                Some(vendor) => {
+                    sys.screen.transition_to_vendor(&mut cpu, &mut sys.overworld, vendor, true, errors)?;
+                }
+                None => {
+                    sys.screen.transition_to_shop_buying(&mut cpu, &mut sys.overworld, true, errors)?;
+                }
+            }
             Ok(())
         })
     }
@@ -465,7 +626,8 @@ impl State {
     ) -> Result<Report<Self>, exec::Error> {
         log::debug!("handling CLOSE-SHOP");
         in_game!(self, rt, cpu, sys, errors => {
-            if !sys.screen.current_screen().is_shop() {
+            let screen = sys.screen.current_screen();
+            if !screen.is_shop() && !screen.is_dialog() {
                 errors.push(sim_error!(cpu.span, NotRightScreen));
                 return Ok(());
             }
@@ -492,6 +654,29 @@ impl State {
         })
     }
 
+    async fn handle_upgrade(
+        self,
+        rt: sim::Context<&sim::Runtime>,
+        item: &cir::ItemSelectSpec,
+    ) -> Result<Report<Self>, exec::Error> {
+        log::debug!("handling UPGRADE");
+        let item = item.clone();
+        in_game!(self, rt, cpu, sys, errors => {
+            sim::actions::upgrade_armor(&mut cpu, sys, errors, &item)
+        })
+    }
+
+    async fn handle_expand(
+        self,
+        rt: sim::Context<&sim::Runtime>,
+        category: cir::Category,
+    ) -> Result<Report<Self>, exec::Error> {
+        log::debug!("handling EXPAND");
+        in_game!(self, rt, cpu, sys, errors => {
+            sim::actions::expand_pouch(&mut cpu, sys, errors, category)
+        })
+    }
+
     async fn handle_buy(
         self,
         rt: sim::Context<&sim::Runtime>,
@@ -595,6 +780,30 @@ impl State {
         .await
     }
 
+    async fn handle_wait(
+        self,
+        rt: sim::Context<&sim::Runtime>,
+        seconds: u32,
+    ) -> Result<Report<Self>, exec::Error> {
+        log::debug!("handling WAIT");
+        in_game!(self, rt, cpu, sys, errors => {
+            sim::actions::wait(&mut cpu, sys, errors, seconds)
+        })
+    }
+
+    async fn handle_ground_limit(
+        self,
+        rt: sim::Context<&sim::Runtime>,
+        meta: &cir::GroundLimitMeta,
+    ) -> Result<Report<Self>, exec::Error> {
+        log::debug!("handling GROUND-LIMIT");
+        let meta = meta.clone();
+        in_game!(self, rt, _cpu, sys, _errors => {
+            sys.overworld.set_ground_limits(meta.materials, meta.weapons, meta.arrows);
+            Ok(())
+        })
+    }
+
     async fn handle_su_break(
         self,
         rt: sim::Context<&sim::Runtime>,
@@ -679,6 +888,7 @@ impl GameState {
         Self {
             process,
             systems: GameSystems::default(),
+            last_cpu: None,
         }
     }
 }
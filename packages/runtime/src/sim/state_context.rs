@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use blueflame::env::{DlcVer, Feature, FeatureSet};
 use blueflame::processor::{self, Cpu1, Cpu2, CrashReport};
 use teleparse::Span;
 
@@ -180,6 +181,12 @@ pub struct Context<T> {
     /// The handle for checking if the run should be aborted,
     /// if the step has potential long-running operations
     handle: Arc<sim::RunHandle>,
+    /// The BlueFlame features to execute the current step with
+    pub features: FeatureSet,
+    /// The DLC version to check DLC items and trials with
+    pub dlc: DlcVer,
+    /// Structured execution traces to record, if tracing is on
+    pub tracing: Option<Arc<sim::Tracing>>,
     /// The Runtime used for execution
     pub inner: T,
 }
@@ -189,6 +196,9 @@ impl<T> Context<T> {
         Self {
             span: Span::new(0, 0),
             handle,
+            features: Feature::default_const(),
+            dlc: DlcVer::V300,
+            tracing: None,
             inner,
         }
     }
@@ -210,11 +220,20 @@ impl Context<&sim::Runtime> {
     {
         let span = self.span;
         let handle = self.handle;
+        let features = self.features;
+        let dlc = self.dlc;
+        let tracing = self.tracing;
         self.inner
             .execute(move |cpu| {
+                // features are per-thread, so they must be set
+                // on the executor thread every time
+                blueflame::env::init_features(features);
                 let ctx = Context {
                     span,
                     handle,
+                    features,
+                    dlc,
+                    tracing,
                     inner: cpu,
                 };
                 f(ctx)
@@ -231,16 +250,30 @@ impl Context<&mut Cpu1> {
     {
         let span = self.span;
         let handle = self.handle;
+        let features = self.features;
+        let dlc = self.dlc;
+        let tracing = self.tracing;
         let cpu1 = self.inner;
         let mut cpu2 = Cpu2::new(cpu1, &mut state.process);
-        cpu2.with_crash_report(|cpu2| {
+        if let Some(t) = &tracing {
+            t.start(&cpu2);
+        }
+        let result = cpu2.with_crash_report(|cpu2| {
             let ctx = Context {
                 span,
                 handle,
+                features,
+                dlc,
+                tracing: tracing.clone(),
                 inner: cpu2,
             };
             f(ctx, &mut state.systems)
-        })?;
+        });
+        if let Some(t) = &tracing {
+            t.finish(&cpu2, span.lo);
+        }
+        result?;
+        state.last_cpu = Some(Box::new(cpu2.cpu1.cpu0.clone()));
         Ok(state)
     }
 
@@ -259,17 +292,34 @@ impl Context<&mut Cpu1> {
     {
         let span = self.span;
         let handle = self.handle;
+        let features = self.features;
+        let dlc = self.dlc;
+        let tracing = self.tracing;
         let cpu1 = self.inner;
         let mut cpu2 = Cpu2::new(cpu1, &mut state.process);
         let mut errors = vec![];
-        cpu2.with_crash_report(|cpu2| {
+        if let Some(t) = &tracing {
+            t.start(&cpu2);
+        }
+        let result = cpu2.with_crash_report(|cpu2| {
             let ctx = Context {
                 span,
                 handle,
+                features,
+                dlc,
+                tracing: tracing.clone(),
                 inner: cpu2,
             };
             f(ctx, &mut state.systems, &mut errors)
-        })?;
+        });
+        if let Some(t) = &tracing {
+            t.finish(&cpu2, span.lo);
+        }
+        result?;
+        for name in state.systems.overworld.take_evicted() {
+            errors.push(sim_warning!(span, GroundItemDespawned(name)));
+        }
+        state.last_cpu = Some(Box::new(cpu2.cpu1.cpu0.clone()));
         Ok(Report::with_errors(state, errors))
     }
 }
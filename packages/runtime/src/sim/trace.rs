@@ -0,0 +1,100 @@
+use std::ops::Range;
+use std::sync::Mutex;
+
+use blueflame::processor::Cpu2;
+use blueflame::trace::{TraceConfig, TraceFormat};
+use skybook_api::runtime::sim::RuntimeTraceParams;
+
+/// Structured execution traces recorded while running the steps
+///
+/// The tracer in BlueFlame is per-thread, so a trace is started
+/// and finished every time a step executes the game on the executor thread.
+/// Steps that are loaded from the state cache are not traced
+pub struct Tracing {
+    config: TraceConfig,
+    traces: Mutex<Vec<StepTrace>>,
+}
+
+/// Trace recorded when executing the game in a step
+#[derive(Debug, Clone)]
+pub struct StepTrace {
+    /// Byte position of the step in the script
+    pub pos: usize,
+    /// The recorded trace, in the format from the config
+    pub data: Vec<u8>,
+}
+
+impl Tracing {
+    pub fn new(config: TraceConfig) -> Self {
+        Self {
+            config,
+            traces: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Create the config from the init params. `Ok(None)` is returned
+    /// if nothing is traced, and `Err` with the range if a memory range is invalid
+    pub fn config_from_params(params: &RuntimeTraceParams) -> Result<Option<TraceConfig>, String> {
+        if !params.is_enabled() {
+            return Ok(None);
+        }
+        let mut mem_writes = Vec::with_capacity(params.mem_writes.len());
+        for range in &params.mem_writes {
+            let Some(range) = parse_mem_range(range) else {
+                return Err(range.clone());
+            };
+            mem_writes.push(range);
+        }
+        let format = if params.jsonl {
+            TraceFormat::Jsonl
+        } else {
+            TraceFormat::Binary
+        };
+        Ok(Some(TraceConfig {
+            format,
+            calls: params.calls,
+            gdt: params.gdt,
+            mem_writes,
+        }))
+    }
+
+    /// Start recording on the current thread
+    pub fn start(&self, cpu: &Cpu2) {
+        cpu.start_trace(self.config.clone());
+    }
+
+    /// Finish recording on the current thread, and store the trace for the step
+    pub fn finish(&self, cpu: &Cpu2, pos: usize) {
+        let Some(data) = cpu.finish_trace() else {
+            return;
+        };
+        self.traces
+            .lock()
+            .expect("failed to acquire lock for traces")
+            .push(StepTrace { pos, data });
+    }
+
+    /// Take out the traces recorded so far
+    pub fn take(&self) -> Vec<StepTrace> {
+        std::mem::take(
+            &mut *self
+                .traces
+                .lock()
+                .expect("failed to acquire lock for traces"),
+        )
+    }
+}
+
+/// Parse a memory range in the form of `0xSTART-0xEND`, where END is exclusive.
+/// `None` is returned if the range is malformed or empty
+pub fn parse_mem_range(range: &str) -> Option<Range<u64>> {
+    let (start, end) = range.split_once('-')?;
+    let start = parse_hex(start.trim())?;
+    let end = parse_hex(end.trim())?;
+    (start < end).then_some(start..end)
+}
+
+fn parse_hex(s: &str) -> Option<u64> {
+    let s = s.strip_prefix("0x")?;
+    u64::from_str_radix(s, 16).ok()
+}
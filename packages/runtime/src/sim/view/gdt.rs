@@ -259,6 +259,7 @@ pub fn extract_gdt_info_from_trigger_param(gdt: &gdt::TriggerParam) -> Result<iv
         is_sheika_slate_obtained: get_flag_value!(gdt.(bool) "IsGet_Obj_DRStone_Get")
             || get_flag_value!(gdt.(bool) "IsGet_Obj_DRStone_A_01"),
         is_paraglider_obtained: get_flag_value!(gdt.(bool) "IsGet_PlayerStole2"),
+        rupees: get_flag_value!(gdt.(s32) "CurrentRupee"),
     };
     Ok(info)
 }
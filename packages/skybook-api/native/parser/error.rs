@@ -77,8 +77,95 @@ pub enum ParserError {
     GdtMissingVecComp,
     #[error("`{1}` is not a valid number of slots for category `{0:?}`")]
     InvalidEquipmentSlotNum(cir::Category, i32),
+    #[error("`{0}` is not a valid duration")]
+    InvalidDuration(i32),
+    #[error("`{0}` is not a valid unit of time, valid units are seconds, minutes, hours and days")]
+    InvalidDurationUnit(String),
+    #[error(
+        "`{0}` is not a valid source to receive items from, valid sources are chest and npc, optionally followed by -before or -after"
+    )]
+    InvalidReceiveSource(String),
+    #[error("`{0}` is not a valid DLC version, valid versions are 0, 1, 2 and 3")]
+    InvalidDlcVersion(i32),
+    #[error("`{0}` is not a defined block")]
+    UndefinedBlock(String),
+    #[error("block `{0}` is already defined")]
+    DuplicateBlock(String),
+    #[error("blocks can only be defined at the top level of the script")]
+    NestedBlock,
+    #[error("block `{0}` takes {1} argument(s), but {2} were given")]
+    BlockArgCount(String, u32, u32),
+    #[error("`{0}` is not a parameter of the block")]
+    UnboundParam(String),
+    #[error("blocks are nested too deeply, the maximum depth is {0}")]
+    BlockTooDeep(u32),
+    #[error("too many steps are expanded from this statement, the maximum is {0}")]
+    TooManyExpandedSteps(u32),
+    #[error("`{0}` is not a valid number of times to repeat")]
+    InvalidRepeatCount(i32),
+    #[error("cannot load the included script `{0}`")]
+    IncludeNotFound(String),
+    #[error("the script `{0}` is already being included, which would include itself")]
+    IncludeCycle(String),
+    #[error("the included script `{0}` has errors")]
+    IncludeHasErrors(String),
+    #[error("`include` can only be used at the top level of a script")]
+    NestedInclude,
+    #[error("the label `{0}` is already defined")]
+    DuplicateLabel(String),
+    #[error("`label` can only be used at the top level of a script")]
+    NestedLabel,
+    #[error("`{0}` is not a defined variable")]
+    UndefinedVariable(String),
+    #[error("cannot divide by zero")]
+    DivideByZero,
+    #[error("`let` can only be used at the top level of a script")]
+    NestedLet,
+    #[error("`else` without a condition must be the last branch of `if`")]
+    ElseNotLast,
+    #[error("expected {0} after `{1}`")]
+    SyntaxExpected(SyntaxExpectation, String),
     //////////////////////////////////
     // Add new errors below
     // The translation files needs to be updated accordingly!!!
     //////////////////////////////////
 }
+
+/// What is expected after a command, when the syntax is wrong
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "__ts-binding", derive(ts_rs::TS))]
+#[cfg_attr(feature = "__ts-binding", ts(export))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(into_wasm_abi))]
+pub enum SyntaxExpectation {
+    //////////////////////////////////
+    // DO NOT update the enum names
+    // The translation files needs to be updated accordingly!!!
+    //////////////////////////////////
+    ItemList,
+    Item,
+    Category,
+    Number,
+    Name,
+    Path,
+    Condition,
+    Properties,
+    FlagName,
+}
+
+impl std::fmt::Display for SyntaxExpectation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::ItemList => "item list",
+            Self::Item => "item",
+            Self::Category => "category",
+            Self::Number => "number",
+            Self::Name => "name",
+            Self::Path => "quoted path",
+            Self::Condition => "condition",
+            Self::Properties => "properties in `[]`",
+            Self::FlagName => "flag name in `<>`",
+        };
+        f.write_str(s)
+    }
+}
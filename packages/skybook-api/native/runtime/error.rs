@@ -41,6 +41,14 @@ pub enum RuntimeInitError {
     InvalidStackStart,
     #[error("pmdm-addr param is invalid")]
     InvalidPmdmAddr,
+    #[error("unknown feature in the features param: {0}")]
+    InvalidFeature(String),
+    #[error("failed to parse the symbol map: {0}")]
+    InvalidSymbolMap(String),
+    #[error("invalid memory range to trace: {0}")]
+    InvalidTraceRange(String),
+    #[error("invalid memory range to watch: {0}")]
+    InvalidWatchRange(String),
     #[error(
         "the custom image provided has program-start = {0}, which does not match the one requested by the environment = {0}"
     )]
@@ -63,8 +71,12 @@ pub enum RuntimeError {
     // Keep the error names sorted
     // The translation files needs to be updated accordingly!!!
     //////////////////////////////////
+    #[error("the armor is already upgraded to the max")]
+    ArmorMaxStar,
     #[error("cannot auto switch screen because screen was switched manually")]
     CannotAutoSwitchScreen,
+    #[error("cannot check the condition because the game is not running")]
+    CannotCheckCondition,
     #[error("cannot do this while holding items in the inventory screen")]
     CannotDoWhileHoldingInInventory,
     #[error("cannot do this while holding items in the overworld")]
@@ -97,12 +109,22 @@ pub enum RuntimeError {
     CannotUseMore(usize),
     #[error("game has crashed in this step")]
     Crash,
+    #[error("`{0}` is auto-equipped")]
+    EquipmentAutoEquipped(String),
+    #[error("`{0}` broke")]
+    EquipmentBroke(String),
     #[error("unexpected executor error")]
     Executor,
+    #[error("`{0}` despawned because there are too many items on the ground")]
+    GroundItemDespawned(String),
     #[error("the `all but` syntax did not achieve the desired result")]
     InaccurateAllBut,
+    #[error("`{0}` is not a valid DLC version, valid versions are 0, 1, 2 and 3")]
+    InvalidDlcVersion(u32),
     #[error("this type of item is not accessible through quick menu")]
     InvalidDpadType,
+    #[error("unknown BlueFlame feature: `{0}`")]
+    InvalidFeature(String),
     #[error("array index `{2}` for the flag `{0}` with type `{1}` might be invalid")]
     InvalidGdtArrayIndex(String, String, usize),
     #[error("this action cannot be performed on the selected slot")]
@@ -125,12 +147,20 @@ pub enum RuntimeError {
     NoArrowsToShoot,
     #[error("no manual save has been made")]
     NoManualSave,
+    #[error("the item is not an arrow")]
+    NotArrow,
     #[error("this item cannot be dropped")]
     NotDroppable,
     #[error("this item cannot be eaten")]
     NotEatable,
+    #[error("this costs `{1}` of `{0}`, but only `{2}` are available")]
+    NotEnoughCurrency(String, i32, i32),
     #[error("this requires `{0}` items, but only `{1}` items found")]
     NotEnoughForAllBut(usize, usize),
+    #[error("this requires `{1}` of `{0}`, but only `{2}` are found")]
+    NotEnoughMaterials(String, usize, usize),
+    #[error("this costs `{0}` rupees, but only `{1}` rupees are available")]
+    NotEnoughRupees(i32, i32),
     #[error("the item is not an equipment")]
     NotEquipment,
     #[error("the item is not equipped in the overworld")]
@@ -143,10 +173,18 @@ pub enum RuntimeError {
     NotRightScreen,
     #[error("the item `{0}` is not sellable")]
     NotSellable(String),
+    #[error("the item `{0}` is not sold here")]
+    NotSoldByVendor(String),
+    #[error("the item `{0}` cannot be upgraded")]
+    NotUpgradable(String),
     #[error("this operation cannot be completed due to previous errors")]
     OperationNotComplete,
     #[error("cannot specify item position here")]
     PositionSpecNotAllowed,
+    #[error("the pouch is already expanded to the max")]
+    PouchFullyExpanded,
+    #[error("the `{0}` pouch cannot be expanded")]
+    PouchNotExpandable(crate::parser::cir::Category),
     #[error(
         "game has is closed in a previous step and you need to `reload` or `new-game` to continue"
     )]
@@ -157,6 +195,8 @@ pub enum RuntimeError {
     PreviousCrash,
     #[error("failed to reload gdt for save. This is a bug")]
     ReloadFail,
+    #[error("this requires DLC version `{0}`, but the DLC version is `{1}`")]
+    RequiresDlc(u32, u32),
     #[error("cannot find the named save `{0}`")]
     SaveNotFound(String),
     #[error("the runtime iteration limit is reached")]
@@ -167,6 +207,8 @@ pub enum RuntimeError {
         "this command or syntax is not implemented yet, please track the development on GitHub"
     )]
     Unimplemented,
+    #[error("the price of `{0}` is not known for this shop, so trading it is not supported yet")]
+    UnknownVendorPrice(String),
     #[error("amount can only be 1 when targeting equipped items in the overworld")]
     UselessAmountForOverworldEquipment,
     #[error("meta properties are ignored when targeting equipped items in the overworld")]
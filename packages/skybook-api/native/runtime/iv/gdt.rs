@@ -74,6 +74,8 @@ mod __impl {
         pub is_sheika_slate_obtained: bool,
         /// If the Paraglider was picker up
         pub is_paraglider_obtained: bool,
+        /// Number of rupees (CurrentRupee)
+        pub rupees: i32,
     }
 
     /// One item in GDT
@@ -97,6 +97,7 @@ mod __impl {
         Overworld,
         Inventory,
         Shop,
+        Dialog,
     }
 
     /// Info for an item in the PMDM. This struct can represent both
@@ -49,4 +49,93 @@ pub struct RuntimeInitParams {
     /// Unspecified (empty string) means using the internal default
     #[serde(default)]
     pub pmdm_addr: String,
+
+    /// BlueFlame features to turn on or off, on top of the defaults
+    ///
+    /// Each entry is a feature name in kebab-case (for example, `check-stack-corruption`).
+    /// Prefix the name with `-` to turn the feature off.
+    ///
+    /// Unspecified (empty) means using the default features
+    #[serde(default)]
+    pub features: Vec<String>,
+
+    /// Contents of a symbol map for the main module (CSV, IDA `.map`
+    /// or Ghidra export), used to show function names in crash reports
+    ///
+    /// Unspecified (empty string) means using the symbol map packed
+    /// in the image, if any
+    #[serde(default)]
+    pub symbol_map: String,
+
+    /// Structured execution trace to record when executing the steps
+    ///
+    /// Unspecified means no trace is recorded
+    #[serde(default)]
+    pub trace: RuntimeTraceParams,
+}
+
+/// What to record in the structured execution trace, see `blueflame::trace`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "__ts-binding", derive(ts_rs::TS))]
+#[cfg_attr(feature = "__ts-binding", ts(export))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(into_wasm_abi, from_wasm_abi))]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeTraceParams {
+    /// Record function calls and returns
+    #[serde(default)]
+    pub calls: bool,
+
+    /// Record GDT flag accesses
+    #[serde(default)]
+    pub gdt: bool,
+
+    /// Record memory writes to these address ranges
+    ///
+    /// Each range looks like 0xSTART-0xEND, where END is exclusive
+    #[serde(default)]
+    pub mem_writes: Vec<String>,
+
+    /// Record the trace as JSONL instead of the compact binary format
+    #[serde(default)]
+    pub jsonl: bool,
+}
+
+impl RuntimeTraceParams {
+    /// Check if anything is recorded with these params
+    pub fn is_enabled(&self) -> bool {
+        self.calls || self.gdt || !self.mem_writes.is_empty()
+    }
+}
+
+/// A memory range to watch while running the steps, see `Runtime::set_watchpoints`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "__ts-binding", derive(ts_rs::TS))]
+#[cfg_attr(feature = "__ts-binding", ts(export))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(into_wasm_abi, from_wasm_abi))]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeWatchpoint {
+    /// Physical address range, looks like 0xSTART-0xEND, where END is exclusive
+    pub range: String,
+
+    /// Which accesses to watch
+    pub kind: RuntimeWatchKind,
+
+    /// Crash the game when the watchpoint is hit
+    #[serde(default)]
+    pub break_on_hit: bool,
+}
+
+/// Which accesses trigger a [`RuntimeWatchpoint`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "__ts-binding", derive(ts_rs::TS))]
+#[cfg_attr(feature = "__ts-binding", ts(export))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(into_wasm_abi, from_wasm_abi))]
+#[serde(rename_all = "camelCase")]
+pub enum RuntimeWatchKind {
+    Read,
+    Write,
+    ReadWrite,
 }
@@ -13,6 +13,13 @@ pub struct ErrorReport<T> {
     pub span: (usize, usize),
     pub is_warning: bool,
     pub error: T,
+    /// Suggested replacements for the text in the span, best first.
+    ///
+    /// For example, similar item names when an item cannot be resolved
+    pub suggestions: Vec<String>,
+    /// The script the span is in. 0 is the main script, other values
+    /// are scripts included with `include`
+    pub file: u32,
 }
 impl<E> ErrorReport<E> {
     pub fn error<T: ToSpan>(t: T, error: E) -> Self {
@@ -21,6 +28,8 @@ impl<E> ErrorReport<E> {
             span: (span.lo, span.hi),
             is_warning: false,
             error,
+            suggestions: vec![],
+            file: 0,
         }
     }
     pub fn warning<T: ToSpan>(t: T, error: E) -> Self {
@@ -29,6 +38,18 @@ impl<E> ErrorReport<E> {
             span: (span.lo, span.hi),
             is_warning: true,
             error,
+            suggestions: vec![],
+            file: 0,
         }
     }
+    /// Attach suggested replacements to the report
+    pub fn with_suggestions(mut self, suggestions: Vec<String>) -> Self {
+        self.suggestions = suggestions;
+        self
+    }
+    /// Set the script the span is in
+    pub fn in_file(mut self, file: u32) -> Self {
+        self.file = file;
+        self
+    }
 }
@@ -6,6 +6,7 @@ import {
     parseEnvImage,
     parseRegionSize,
     parseEnvDlcVersion,
+    parseEnvFeatures,
 } from "./env_parser.ts";
 
 describe("parseEnvFromScript", () => {
@@ -23,6 +24,8 @@ describe("parseEnvFromScript", () => {
                 stackSize: 0,
                 heapFreeSize: 0,
                 pmdmAddr: "",
+                features: [],
+                symbolMap: "",
             },
             errors: [],
         });
@@ -37,10 +40,11 @@ stack-start = 34500000
 stack-size = 0x345000
 heap-free-size = 0x345000
 pmdm-addr = 0x34500000
+features = limited-block-count, -check-stack-corruption
 '''
 `),
         ).toEqual({
-            lines: [2, 9],
+            lines: [2, 10],
             image: "1.5.0",
             params: {
                 dlc: 3,
@@ -49,6 +53,8 @@ pmdm-addr = 0x34500000
                 stackSize: 0x345000,
                 heapFreeSize: 0x345000,
                 pmdmAddr: "0x0000000034500000",
+                features: ["limited-block-count", "-check-stack-corruption"],
+                symbolMap: "",
             },
             errors: [],
         });
@@ -99,6 +105,20 @@ describe("parseEnvDlcVersion", () => {
     });
 });
 
+describe("parseEnvFeatures", () => {
+    it.each(["", " ", ",", " , "])("parses empty", (input) => {
+        expect(parseEnvFeatures(input)).toEqual([]);
+    });
+    it("parses separated list", () => {
+        expect(parseEnvFeatures("a-b,-c d, +e")).toEqual([
+            "a-b",
+            "-c",
+            "d",
+            "+e",
+        ]);
+    });
+});
+
 describe("parseAbsAddrString", () => {
     it.each(["", "0", "0x0", "0x00"])("parses empty", (input) => {
         expect(parseRegionStart(input)).toEqual({
@@ -1,7 +1,7 @@
 /** Parser for the env tag in the script */
 
 import type { Result } from "@pistonite/pure/result";
-import type { RuntimeInitParams } from "./native";
+import type { RuntimeInitParams, RuntimeTraceParams } from "./native";
 
 /** Parse the leading env tag from the script */
 export const parseEnvFromScript = (script: string): ScriptEnv => {
@@ -12,6 +12,9 @@ export const parseEnvFromScript = (script: string): ScriptEnv => {
         stackSize: 0,
         heapFreeSize: 0,
         pmdmAddr: "",
+        features: [],
+        symbolMap: "",
+        trace: { calls: false, gdt: false, memWrites: [], jsonl: false },
     };
     const lines = script.split("\n");
     let i = 0;
@@ -132,6 +135,14 @@ export const parseEnvFromScript = (script: string): ScriptEnv => {
                 }
                 break;
             }
+            case "features": {
+                params.features = parseEnvFeatures(val);
+                break;
+            }
+            case "trace": {
+                params.trace = parseEnvTrace(val);
+                break;
+            }
             default: {
                 errors.push({
                     type: "UnknownKey",
@@ -210,6 +221,18 @@ export const parseEnvDlcVersion = (dlc: string): number => {
             return 3;
     }
 };
+/**
+ * Parse the feature toggles, separated by comma or whitespace.
+ *
+ * The names are validated when the runtime is initialized
+ */
+export const parseEnvFeatures = (features: string): string[] => {
+    return features
+        .split(/[\s,]+/)
+        .map((x) => x.trim())
+        .filter(Boolean);
+};
+
 /** Input is hex string with optional 0x prefix */
 export const parseRegionStart = (
     addr: string,
@@ -306,3 +329,36 @@ export type ScriptEnvError = {
           key: string;
       }
 );
+
+/**
+ * Parse what to trace, separated by comma or whitespace.
+ *
+ * `calls`, `gdt` and `jsonl` turn on the option with the same name,
+ * and the other values are memory ranges to trace writes to.
+ * The ranges are validated when the runtime is initialized
+ */
+export const parseEnvTrace = (trace: string): RuntimeTraceParams => {
+    const params: RuntimeTraceParams = {
+        calls: false,
+        gdt: false,
+        memWrites: [],
+        jsonl: false,
+    };
+    for (const value of parseEnvFeatures(trace)) {
+        switch (value) {
+            case "calls":
+                params.calls = true;
+                break;
+            case "gdt":
+                params.gdt = true;
+                break;
+            case "jsonl":
+                params.jsonl = true;
+                break;
+            default:
+                params.memWrites.push(value);
+                break;
+        }
+    }
+    return params;
+};
@@ -27,6 +27,23 @@ export interface ExtensionApp {
      */
     setScript(script: string, position: number): WxPromise<void>;
 
+    /**
+     * Get the scripts that can be included with `include "PATH"`,
+     * as a map from the path to the content of the script.
+     */
+    getIncludedScripts(): WxPromise<Record<string, string>>;
+
+    /**
+     * Add or replace the script that can be included with `include "PATH"`,
+     * or remove it if `script` is undefined.
+     *
+     * This will trigger a rerun of the simulation
+     */
+    setIncludedScript(
+        path: string,
+        script: string | undefined,
+    ): WxPromise<void>;
+
     /**
      * Resolve an item from a query
      *
@@ -114,6 +131,16 @@ export interface ExtensionApp {
         bytePos: number,
     ): WxPromise<MaybeAborted<Diagnostic[]>>;
 
+    /**
+     * Get information diagnostics for how the steps were run: the branch
+     * taken by each `if`, and the steps that are skipped because their
+     * branch is not taken
+     */
+    provideStepRunDiagnostics(
+        script: string,
+        taskId: string,
+    ): WxPromise<MaybeAborted<Diagnostic[]>>;
+
     /**
      * Get the pouch (visible inventory) state.
      *
@@ -55,6 +55,14 @@ export class _wxSenderImpl implements ExtensionApp {
         return this.sender.send<MaybeAborted<Result<InvView_Gdt, RuntimeViewError>>>(23 /* ExtensionApp.getGdtInventory */, [ taskId, script, charPos ]);
     }
 
+    /**
+     * Get the scripts that can be included with `include "PATH"`,
+     * as a map from the path to the content of the script.
+     */
+    public getIncludedScripts( ): WxPromise<Record<string, string>> {
+        return this.sender.send<Record<string, string>>(24 /* ExtensionApp.getIncludedScripts */, [ ]);
+    }
+
     /**
      * Get the overworld state at the character position `charPos` in the script
      * 
@@ -63,7 +71,7 @@ export class _wxSenderImpl implements ExtensionApp {
      * `charPos` defaults to 0
      */
     public getOverworldItems( taskId: string, script: string | undefined, charPos: number | undefined ): WxPromise<MaybeAborted<Result<InvView_Overworld, RuntimeViewError>>> {
-        return this.sender.send<MaybeAborted<Result<InvView_Overworld, RuntimeViewError>>>(24 /* ExtensionApp.getOverworldItems */, [ taskId, script, charPos ]);
+        return this.sender.send<MaybeAborted<Result<InvView_Overworld, RuntimeViewError>>>(25 /* ExtensionApp.getOverworldItems */, [ taskId, script, charPos ]);
     }
 
     /**
@@ -74,7 +82,7 @@ export class _wxSenderImpl implements ExtensionApp {
      * `charPos` defaults to 0
      */
     public getPouchList( taskId: string, script: string | undefined, charPos: number | undefined ): WxPromise<MaybeAborted<Result<InvView_PouchList, RuntimeViewError>>> {
-        return this.sender.send<MaybeAborted<Result<InvView_PouchList, RuntimeViewError>>>(25 /* ExtensionApp.getPouchList */, [ taskId, script, charPos ]);
+        return this.sender.send<MaybeAborted<Result<InvView_PouchList, RuntimeViewError>>>(26 /* ExtensionApp.getPouchList */, [ taskId, script, charPos ]);
     }
 
     /**
@@ -86,7 +94,7 @@ export class _wxSenderImpl implements ExtensionApp {
      * `charPos` defaults to 0
      */
     public getSaveInventory( taskId: string, script: string | undefined, charPos: number | undefined, name: string | undefined ): WxPromise<MaybeAborted<Result<InvView_Gdt, RuntimeViewError>>> {
-        return this.sender.send<MaybeAborted<Result<InvView_Gdt, RuntimeViewError>>>(26 /* ExtensionApp.getSaveInventory */, [ taskId, script, charPos, name ]);
+        return this.sender.send<MaybeAborted<Result<InvView_Gdt, RuntimeViewError>>>(27 /* ExtensionApp.getSaveInventory */, [ taskId, script, charPos, name ]);
     }
 
     /**
@@ -98,14 +106,14 @@ export class _wxSenderImpl implements ExtensionApp {
      * `charPos` defaults to 0
      */
     public getSaveNames( taskId: string, script: string | undefined, charPos: number | undefined ): WxPromise<MaybeAborted<string[]>> {
-        return this.sender.send<MaybeAborted<string[]>>(27 /* ExtensionApp.getSaveNames */, [ taskId, script, charPos ]);
+        return this.sender.send<MaybeAborted<string[]>>(28 /* ExtensionApp.getSaveNames */, [ taskId, script, charPos ]);
     }
 
     /**
      * Get the current simulator script.
      */
     public getScript( ): WxPromise<string> {
-        return this.sender.send<string>(28 /* ExtensionApp.getScript */, [ ]);
+        return this.sender.send<string>(29 /* ExtensionApp.getScript */, [ ]);
     }
 
     /**
@@ -115,11 +123,11 @@ export class _wxSenderImpl implements ExtensionApp {
      * if you need the character positions.
      */
     public getStepBytePositions( script: string ): WxPromise<Uint32Array> {
-        return this.sender.send<Uint32Array>(29 /* ExtensionApp.getStepBytePositions */, [ script ]);
+        return this.sender.send<Uint32Array>(30 /* ExtensionApp.getStepBytePositions */, [ script ]);
     }
 
     public getStepCharPositions( script: string ): WxPromise<Uint32Array> {
-        return this.sender.send<Uint32Array>(30 /* ExtensionApp.getStepCharPositions */, [ script ]);
+        return this.sender.send<Uint32Array>(31 /* ExtensionApp.getStepCharPositions */, [ script ]);
     }
 
     /**
@@ -130,14 +138,14 @@ export class _wxSenderImpl implements ExtensionApp {
      * `charPos` defaults to 0
      */
     public getStepFromCharPos( script: string | undefined, charPos: number | undefined ): WxPromise<number> {
-        return this.sender.send<number>(31 /* ExtensionApp.getStepFromCharPos */, [ script, charPos ]);
+        return this.sender.send<number>(32 /* ExtensionApp.getStepFromCharPos */, [ script, charPos ]);
     }
 
     /**
      * Invoke the parser for the script and get the diagnostics.
      */
     public provideParserDiagnostics( script: string ): WxPromise<Diagnostic[]> {
-        return this.sender.send<Diagnostic[]>(32 /* ExtensionApp.provideParserDiagnostics */, [ script ]);
+        return this.sender.send<Diagnostic[]>(33 /* ExtensionApp.provideParserDiagnostics */, [ script ]);
     }
 
     /**
@@ -145,14 +153,14 @@ export class _wxSenderImpl implements ExtensionApp {
      * the step that contains the bytePos.
      */
     public providePartialRuntimeDiagnostics( script: string, taskId: string, bytePos: number ): WxPromise<MaybeAborted<Diagnostic[]>> {
-        return this.sender.send<MaybeAborted<Diagnostic[]>>(33 /* ExtensionApp.providePartialRuntimeDiagnostics */, [ script, taskId, bytePos ]);
+        return this.sender.send<MaybeAborted<Diagnostic[]>>(34 /* ExtensionApp.providePartialRuntimeDiagnostics */, [ script, taskId, bytePos ]);
     }
 
     /**
      * Get the diagnostics from running the script.
      */
     public provideRuntimeDiagnostics( script: string, taskId: string ): WxPromise<MaybeAborted<Diagnostic[]>> {
-        return this.sender.send<MaybeAborted<Diagnostic[]>>(34 /* ExtensionApp.provideRuntimeDiagnostics */, [ script, taskId ]);
+        return this.sender.send<MaybeAborted<Diagnostic[]>>(35 /* ExtensionApp.provideRuntimeDiagnostics */, [ script, taskId ]);
     }
 
     /**
@@ -164,7 +172,16 @@ export class _wxSenderImpl implements ExtensionApp {
      * (Note this is different from Runtime.getSemanticTokens)
      */
     public provideSemanticTokens( script: string, start: number, end: number ): WxPromise<Uint32Array> {
-        return this.sender.send<Uint32Array>(35 /* ExtensionApp.provideSemanticTokens */, [ script, start, end ]);
+        return this.sender.send<Uint32Array>(36 /* ExtensionApp.provideSemanticTokens */, [ script, start, end ]);
+    }
+
+    /**
+     * Get information diagnostics for how the steps were run: the branch
+     * taken by each `if`, and the steps that are skipped because their
+     * branch is not taken
+     */
+    public provideStepRunDiagnostics( script: string, taskId: string ): WxPromise<MaybeAborted<Diagnostic[]>> {
+        return this.sender.send<MaybeAborted<Diagnostic[]>>(37 /* ExtensionApp.provideStepRunDiagnostics */, [ script, taskId ]);
     }
 
     /**
@@ -177,7 +194,7 @@ export class _wxSenderImpl implements ExtensionApp {
      * before canceling the previous one
      */
     public requestNewTaskIds( uniqueId: string, count: number ): WxPromise<string[]> {
-        return this.sender.send<string[]>(36 /* ExtensionApp.requestNewTaskIds */, [ uniqueId, count ]);
+        return this.sender.send<string[]>(38 /* ExtensionApp.requestNewTaskIds */, [ uniqueId, count ]);
     }
 
     /**
@@ -190,7 +207,17 @@ export class _wxSenderImpl implements ExtensionApp {
      * even when there is no error, the search result could be empty.
      */
     public resolveItem( query: string, localized: boolean, limit: number ): WxPromise<Result<ItemSearchResult[], string>> {
-        return this.sender.send<Result<ItemSearchResult[], string>>(37 /* ExtensionApp.resolveItem */, [ query, localized, limit ]);
+        return this.sender.send<Result<ItemSearchResult[], string>>(39 /* ExtensionApp.resolveItem */, [ query, localized, limit ]);
+    }
+
+    /**
+     * Add or replace the script that can be included with `include "PATH"`,
+     * or remove it if `script` is undefined.
+     * 
+     * This will trigger a rerun of the simulation
+     */
+    public setIncludedScript( path: string, script: string | undefined ): WxPromise<void> {
+        return this.sender.sendVoid(40 /* ExtensionApp.setIncludedScript */, [ path, script ]);
     }
 
     /**
@@ -202,7 +229,7 @@ export class _wxSenderImpl implements ExtensionApp {
      * a character offset (not byte offset) and is 0-based.
      */
     public setScript( script: string, position: number ): WxPromise<void> {
-        return this.sender.sendVoid(38 /* ExtensionApp.setScript */, [ script, position ]);
+        return this.sender.sendVoid(41 /* ExtensionApp.setScript */, [ script, position ]);
     }
 }
 
@@ -223,62 +250,73 @@ export const _wxRecverImpl = (handler: ExtensionApp): WxBusRecvHandler => {
             const [ a0, a1, a2 ] = args;
             return handler.getGdtInventory( a0, a1, a2 );
         }
-        case 24 /* ExtensionApp.getOverworldItems */: {
+        case 24 /* ExtensionApp.getIncludedScripts */: {
+            return handler.getIncludedScripts();
+        }
+        case 25 /* ExtensionApp.getOverworldItems */: {
             const [ a0, a1, a2 ] = args;
             return handler.getOverworldItems( a0, a1, a2 );
         }
-        case 25 /* ExtensionApp.getPouchList */: {
+        case 26 /* ExtensionApp.getPouchList */: {
             const [ a0, a1, a2 ] = args;
             return handler.getPouchList( a0, a1, a2 );
         }
-        case 26 /* ExtensionApp.getSaveInventory */: {
+        case 27 /* ExtensionApp.getSaveInventory */: {
             const [ a0, a1, a2, a3 ] = args;
             return handler.getSaveInventory( a0, a1, a2, a3 );
         }
-        case 27 /* ExtensionApp.getSaveNames */: {
+        case 28 /* ExtensionApp.getSaveNames */: {
             const [ a0, a1, a2 ] = args;
             return handler.getSaveNames( a0, a1, a2 );
         }
-        case 28 /* ExtensionApp.getScript */: {
+        case 29 /* ExtensionApp.getScript */: {
             return handler.getScript();
         }
-        case 29 /* ExtensionApp.getStepBytePositions */: {
+        case 30 /* ExtensionApp.getStepBytePositions */: {
             const [ a0 ] = args;
             return handler.getStepBytePositions( a0 );
         }
-        case 30 /* ExtensionApp.getStepCharPositions */: {
+        case 31 /* ExtensionApp.getStepCharPositions */: {
             const [ a0 ] = args;
             return handler.getStepCharPositions( a0 );
         }
-        case 31 /* ExtensionApp.getStepFromCharPos */: {
+        case 32 /* ExtensionApp.getStepFromCharPos */: {
             const [ a0, a1 ] = args;
             return handler.getStepFromCharPos( a0, a1 );
         }
-        case 32 /* ExtensionApp.provideParserDiagnostics */: {
+        case 33 /* ExtensionApp.provideParserDiagnostics */: {
             const [ a0 ] = args;
             return handler.provideParserDiagnostics( a0 );
         }
-        case 33 /* ExtensionApp.providePartialRuntimeDiagnostics */: {
+        case 34 /* ExtensionApp.providePartialRuntimeDiagnostics */: {
             const [ a0, a1, a2 ] = args;
             return handler.providePartialRuntimeDiagnostics( a0, a1, a2 );
         }
-        case 34 /* ExtensionApp.provideRuntimeDiagnostics */: {
+        case 35 /* ExtensionApp.provideRuntimeDiagnostics */: {
             const [ a0, a1 ] = args;
             return handler.provideRuntimeDiagnostics( a0, a1 );
         }
-        case 35 /* ExtensionApp.provideSemanticTokens */: {
+        case 36 /* ExtensionApp.provideSemanticTokens */: {
             const [ a0, a1, a2 ] = args;
             return handler.provideSemanticTokens( a0, a1, a2 );
         }
-        case 36 /* ExtensionApp.requestNewTaskIds */: {
+        case 37 /* ExtensionApp.provideStepRunDiagnostics */: {
+            const [ a0, a1 ] = args;
+            return handler.provideStepRunDiagnostics( a0, a1 );
+        }
+        case 38 /* ExtensionApp.requestNewTaskIds */: {
             const [ a0, a1 ] = args;
             return handler.requestNewTaskIds( a0, a1 );
         }
-        case 37 /* ExtensionApp.resolveItem */: {
+        case 39 /* ExtensionApp.resolveItem */: {
             const [ a0, a1, a2 ] = args;
             return handler.resolveItem( a0, a1, a2 );
         }
-        case 38 /* ExtensionApp.setScript */: {
+        case 40 /* ExtensionApp.setIncludedScript */: {
+            const [ a0, a1 ] = args;
+            return handler.setIncludedScript( a0, a1 );
+        }
+        case 41 /* ExtensionApp.setScript */: {
             const [ a0, a1 ] = args;
             return handler.setScript( a0, a1 );
         }
@@ -8,8 +8,8 @@ import type { Runtime } from "../runtime_api.ts";
 
 import type { WxPromise, WxBusRecvHandler, WxProtocolBoundSender } from "@pistonite/workex";
 import type { Result } from "@pistonite/pure/result";
-import type { ErrorReport, InvView_Gdt, InvView_Overworld, InvView_PouchList, MaybeAborted, ParserError, RuntimeError, RuntimeViewError } from "../native";
-import type { ItemSearchResult, RuntimeWorkerInitArgs, RuntimeWorkerInitOutput, RuntimeWorkerInitError } from "../types.ts";
+import type { ErrorReport, InvView_Gdt, InvView_Overworld, InvView_PouchList, MaybeAborted, ParserError, RuntimeError, RuntimeInitError, RuntimeViewError, RuntimeWatchpoint } from "../native";
+import type { ItemSearchResult, RuntimeWorkerInitArgs, RuntimeWorkerInitOutput, RuntimeWorkerInitError, StepRunEntry } from "../types.ts";
 
 /*
  * These generated implementations are used internally by other generated code.
@@ -30,7 +30,7 @@ export class _wxSenderImpl implements Runtime {
      * Abort a task by task id passed into one of the runtime functions that execute the script
      */
     public abortTask( taskId: string ): WxPromise<void> {
-        return this.sender.sendVoid(39 /* Runtime.abortTask */, [ taskId ]);
+        return this.sender.sendVoid(42 /* Runtime.abortTask */, [ taskId ]);
     }
 
     /**
@@ -41,7 +41,7 @@ export class _wxSenderImpl implements Runtime {
      * in the background if it didn't change.
      */
     public executeScript( script: string, taskId: string ): WxPromise<void> {
-        return this.sender.sendVoid(40 /* Runtime.executeScript */, [ script, taskId ]);
+        return this.sender.sendVoid(43 /* Runtime.executeScript */, [ script, taskId ]);
     }
 
     /**
@@ -52,7 +52,7 @@ export class _wxSenderImpl implements Runtime {
      * The taskId should be a UUID, and can be passed into abortTask() to abort this run
      */
     public getCrashInfo( script: string, taskId: string, pos: number ): WxPromise<MaybeAborted<string>> {
-        return this.sender.send<MaybeAborted<string>>(41 /* Runtime.getCrashInfo */, [ script, taskId, pos ]);
+        return this.sender.send<MaybeAborted<string>>(44 /* Runtime.getCrashInfo */, [ script, taskId, pos ]);
     }
 
     /**
@@ -62,7 +62,7 @@ export class _wxSenderImpl implements Runtime {
      * The taskId should be a UUID, and can be passed into abortTask() to abort this run
      */
     public getGdtInventory( script: string, taskId: string, pos: number ): WxPromise<MaybeAborted<Result<InvView_Gdt, RuntimeViewError>>> {
-        return this.sender.send<MaybeAborted<Result<InvView_Gdt, RuntimeViewError>>>(42 /* Runtime.getGdtInventory */, [ script, taskId, pos ]);
+        return this.sender.send<MaybeAborted<Result<InvView_Gdt, RuntimeViewError>>>(45 /* Runtime.getGdtInventory */, [ script, taskId, pos ]);
     }
 
     /**
@@ -72,7 +72,7 @@ export class _wxSenderImpl implements Runtime {
      * The taskId should be a UUID, and can be passed into abortTask() to abort this run
      */
     public getOverworldItems( script: string, taskId: string, pos: number ): WxPromise<MaybeAborted<Result<InvView_Overworld, RuntimeViewError>>> {
-        return this.sender.send<MaybeAborted<Result<InvView_Overworld, RuntimeViewError>>>(43 /* Runtime.getOverworldItems */, [ script, taskId, pos ]);
+        return this.sender.send<MaybeAborted<Result<InvView_Overworld, RuntimeViewError>>>(46 /* Runtime.getOverworldItems */, [ script, taskId, pos ]);
     }
 
     /**
@@ -81,7 +81,7 @@ export class _wxSenderImpl implements Runtime {
      * Note that the span in the errors are byte offsets, not character offsets.
      */
     public getParserDiagnostics( script: string ): WxPromise<ErrorReport<ParserError>[]> {
-        return this.sender.send<ErrorReport<ParserError>[]>(44 /* Runtime.getParserDiagnostics */, [ script ]);
+        return this.sender.send<ErrorReport<ParserError>[]>(47 /* Runtime.getParserDiagnostics */, [ script ]);
     }
 
     /**
@@ -91,7 +91,7 @@ export class _wxSenderImpl implements Runtime {
      * The taskId should be a UUID, and can be passed into abortTask() to abort this run
      */
     public getPouchList( script: string, taskId: string, pos: number ): WxPromise<MaybeAborted<Result<InvView_PouchList, RuntimeViewError>>> {
-        return this.sender.send<MaybeAborted<Result<InvView_PouchList, RuntimeViewError>>>(45 /* Runtime.getPouchList */, [ script, taskId, pos ]);
+        return this.sender.send<MaybeAborted<Result<InvView_PouchList, RuntimeViewError>>>(48 /* Runtime.getPouchList */, [ script, taskId, pos ]);
     }
 
     /**
@@ -103,7 +103,7 @@ export class _wxSenderImpl implements Runtime {
      * The taskId should be a UUID, and can be passed into abortTask() to abort this run
      */
     public getRuntimeDiagnostics( script: string, taskId: string, bytePos: number ): WxPromise<MaybeAborted<ErrorReport<RuntimeError>[]>> {
-        return this.sender.send<MaybeAborted<ErrorReport<RuntimeError>[]>>(46 /* Runtime.getRuntimeDiagnostics */, [ script, taskId, bytePos ]);
+        return this.sender.send<MaybeAborted<ErrorReport<RuntimeError>[]>>(49 /* Runtime.getRuntimeDiagnostics */, [ script, taskId, bytePos ]);
     }
 
     /**
@@ -113,7 +113,7 @@ export class _wxSenderImpl implements Runtime {
      * The taskId should be a UUID, and can be passed into abortTask() to abort this run
      */
     public getSaveInventory( script: string, taskId: string, pos: number, name: string | undefined ): WxPromise<MaybeAborted<Result<InvView_Gdt, RuntimeViewError>>> {
-        return this.sender.send<MaybeAborted<Result<InvView_Gdt, RuntimeViewError>>>(47 /* Runtime.getSaveInventory */, [ script, taskId, pos, name ]);
+        return this.sender.send<MaybeAborted<Result<InvView_Gdt, RuntimeViewError>>>(50 /* Runtime.getSaveInventory */, [ script, taskId, pos, name ]);
     }
 
     /**
@@ -123,7 +123,7 @@ export class _wxSenderImpl implements Runtime {
      * The taskId should be a UUID, and can be passed into abortTask() to abort this run
      */
     public getSaveNames( script: string, taskId: string, pos: number ): WxPromise<MaybeAborted<string[]>> {
-        return this.sender.send<MaybeAborted<string[]>>(48 /* Runtime.getSaveNames */, [ script, taskId, pos ]);
+        return this.sender.send<MaybeAborted<string[]>>(51 /* Runtime.getSaveNames */, [ script, taskId, pos ]);
     }
 
     /**
@@ -134,28 +134,49 @@ export class _wxSenderImpl implements Runtime {
      * The offsets in both inputs and outputs should be byte offsets, not character offsets.
      */
     public getSemanticTokens( script: string, start: number, end: number ): WxPromise<Uint32Array> {
-        return this.sender.send<Uint32Array>(49 /* Runtime.getSemanticTokens */, [ script, start, end ]);
+        return this.sender.send<Uint32Array>(52 /* Runtime.getSemanticTokens */, [ script, start, end ]);
     }
 
     /**
      * Get the starting byte positions for each step
      */
     public getStepBytePositions( script: string ): WxPromise<Uint32Array> {
-        return this.sender.send<Uint32Array>(50 /* Runtime.getStepBytePositions */, [ script ]);
+        return this.sender.send<Uint32Array>(53 /* Runtime.getStepBytePositions */, [ script ]);
     }
 
     /**
      * Get index of the step from byte position in the script
      */
     public getStepFromPos( script: string, pos: number ): WxPromise<number> {
-        return this.sender.send<number>(51 /* Runtime.getStepFromPos */, [ script, pos ]);
+        return this.sender.send<number>(54 /* Runtime.getStepFromPos */, [ script, pos ]);
+    }
+
+    /**
+     * Run the script and get how each step was run, up to and including
+     * the step containing the bytePos. See StepRunEntry
+     * 
+     * The taskId should be a UUID, and can be passed into abortTask() to abort this run
+     */
+    public getStepRuns( script: string, taskId: string, bytePos: number ): WxPromise<MaybeAborted<StepRunEntry[]>> {
+        return this.sender.send<MaybeAborted<StepRunEntry[]>>(55 /* Runtime.getStepRuns */, [ script, taskId, bytePos ]);
+    }
+
+    /**
+     * Execute the script if not up-to-date, and return the memory accesses that hit
+     * watchpoints in the step at the byte offset `pos` in the script, formatted with
+     * the stack traces. See setWatchpoints()
+     * 
+     * The taskId should be a UUID, and can be passed into abortTask() to abort this run
+     */
+    public getWatchHits( script: string, taskId: string, pos: number ): WxPromise<MaybeAborted<string[]>> {
+        return this.sender.send<MaybeAborted<string[]>>(56 /* Runtime.getWatchHits */, [ script, taskId, pos ]);
     }
 
     /**
      * Initialize the runtime with the given arguments.
      */
     public initialize( args: RuntimeWorkerInitArgs ): WxPromise<Result<RuntimeWorkerInitOutput, RuntimeWorkerInitError>> {
-        return this.sender.send<Result<RuntimeWorkerInitOutput, RuntimeWorkerInitError>>(52 /* Runtime.initialize */, [ args ]);
+        return this.sender.send<Result<RuntimeWorkerInitOutput, RuntimeWorkerInitError>>(57 /* Runtime.initialize */, [ args ]);
     }
 
     /**
@@ -163,7 +184,24 @@ export class _wxSenderImpl implements Runtime {
      * Returns an empty list if no items are found.
      */
     public resolveItemIdent( query: string ): WxPromise<ItemSearchResult[]> {
-        return this.sender.send<ItemSearchResult[]>(53 /* Runtime.resolveItemIdent */, [ query ]);
+        return this.sender.send<ItemSearchResult[]>(58 /* Runtime.resolveItemIdent */, [ query ]);
+    }
+
+    /**
+     * Replace the scripts that can be included with `include "PATH"`,
+     * as a map from the path to the content of the script.
+     * The next parse and run use the new scripts
+     */
+    public setIncludedScripts( scripts: Record<string, string> ): WxPromise<void> {
+        return this.sender.sendVoid(59 /* Runtime.setIncludedScripts */, [ scripts ]);
+    }
+
+    /**
+     * Replace the memory watchpoints. The next run executes all the steps again
+     * to record the hits, which can be read with getWatchHits()
+     */
+    public setWatchpoints( watchpoints: RuntimeWatchpoint[] ): WxPromise<Result<void, RuntimeInitError>> {
+        return this.sender.send<Result<void, RuntimeInitError>>(60 /* Runtime.setWatchpoints */, [ watchpoints ]);
     }
 }
 
@@ -172,65 +210,81 @@ export class _wxSenderImpl implements Runtime {
  */
 export const _wxRecverImpl = (handler: Runtime): WxBusRecvHandler => {
     return ((fId, args: any[]) => { switch (fId) {
-        case 39 /* Runtime.abortTask */: {
+        case 42 /* Runtime.abortTask */: {
             const [ a0 ] = args;
             return handler.abortTask( a0 );
         }
-        case 40 /* Runtime.executeScript */: {
+        case 43 /* Runtime.executeScript */: {
             const [ a0, a1 ] = args;
             return handler.executeScript( a0, a1 );
         }
-        case 41 /* Runtime.getCrashInfo */: {
+        case 44 /* Runtime.getCrashInfo */: {
             const [ a0, a1, a2 ] = args;
             return handler.getCrashInfo( a0, a1, a2 );
         }
-        case 42 /* Runtime.getGdtInventory */: {
+        case 45 /* Runtime.getGdtInventory */: {
             const [ a0, a1, a2 ] = args;
             return handler.getGdtInventory( a0, a1, a2 );
         }
-        case 43 /* Runtime.getOverworldItems */: {
+        case 46 /* Runtime.getOverworldItems */: {
             const [ a0, a1, a2 ] = args;
             return handler.getOverworldItems( a0, a1, a2 );
         }
-        case 44 /* Runtime.getParserDiagnostics */: {
+        case 47 /* Runtime.getParserDiagnostics */: {
             const [ a0 ] = args;
             return handler.getParserDiagnostics( a0 );
         }
-        case 45 /* Runtime.getPouchList */: {
+        case 48 /* Runtime.getPouchList */: {
             const [ a0, a1, a2 ] = args;
             return handler.getPouchList( a0, a1, a2 );
         }
-        case 46 /* Runtime.getRuntimeDiagnostics */: {
+        case 49 /* Runtime.getRuntimeDiagnostics */: {
             const [ a0, a1, a2 ] = args;
             return handler.getRuntimeDiagnostics( a0, a1, a2 );
         }
-        case 47 /* Runtime.getSaveInventory */: {
+        case 50 /* Runtime.getSaveInventory */: {
             const [ a0, a1, a2, a3 ] = args;
             return handler.getSaveInventory( a0, a1, a2, a3 );
         }
-        case 48 /* Runtime.getSaveNames */: {
+        case 51 /* Runtime.getSaveNames */: {
             const [ a0, a1, a2 ] = args;
             return handler.getSaveNames( a0, a1, a2 );
         }
-        case 49 /* Runtime.getSemanticTokens */: {
+        case 52 /* Runtime.getSemanticTokens */: {
             const [ a0, a1, a2 ] = args;
             return handler.getSemanticTokens( a0, a1, a2 );
         }
-        case 50 /* Runtime.getStepBytePositions */: {
+        case 53 /* Runtime.getStepBytePositions */: {
             const [ a0 ] = args;
             return handler.getStepBytePositions( a0 );
         }
-        case 51 /* Runtime.getStepFromPos */: {
+        case 54 /* Runtime.getStepFromPos */: {
             const [ a0, a1 ] = args;
             return handler.getStepFromPos( a0, a1 );
         }
-        case 52 /* Runtime.initialize */: {
+        case 55 /* Runtime.getStepRuns */: {
+            const [ a0, a1, a2 ] = args;
+            return handler.getStepRuns( a0, a1, a2 );
+        }
+        case 56 /* Runtime.getWatchHits */: {
+            const [ a0, a1, a2 ] = args;
+            return handler.getWatchHits( a0, a1, a2 );
+        }
+        case 57 /* Runtime.initialize */: {
             const [ a0 ] = args;
             return handler.initialize( a0 );
         }
-        case 53 /* Runtime.resolveItemIdent */: {
+        case 58 /* Runtime.resolveItemIdent */: {
             const [ a0 ] = args;
             return handler.resolveItemIdent( a0 );
         }
+        case 59 /* Runtime.setIncludedScripts */: {
+            const [ a0 ] = args;
+            return handler.setIncludedScripts( a0 );
+        }
+        case 60 /* Runtime.setWatchpoints */: {
+            const [ a0 ] = args;
+            return handler.setWatchpoints( a0 );
+        }
     } return Promise.resolve({ err: { code: "UnknownFunction" } }); }) as WxBusRecvHandler;
 };
\ No newline at end of file
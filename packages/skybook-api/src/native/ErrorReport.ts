@@ -8,4 +8,15 @@ export type ErrorReport<T> = {
     span: [number, number];
     isWarning: boolean;
     error: T;
+    /**
+     * Suggested replacements for the text in the span, best first.
+     *
+     * For example, similar item names when an item cannot be resolved
+     */
+    suggestions: Array<string>;
+    /**
+     * The script the span is in. 0 is the main script, other values
+     * are scripts included with `include`
+     */
+    file: number;
 };
@@ -52,4 +52,8 @@ export type InvView_GdtInfo = {
      * If the Paraglider was picker up
      */
     isParagliderObtained: boolean;
+    /**
+     * Number of rupees (CurrentRupee)
+     */
+    rupees: number;
 };
@@ -1,6 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { Category } from "./Category";
 import type { MetaValue } from "./MetaValue";
+import type { SyntaxExpectation } from "./SyntaxExpectation";
 
 /**
  * Error type for the parser
@@ -35,4 +36,27 @@ export type ParserError =
     | { type: "GdtTypeConflict" }
     | { type: "GdtInvalidIndex"; data: number }
     | { type: "GdtMissingVecComp" }
-    | { type: "InvalidEquipmentSlotNum"; data: [Category, number] };
+    | { type: "InvalidEquipmentSlotNum"; data: [Category, number] }
+    | { type: "InvalidDuration"; data: number }
+    | { type: "InvalidDurationUnit"; data: string }
+    | { type: "InvalidReceiveSource"; data: string }
+    | { type: "InvalidDlcVersion"; data: number }
+    | { type: "UndefinedBlock"; data: string }
+    | { type: "DuplicateBlock"; data: string }
+    | { type: "NestedBlock" }
+    | { type: "BlockArgCount"; data: [string, number, number] }
+    | { type: "UnboundParam"; data: string }
+    | { type: "BlockTooDeep"; data: number }
+    | { type: "TooManyExpandedSteps"; data: number }
+    | { type: "InvalidRepeatCount"; data: number }
+    | { type: "IncludeNotFound"; data: string }
+    | { type: "IncludeCycle"; data: string }
+    | { type: "IncludeHasErrors"; data: string }
+    | { type: "NestedInclude" }
+    | { type: "DuplicateLabel"; data: string }
+    | { type: "NestedLabel" }
+    | { type: "UndefinedVariable"; data: string }
+    | { type: "DivideByZero" }
+    | { type: "NestedLet" }
+    | { type: "ElseNotLast" }
+    | { type: "SyntaxExpected"; data: [SyntaxExpectation, string] };
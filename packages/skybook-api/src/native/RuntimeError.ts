@@ -5,7 +5,9 @@ import type { Category } from "./Category";
  * Error type for the runtime
  */
 export type RuntimeError =
+    | { type: "ArmorMaxStar" }
     | { type: "CannotAutoSwitchScreen" }
+    | { type: "CannotCheckCondition" }
     | { type: "CannotDoWhileHoldingInInventory" }
     | { type: "CannotDoWhileHoldingInOverworld" }
     | { type: "CannotFindGroundItem" }
@@ -22,9 +24,14 @@ export type RuntimeError =
     | { type: "CannotUseCategory" }
     | { type: "CannotUseMore"; data: number }
     | { type: "Crash" }
+    | { type: "EquipmentAutoEquipped"; data: string }
+    | { type: "EquipmentBroke"; data: string }
     | { type: "Executor" }
+    | { type: "GroundItemDespawned"; data: string }
     | { type: "InaccurateAllBut" }
+    | { type: "InvalidDlcVersion"; data: number }
     | { type: "InvalidDpadType" }
+    | { type: "InvalidFeature"; data: string }
     | { type: "InvalidGdtArrayIndex"; data: [string, string, number] }
     | { type: "InvalidItemTarget" }
     | { type: "InvalidPromptTarget" }
@@ -34,24 +41,34 @@ export type RuntimeError =
     | { type: "ItemMismatchCategory"; data: [string, Category] }
     | { type: "NoArrowsToShoot" }
     | { type: "NoManualSave" }
+    | { type: "NotArrow" }
     | { type: "NotDroppable" }
     | { type: "NotEatable" }
+    | { type: "NotEnoughCurrency"; data: [string, number, number] }
     | { type: "NotEnoughForAllBut"; data: [number, number] }
+    | { type: "NotEnoughMaterials"; data: [string, number, number] }
+    | { type: "NotEnoughRupees"; data: [number, number] }
     | { type: "NotEquipment" }
     | { type: "NotEquippedInOverworld" }
     | { type: "NotHoldable" }
     | { type: "NotHolding" }
     | { type: "NotRightScreen" }
     | { type: "NotSellable"; data: string }
+    | { type: "NotSoldByVendor"; data: string }
+    | { type: "NotUpgradable"; data: string }
     | { type: "OperationNotComplete" }
     | { type: "PositionSpecNotAllowed" }
+    | { type: "PouchFullyExpanded" }
+    | { type: "PouchNotExpandable"; data: Category }
     | { type: "PreviousClosed" }
     | { type: "PreviousCrash" }
     | { type: "ReloadFail" }
+    | { type: "RequiresDlc"; data: [number, number] }
     | { type: "SaveNotFound"; data: string }
     | { type: "TooManyIterations" }
     | { type: "Uninitialized" }
     | { type: "Unimplemented" }
+    | { type: "UnknownVendorPrice"; data: string }
     | { type: "UselessAmountForOverworldEquipment" }
     | { type: "UselessMetaForOverworldEquipment" }
     | { type: "UselessItemMatchProp" }
@@ -10,6 +10,10 @@ export type RuntimeInitError =
     | { type: "BadImage" }
     | { type: "InvalidStackStart" }
     | { type: "InvalidPmdmAddr" }
+    | { type: "InvalidFeature"; data: string }
+    | { type: "InvalidSymbolMap"; data: string }
+    | { type: "InvalidTraceRange"; data: string }
+    | { type: "InvalidWatchRange"; data: string }
     | { type: "ProgramStartMismatch"; data: [string, string] }
     | { type: "HeapTooBig" }
     | { type: "InitializeProcess" };
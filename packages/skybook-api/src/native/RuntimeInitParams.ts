@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RuntimeTraceParams } from "./RuntimeTraceParams";
 
 /**
  * Parameters for initializing a custom image
@@ -46,4 +47,27 @@ export type RuntimeInitParams = {
      * Unspecified (empty string) means using the internal default
      */
     pmdmAddr: string;
+    /**
+     * BlueFlame features to turn on or off, on top of the defaults
+     *
+     * Each entry is a feature name in kebab-case (for example, `check-stack-corruption`).
+     * Prefix the name with `-` to turn the feature off.
+     *
+     * Unspecified (empty) means using the default features
+     */
+    features: Array<string>;
+    /**
+     * Contents of a symbol map for the main module (CSV, IDA `.map`
+     * or Ghidra export), used to show function names in crash reports
+     *
+     * Unspecified (empty string) means using the symbol map packed
+     * in the image, if any
+     */
+    symbolMap: string;
+    /**
+     * Structured execution trace to record when executing the steps
+     *
+     * Unspecified means no trace is recorded
+     */
+    trace: RuntimeTraceParams;
 };
@@ -0,0 +1,25 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What to record in the structured execution trace, see `blueflame::trace`
+ */
+export type RuntimeTraceParams = {
+    /**
+     * Record function calls and returns
+     */
+    calls: boolean;
+    /**
+     * Record GDT flag accesses
+     */
+    gdt: boolean;
+    /**
+     * Record memory writes to these address ranges
+     *
+     * Each range looks like 0xSTART-0xEND, where END is exclusive
+     */
+    memWrites: Array<string>;
+    /**
+     * Record the trace as JSONL instead of the compact binary format
+     */
+    jsonl: boolean;
+};
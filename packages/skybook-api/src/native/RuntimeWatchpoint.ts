@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RuntimeWatchKind } from "./RuntimeWatchKind";
+
+/**
+ * A memory range to watch while running the steps, see `Runtime::set_watchpoints`
+ */
+export type RuntimeWatchpoint = {
+    /**
+     * Physical address range, looks like 0xSTART-0xEND, where END is exclusive
+     */
+    range: string;
+    /**
+     * Which accesses to watch
+     */
+    kind: RuntimeWatchKind;
+    /**
+     * Crash the game when the watchpoint is hit
+     */
+    breakOnHit: boolean;
+};
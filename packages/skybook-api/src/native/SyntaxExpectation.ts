@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What is expected after a command, when the syntax is wrong
+ */
+export type SyntaxExpectation =
+    | "ItemList"
+    | "Item"
+    | "Category"
+    | "Number"
+    | "Name"
+    | "Path"
+    | "Condition"
+    | "Properties"
+    | "FlagName";
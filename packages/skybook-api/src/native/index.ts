@@ -21,4 +21,8 @@ export * from "./Pointer.ts";
 export * from "./RuntimeError.ts";
 export * from "./RuntimeInitError.ts";
 export * from "./RuntimeInitParams.ts";
+export * from "./RuntimeTraceParams.ts";
 export * from "./RuntimeViewError.ts";
+export * from "./RuntimeWatchKind.ts";
+export * from "./RuntimeWatchpoint.ts";
+export * from "./SyntaxExpectation.ts";
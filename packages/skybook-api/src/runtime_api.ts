@@ -9,13 +9,16 @@ import type {
     MaybeAborted,
     ParserError,
     RuntimeError,
+    RuntimeInitError,
     RuntimeViewError,
+    RuntimeWatchpoint,
 } from "./native";
 import type {
     ItemSearchResult,
     RuntimeWorkerInitArgs,
     RuntimeWorkerInitOutput,
     RuntimeWorkerInitError,
+    StepRunEntry,
 } from "./types.ts";
 
 /**
@@ -29,6 +32,21 @@ export interface Runtime {
         args: RuntimeWorkerInitArgs,
     ): WxPromise<Result<RuntimeWorkerInitOutput, RuntimeWorkerInitError>>;
 
+    /**
+     * Replace the memory watchpoints. The next run executes all the steps again
+     * to record the hits, which can be read with getWatchHits()
+     */
+    setWatchpoints(
+        watchpoints: RuntimeWatchpoint[],
+    ): WxPromise<Result<void, RuntimeInitError>>;
+
+    /**
+     * Replace the scripts that can be included with `include "PATH"`,
+     * as a map from the path to the content of the script.
+     * The next parse and run use the new scripts
+     */
+    setIncludedScripts(scripts: Record<string, string>): WxPromise<void>;
+
     /**
      * Resolve an item identifier search query to a list of items, ordered by score (best first).
      * Returns an empty list if no items are found.
@@ -87,6 +105,18 @@ export interface Runtime {
         bytePos: number,
     ): WxPromise<MaybeAborted<ErrorReport<RuntimeError>[]>>;
 
+    /**
+     * Run the script and get how each step was run, up to and including
+     * the step containing the bytePos. See StepRunEntry
+     *
+     * The taskId should be a UUID, and can be passed into abortTask() to abort this run
+     */
+    getStepRuns(
+        script: string,
+        taskId: string,
+        bytePos: number,
+    ): WxPromise<MaybeAborted<StepRunEntry[]>>;
+
     /**
      * Execute the script if not up-to-date, and return the pouch inventory list view
      * at the byte offset `pos` in the script.
@@ -136,6 +166,19 @@ export interface Runtime {
         pos: number,
     ): WxPromise<MaybeAborted<string>>;
 
+    /**
+     * Execute the script if not up-to-date, and return the memory accesses that hit
+     * watchpoints in the step at the byte offset `pos` in the script, formatted with
+     * the stack traces. See setWatchpoints()
+     *
+     * The taskId should be a UUID, and can be passed into abortTask() to abort this run
+     */
+    getWatchHits(
+        script: string,
+        taskId: string,
+        pos: number,
+    ): WxPromise<MaybeAborted<string[]>>;
+
     /**
      * Execute the script if not up-to-date, and return the list of save names at the
      * byte offset `pos` in the script. The list does not include the manual save.
@@ -22,6 +22,23 @@ export type ItemSearchResult = {
     cookEffect: number;
 };
 
+/**
+ * One entry in the step list, with the index of the actual step
+ *
+ * Command entries include all the steps until the step of the next entry
+ */
+export type StepDisplayEntry =
+    | {
+          type: "text";
+          /** Text from a note or page title, displayed before the step */
+          text: string;
+          step: number;
+      }
+    | {
+          type: "command";
+          step: number;
+      };
+
 /** Diagnostic type for the script */
 export type Diagnostic = {
     /** (Localized) message to display */
@@ -34,6 +51,16 @@ export type Diagnostic = {
      * Whether this diagnostic is only a warning. If false, it should be treated as an error
      */
     isWarning: boolean;
+    /**
+     * Whether this diagnostic is only information, like which branch of an `if`
+     * was taken. If true, `isWarning` is ignored
+     */
+    isInfo: boolean;
+    /**
+     * Suggested replacements for the text in the range, best first.
+     * Can be offered as quick fixes
+     */
+    suggestions: string[];
 };
 
 /** Args for initializing the runtime */
@@ -81,3 +108,26 @@ export type RuntimeWorkerInitError =
  * - read-only: edits not allowed
  */
 export type SessionMode = "local" | "edit-only" | "read-only";
+
+/**
+ * How a step was run
+ *
+ * Steps in a branch of an `if` that was not taken are skipped.
+ * For `if` steps, `branch` is the index of the branch taken, where
+ * the `else` branch is the number of conditions. It is `null` if the
+ * conditions could not be checked
+ */
+export type StepRunEntry =
+    | { type: "executed" }
+    | { type: "skipped" }
+    | { type: "branch"; branch: number | null };
+
+/** Consecutive steps that share the same note */
+export type NoteSection = {
+    /** The note in markdown */
+    note: string;
+    /** Index of the first step in the section */
+    start: number;
+    /** Index after the last step in the section */
+    end: number;
+};